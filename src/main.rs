@@ -3,7 +3,7 @@ use actix_web::{web, get, App, Error, HttpRequest, HttpResponse, HttpServer, Res
 use actix_web_actors::ws;
 use actix::{Actor, Addr};
 use crate::websocket::PlayerSocket;
-use crate::poker::Table;
+use crate::poker::{Table, TableConfig};
 use crate::poker::GameActionPayload;
 use crate::poker::MessageManager;
 use crate::poker::GameActionResponse;
@@ -67,7 +67,12 @@ fn init() -> ChannelData {
     // Game loop
     tokio::task::spawn(async move { // gotta benchmark to see if block_in_place provides any speedup
         // in reality, it makes sense for this thread to be async since we are waiting for I/O
-        let table = Arc::new(Mutex::new(Table::new()));
+        let mut table = Table::with_config(TableConfig::new()).expect("Default table config is invalid");
+        // hand histories are only kept if the server is told where to put them
+        if let Ok(dir) = std::env::var("HAND_HISTORY_DIR") {
+            table.set_hand_history_dir(dir.into());
+        }
+        let table = Arc::new(Mutex::new(table));
         Table::run_loop(table, &mut gl_rx, res_tx).await;
     });
 
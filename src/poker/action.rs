@@ -7,6 +7,7 @@ use actix::{Addr, Recipient};
 use tokio::sync::mpsc::{UnboundedSender, UnboundedReceiver};
 
 use crate::websocket::{WebsocketResponse};
+use crate::poker::game_actions::GameResponse;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, Map, json};
@@ -14,7 +15,7 @@ use serde_repr::{Deserialize_repr};
 
 // These types are placeholder types that will be to and from the game
 pub type GameActionPayload = ActionRequest;   // Sent to the game
-pub type GameActionResponse = i64;            // Received from the game
+pub type GameActionResponse = GameResponse;   // Received from the game
 
 #[derive(Deserialize_repr, Debug)]
 #[repr(u8)]
@@ -27,6 +28,23 @@ pub enum ActionType {
      StopGame = 6,
      PauseGame = 7,
      ResumeGame = 8,
+     SitDown = 9,
+     StandUp = 10,
+     ReserveSeat = 11,
+     JoinWaitingList = 12,
+     LeaveWaitingList = 13,
+     SitOut = 14,
+     SitIn = 15,
+     UpdateSettings = 16,
+     VoteBombPot = 17,
+     RunIt = 18,
+     ShowCards = 19,
+     UpdateTableConfig = 20,
+     AddChips = 21,
+     GetLedger = 22,
+     UndoAction = 23,
+     RewindStreet = 24,
+     Showdown = 25,
 }
 
 #[derive(Debug)]
@@ -57,7 +75,31 @@ impl MessageManager {
 
     pub async fn listener(addr: Addr<MessageManager>, rx: &mut UnboundedReceiver<GameActionResponse>) {
         while let Some(msg) = rx.recv().await {
-            // addr.send(BroadcastState{});
+            addr.do_send(msg);
+        }
+    }
+
+    // Responses are serialized as externally tagged enums ({"Variant": {...}}), so the variant name becomes the action type
+    fn to_websocket_response<T: Serialize>(res: &T) -> WebsocketResponse {
+        match serde_json::to_value(res) {
+            Ok(Value::Object(map)) if map.len() == 1 => {
+                let (action_type, data) = map.into_iter().next().unwrap();
+                WebsocketResponse {
+                    action_type: action_type,
+                    error: None,
+                    data: data,
+                }
+            },
+            Ok(value) => WebsocketResponse {
+                action_type: "Unknown".to_string(),
+                error: None,
+                data: value,
+            },
+            Err(err) => WebsocketResponse {
+                action_type: "Serialize error".to_string(),
+                error: Some(err.to_string()),
+                data: json!({}),
+            },
         }
     }
 }
@@ -95,7 +137,32 @@ impl Handler<ActionRequest> for MessageManager {
     type Result = ();
 
     fn handle(&mut self, msg: ActionRequest, _: &mut Self::Context) {
+        // forward to the table
+        let _ = self.tx.send(msg);
+    }
+}
+
+impl Handler<GameResponse> for MessageManager {
+    type Result = ();
 
+    fn handle(&mut self, msg: GameResponse, _: &mut Self::Context) {
+        match msg {
+            GameResponse::Broadcast(res) => {
+                for s in self.sockets.values() {
+                    s.do_send(MessageManager::to_websocket_response(&res));
+                }
+            },
+            GameResponse::Multicast(id, res) => {
+                for (_, s) in self.sockets.iter().filter(|(socket_id, _)| **socket_id != id) {
+                    s.do_send(MessageManager::to_websocket_response(&res));
+                }
+            },
+            GameResponse::SingleResponse(id, res) => {
+                if let Some(s) = self.sockets.get(&id) {
+                    s.do_send(MessageManager::to_websocket_response(&res));
+                }
+            },
+        }
     }
 }
 
@@ -0,0 +1,17 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+// Everything that happened during a hand, in order
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub enum LoggedAction {
+    HandStarted { hand_number: u64, btn_idx: usize, stacks: BTreeMap<usize, u64> },
+    Posted { position: usize, ante: u64, live: u64, dead: u64 },    // everything put in before the deal, the live part being the player's bet
+    BringIn { position: usize, amount: u64 },                       // forced bet of the worst door card in stud games
+    Fold { position: usize },
+    CheckCall { position: usize },
+    BetRaise { position: usize, amount: u64 },
+    Draw { position: usize, discard_count: usize },
+    StreetEnded { street_idx: usize },
+    HandEnded { winnings: HashMap<usize, u64> },
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::poker::LoggedAction;
+
+// Everything known about the table when chips went missing (or appeared out of nowhere)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct AuditReport {
+    pub hand_number: u64,
+    pub checkpoint: String,     // where in the hand the check failed
+    pub expected_total: u64,
+    pub actual_total: u64,
+    pub stacks: BTreeMap<usize, u64>,
+    pub pot_total: u64,
+    pub rake: u64,
+    pub action_log: Vec<LoggedAction>,
+}
+
+// Makes sure the chips of everyone dealt into a hand only ever move between stacks, the pot and the rake
+#[derive(Debug, Clone)]
+pub struct ChipAuditor {
+    hand_number: u64,
+    starting_total: u64,    // chips of every player dealt in when the hand started
+}
+
+impl ChipAuditor {
+    pub fn new() -> ChipAuditor {
+        ChipAuditor {
+            hand_number: 0,
+            starting_total: 0,
+        }
+    }
+
+    pub fn start_hand(&mut self, hand_number: u64, stacks: &BTreeMap<usize, u64>) {
+        self.hand_number = hand_number;
+        self.starting_total = stacks.values().sum();
+    }
+
+    pub fn get_starting_total(& self) -> u64 {
+        self.starting_total
+    }
+
+    pub fn verify(& self, checkpoint: &str, stacks: BTreeMap<usize, u64>, pot_total: u64, rake: u64, action_log: &Vec<LoggedAction>) -> Result<(), AuditReport> {
+        let actual_total = stacks.values().sum::<u64>() + pot_total + rake;
+        if actual_total == self.starting_total {
+            return Ok(());
+        }
+
+        Err(AuditReport {
+            hand_number: self.hand_number,
+            checkpoint: checkpoint.to_string(),
+            expected_total: self.starting_total,
+            actual_total: actual_total,
+            stacks: stacks,
+            pot_total: pot_total,
+            rake: rake,
+            action_log: action_log.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chips_conserved() {
+        let mut auditor = ChipAuditor::new();
+        auditor.start_hand(1, &BTreeMap::from([(0, 200), (1, 100)]));
+
+        assert_eq!(300, auditor.get_starting_total());
+        assert_eq!(Ok(()), auditor.verify("Street 0", BTreeMap::from([(0, 180), (1, 80)]), 40, 0, &Vec::new()));
+        assert_eq!(Ok(()), auditor.verify("Settlement", BTreeMap::from([(0, 237), (1, 60)]), 0, 3, &Vec::new()));
+    }
+
+    #[test]
+    fn chips_created() {
+        let mut auditor = ChipAuditor::new();
+        auditor.start_hand(4, &BTreeMap::from([(0, 200), (1, 100)]));
+
+        let log = vec![LoggedAction::Fold { position: 1 }];
+        let report = auditor.verify("Settlement", BTreeMap::from([(0, 202), (1, 100)]), 0, 0, &log).unwrap_err();
+
+        assert_eq!(4, report.hand_number);
+        assert_eq!(300, report.expected_total);
+        assert_eq!(302, report.actual_total);
+        assert_eq!(log, report.action_log);
+    }
+}
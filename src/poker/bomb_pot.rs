@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BombPotSchedule {
+    Never,          // bomb pots only happen when players vote for one
+    EveryHands(u64),
+    EveryOrbit,     // once every player has had the button since the last bomb pot
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BombPotSettings {
+    pub schedule: BombPotSchedule,
+    pub ante_amt: u64,      // amount every player puts in. No blinds are posted in a bomb pot
+    pub allow_votes: bool,  // players can vote for the next hand to be a bomb pot
+}
+
+impl BombPotSettings {
+    pub fn new() -> BombPotSettings {
+        BombPotSettings {
+            schedule: BombPotSchedule::Never,
+            ante_amt: 10,
+            allow_votes: true,
+        }
+    }
+
+    // Is the next hand a scheduled bomb pot, given the number of normal hands dealt since the last one
+    pub fn is_scheduled(& self, hands_since_bomb_pot: u64, player_count: usize) -> bool {
+        match self.schedule {
+            BombPotSchedule::Never => false,
+            BombPotSchedule::EveryHands(hands) => hands > 0 && hands_since_bomb_pot + 1 >= hands,
+            BombPotSchedule::EveryOrbit => player_count > 0 && hands_since_bomb_pot >= player_count as u64,
+        }
+    }
+}
+
+// A majority of the players that are not away is needed for a bomb pot
+pub fn get_bomb_pot_votes_needed(player_count: usize) -> usize {
+    player_count / 2 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(schedule: BombPotSchedule) -> BombPotSettings {
+        BombPotSettings {
+            schedule: schedule,
+            ante_amt: 10,
+            allow_votes: true,
+        }
+    }
+
+    #[test]
+    fn never_scheduled() {
+        let settings = settings(BombPotSchedule::Never);
+        assert!((0..100).all(|hands| !settings.is_scheduled(hands, 6)));
+    }
+
+    #[test]
+    fn every_n_hands() {
+        let settings = settings(BombPotSchedule::EveryHands(5));
+        assert!(!settings.is_scheduled(0, 6));
+        assert!(!settings.is_scheduled(3, 6));
+        assert!(settings.is_scheduled(4, 6));
+
+        assert!(!BombPotSettings { schedule: BombPotSchedule::EveryHands(0), ..settings }.is_scheduled(10, 6));
+    }
+
+    #[test]
+    fn every_orbit() {
+        let settings = settings(BombPotSchedule::EveryOrbit);
+        assert!(!settings.is_scheduled(5, 6));
+        assert!(settings.is_scheduled(6, 6));
+        assert!(settings.is_scheduled(3, 3));
+    }
+
+    #[test]
+    fn votes_needed() {
+        assert_eq!(2, get_bomb_pot_votes_needed(2));
+        assert_eq!(2, get_bomb_pot_votes_needed(3));
+        assert_eq!(4, get_bomb_pot_votes_needed(6));
+    }
+}
@@ -0,0 +1,194 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSettings {
+    pub action_secs: u64,               // time every player gets per action before dipping into their time bank
+    pub starting_time_bank_secs: u64,   // time bank a player has when sitting down
+    pub max_time_bank_secs: u64,
+    pub refill_secs: u64,               // amount added to every time bank ...
+    pub refill_every_hands: u64,        // ... every this many hands (0 disables refills)
+}
+
+impl ClockSettings {
+    pub fn new() -> ClockSettings {
+        ClockSettings {
+            action_secs: 30,
+            starting_time_bank_secs: 60,
+            max_time_bank_secs: 120,
+            refill_secs: 10,
+            refill_every_hands: 10,
+        }
+    }
+
+    pub fn get_action_time(& self) -> Duration {
+        Duration::from_secs(self.action_secs)
+    }
+
+    pub fn get_starting_time_bank(& self) -> Duration {
+        Duration::from_secs(self.starting_time_bank_secs)
+    }
+
+    // Time bank after the refill for the given hand number has been applied
+    pub fn refill_time_bank(& self, time_bank: Duration, hand_number: u64) -> Duration {
+        if self.refill_every_hands == 0 || hand_number == 0 || hand_number % self.refill_every_hands != 0 {
+            return time_bank;
+        }
+        std::cmp::min(time_bank + Duration::from_secs(self.refill_secs), Duration::from_secs(self.max_time_bank_secs))
+    }
+}
+
+// Keeps track of how long the player in action has been thinking. Time spent paused is not counted
+#[derive(Debug)]
+pub struct ActionClock {
+    turn_started_at: Option<Instant>,   // None if no turn is being timed
+    paused_at: Option<Instant>,
+    paused_for: Duration,               // total time paused during the current turn
+    is_using_time_bank: bool,
+}
+
+impl ActionClock {
+    pub fn new() -> ActionClock {
+        ActionClock {
+            turn_started_at: None,
+            paused_at: None,
+            paused_for: Duration::ZERO,
+            is_using_time_bank: false,
+        }
+    }
+
+    // Starts timing a new turn. Keeps the clock frozen if it was paused
+    pub fn start(&mut self, now: Instant) {
+        self.turn_started_at = Some(now);
+        self.paused_at = self.paused_at.map(|_| now);
+        self.paused_for = Duration::ZERO;
+        self.is_using_time_bank = false;
+    }
+
+    // Stops timing the current turn and returns how long the turn took
+    pub fn stop(&mut self, now: Instant) -> Duration {
+        let elapsed = self.elapsed(now);
+        self.turn_started_at = None;
+        self.paused_for = Duration::ZERO;
+        self.is_using_time_bank = false;
+        elapsed
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(now);
+        }
+    }
+
+    pub fn resume(&mut self, now: Instant) {
+        if let Some(paused_at) = self.paused_at.take() {
+            if self.turn_started_at.is_some() {
+                self.paused_for += now.saturating_duration_since(paused_at);
+            }
+        }
+    }
+
+    pub fn is_running(& self) -> bool {
+        self.turn_started_at.is_some()
+    }
+
+    pub fn is_paused(& self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    pub fn is_using_time_bank(& self) -> bool {
+        self.is_using_time_bank
+    }
+
+    pub fn use_time_bank(&mut self) {
+        self.is_using_time_bank = true;
+    }
+
+    pub fn elapsed(& self, now: Instant) -> Duration {
+        match self.turn_started_at {
+            Some(started_at) => {
+                let end = self.paused_at.unwrap_or(now);
+                end.saturating_duration_since(started_at).saturating_sub(self.paused_for)
+            },
+            None => Duration::ZERO,
+        }
+    }
+
+    // (action time left, time bank left) for a player with the given limits
+    pub fn get_time_left(& self, now: Instant, action_time: Duration, time_bank: Duration) -> (Duration, Duration) {
+        let elapsed = self.elapsed(now);
+        let action_time_left = action_time.saturating_sub(elapsed);
+        let time_bank_left = time_bank.saturating_sub(elapsed.saturating_sub(action_time));
+        (action_time_left, time_bank_left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_while_running() {
+        let start = Instant::now();
+        let mut clock = ActionClock::new();
+
+        assert_eq!(Duration::ZERO, clock.elapsed(start));
+
+        clock.start(start);
+        assert!(clock.is_running());
+        assert_eq!(Duration::from_secs(12), clock.elapsed(start + Duration::from_secs(12)));
+        assert_eq!(Duration::from_secs(15), clock.stop(start + Duration::from_secs(15)));
+        assert!(!clock.is_running());
+    }
+
+    #[test]
+    fn pause_freezes_clock() {
+        let start = Instant::now();
+        let mut clock = ActionClock::new();
+
+        clock.start(start);
+        clock.pause(start + Duration::from_secs(5));
+        assert_eq!(Duration::from_secs(5), clock.elapsed(start + Duration::from_secs(100)));
+
+        clock.resume(start + Duration::from_secs(100));
+        assert_eq!(Duration::from_secs(8), clock.elapsed(start + Duration::from_secs(103)));
+    }
+
+    #[test]
+    fn turn_started_while_paused() {
+        let start = Instant::now();
+        let mut clock = ActionClock::new();
+
+        clock.pause(start);
+        clock.start(start + Duration::from_secs(2));
+        assert!(clock.is_paused());
+        assert_eq!(Duration::ZERO, clock.elapsed(start + Duration::from_secs(60)));
+
+        clock.resume(start + Duration::from_secs(60));
+        assert_eq!(Duration::from_secs(4), clock.elapsed(start + Duration::from_secs(64)));
+    }
+
+    #[test]
+    fn time_left_uses_time_bank() {
+        let start = Instant::now();
+        let mut clock = ActionClock::new();
+        let action_time = Duration::from_secs(30);
+        let time_bank = Duration::from_secs(20);
+
+        clock.start(start);
+        assert_eq!((Duration::from_secs(20), time_bank), clock.get_time_left(start + Duration::from_secs(10), action_time, time_bank));
+        assert_eq!((Duration::ZERO, Duration::from_secs(15)), clock.get_time_left(start + Duration::from_secs(35), action_time, time_bank));
+        assert_eq!((Duration::ZERO, Duration::ZERO), clock.get_time_left(start + Duration::from_secs(60), action_time, time_bank));
+    }
+
+    #[test]
+    fn time_bank_refill() {
+        let settings = ClockSettings::new();
+
+        assert_eq!(Duration::from_secs(50), settings.refill_time_bank(Duration::from_secs(50), 9));
+        assert_eq!(Duration::from_secs(60), settings.refill_time_bank(Duration::from_secs(50), 10));
+        assert_eq!(Duration::from_secs(120), settings.refill_time_bank(Duration::from_secs(115), 20));
+    }
+}
@@ -1,16 +1,33 @@
-use super::requests::{BetAction, DrawAction};
+use super::requests::{BetAction, DrawAction, RunItAction, ShowdownAction};
 use super::responses::{PersonalFoldResponse, PublicFoldResponse,
                     PersonalCheckCallResponse, PublicCheckCallResponse,
                     PersonalBetRaiseResponse, PublicBetRaiseResponse,
                     PersonalDrawResponse, PublicDrawResponse,
-                    StartingHandResponse, GameState};
+                    PublicSitDownResponse, PersonalSitDownResponse,
+                    PublicStandUpResponse, PersonalStandUpResponse,
+                    PublicReserveSeatResponse, PersonalReserveSeatResponse,
+                    PersonalWaitingListResponse,
+                    ActionClockResponse, PublicAwayResponse, PersonalAwayResponse,
+                    PersonalSettingsResponse, PublicBombPotVoteResponse, PersonalBombPotVoteResponse,
+                    RunItOfferResponse, PublicRunItResponse, PersonalRunItResponse,
+                    RunItDecisionResponse, RunoutResultResponse,
+                    PublicShowHandResponse, PublicMuckHandResponse, PersonalShowHandResponse,
+                    PersonalTableConfigResponse, PersonalAddChipsResponse, LedgerResponse,
+                    PersonalUndoResponse,
+                    StartingHandResponse, GameState, HandResultResponse};
 
+use crate::poker::{TableConfig, AuditReport};
+
+use actix::prelude::Message;
+use serde::Serialize;
 use uuid::Uuid;
 
 #[derive(Debug)]
 pub enum GameAction {
     Pot(Uuid, PotAction),
     Draw(Uuid, DrawAction),
+    RunIt(Uuid, RunItAction),
+    Showdown(Uuid, ShowdownAction),
 }
 
 #[derive(Debug)]
@@ -20,27 +37,48 @@ pub enum PotAction {
     BetRaise(BetAction),
 }
 
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
 pub enum GameResponse {
     Broadcast(Broadcast),
     Multicast(Uuid, Multicast), // multicast to everyone BUT Uuid
     SingleResponse(Uuid, SingleResponse),
 }
 
+#[derive(Serialize, Debug)]
 pub enum Broadcast {
     State(GameState),
+    HandResult(HandResultResponse),
+    ActionClock(ActionClockResponse),
+    BombPotVote(PublicBombPotVoteResponse),
+    RunItOffer(RunItOfferResponse),
+    RunItDecision(RunItDecisionResponse),
+    RunoutResult(RunoutResultResponse),
+    TableConfig(TableConfig),
+    AuditFailure(AuditReport),  // chips were created or destroyed, the table is halted
+    HandFailure(String),        // the hand could not go on, the table is halted
 }
 
 // Used to send responses to other clients
 // Should only contain public information (e.g. the cards drawn should not be multicasted, but the number of cards drawn should be)
+#[derive(Serialize, Debug)]
 pub enum Multicast {
     DrawResponse(PublicDrawResponse),
     BetRaiseResponse(PublicBetRaiseResponse),
     CheckCallResponse(PublicCheckCallResponse),
     FoldResponse(PublicFoldResponse),
-    
+
+    SitDownResponse(PublicSitDownResponse),
+    StandUpResponse(PublicStandUpResponse),
+    ReserveSeatResponse(PublicReserveSeatResponse),
+    AwayResponse(PublicAwayResponse),
+    RunItResponse(PublicRunItResponse),
+    ShowHandResponse(PublicShowHandResponse),
+    MuckHandResponse(PublicMuckHandResponse),
 }
 
 // single response types allow for sending back status errors and more private information
+#[derive(Serialize, Debug)]
 pub enum SingleResponse {
     State(GameState), // sends back game state if client requests it (prevents the need to send everyone)
 
@@ -49,5 +87,20 @@ pub enum SingleResponse {
     CheckCallResponse(PersonalCheckCallResponse),
     FoldResponse(PersonalFoldResponse),
 
+    SitDownResponse(PersonalSitDownResponse),
+    StandUpResponse(PersonalStandUpResponse),
+    ReserveSeatResponse(PersonalReserveSeatResponse),
+    WaitingListResponse(PersonalWaitingListResponse),
+    AwayResponse(PersonalAwayResponse),
+    SettingsResponse(PersonalSettingsResponse),
+    BombPotVoteResponse(PersonalBombPotVoteResponse),
+    RunItResponse(PersonalRunItResponse),
+    ShowHandResponse(PersonalShowHandResponse),
+    TableConfigResponse(PersonalTableConfigResponse),
+    AddChipsResponse(PersonalAddChipsResponse),
+    LedgerResponse(LedgerResponse),
+    UndoResponse(PersonalUndoResponse),
+    HandHistoryError(String),   // sent to the host when the hand history could not be written
+
     StartingHandResponse(StartingHandResponse),
 }
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+// Rebuy if the player has busted, top-up otherwise
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct AddChipsAction {
+    pub amount: u64,
+}
+
+impl TryFrom<Map<String, Value>> for AddChipsAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to AddChipsAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_add_chips() {
+        let value = json!({"amount": 150});
+        let action: AddChipsAction = AddChipsAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(AddChipsAction { amount: 150 }, action);
+    }
+}
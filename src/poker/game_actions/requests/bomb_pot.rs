@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct BombPotVoteAction {
+    pub vote: bool,     // false takes back an earlier vote
+}
+
+impl TryFrom<Map<String, Value>> for BombPotVoteAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to BombPotVoteAction fields")
+    }
+}
@@ -3,3 +3,24 @@ pub use self::bet_raise::BetAction;
 
 mod draw;
 pub use self::draw::DrawAction;
+
+mod seat;
+pub use self::seat::{SitDownAction, ReserveSeatAction, WaitingListAction, SitInAction};
+
+mod settings;
+pub use self::settings::PlayerSettingsAction;
+
+mod bomb_pot;
+pub use self::bomb_pot::BombPotVoteAction;
+
+mod run_it;
+pub use self::run_it::RunItAction;
+
+mod show_cards;
+pub use self::show_cards::ShowCardsAction;
+
+mod showdown;
+pub use self::showdown::ShowdownAction;
+
+mod add_chips;
+pub use self::add_chips::AddChipsAction;
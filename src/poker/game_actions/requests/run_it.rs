@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct RunItAction {
+    pub times: usize,   // number of runouts the player agrees to
+}
+
+impl TryFrom<Map<String, Value>> for RunItAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to RunItAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_run_it() {
+        let value = json!({"times": 2});
+        let action: RunItAction = RunItAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(RunItAction { times: 2 }, action);
+    }
+
+    #[test]
+    #[should_panic]
+    fn deserialize_run_it_negative() {
+        let value = json!({"times": -1});
+        let _: RunItAction = serde_json::from_value(value).expect("An error occurred!");
+    }
+}
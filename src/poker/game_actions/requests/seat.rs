@@ -0,0 +1,117 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SitDownAction {
+    pub position: usize,
+    pub name: String,
+    pub buy_in: u64,
+    #[serde(default)]
+    pub wait_for_bb: bool,  // wait for the big blind instead of posting one to be dealt in right away
+}
+
+impl TryFrom<Map<String, Value>> for SitDownAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to SitDownAction fields")
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ReserveSeatAction {
+    pub position: usize,
+}
+
+impl TryFrom<Map<String, Value>> for ReserveSeatAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to ReserveSeatAction fields")
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct WaitingListAction {
+    pub name: String,
+    pub buy_in: u64,
+}
+
+impl TryFrom<Map<String, Value>> for WaitingListAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to WaitingListAction fields")
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct SitInAction {
+    #[serde(default)]
+    pub wait_for_bb: bool,  // wait for the big blind instead of posting any missed blinds
+}
+
+impl TryFrom<Map<String, Value>> for SitInAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to SitInAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_sit_down() {
+        let value = json!({"position": 3, "name": "Player 3", "buyIn": 200});
+        let action: SitDownAction = serde_json::from_value(value).expect("An error occurred!");
+        let expected = SitDownAction {
+            position: 3,
+            name: "Player 3".to_string(),
+            buy_in: 200,
+            wait_for_bb: false,
+        };
+        assert_eq!(expected, action);
+    }
+
+    #[test]
+    #[should_panic]
+    fn deserialize_sit_down_missing_buy_in() {
+        let value = json!({"position": 3, "name": "Player 3"});
+        let _: SitDownAction = serde_json::from_value(value).expect("An error occurred!");
+    }
+
+    #[test]
+    fn deserialize_waiting_list() {
+        let value = json!({"name": "Player 7", "buyIn": 150});
+        let action: WaitingListAction = WaitingListAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        let expected = WaitingListAction {
+            name: "Player 7".to_string(),
+            buy_in: 150,
+        };
+        assert_eq!(expected, action);
+    }
+
+    #[test]
+    fn deserialize_sit_in() {
+        let value = json!({"waitForBb": true});
+        let action: SitInAction = SitInAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(SitInAction { wait_for_bb: true }, action);
+
+        let value = json!({});
+        let action: SitInAction = SitInAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(SitInAction { wait_for_bb: false }, action);
+    }
+}
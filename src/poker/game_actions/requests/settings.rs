@@ -0,0 +1,43 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+// Player preferences that apply from the next hand on. Settings that are left out are not changed
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct PlayerSettingsAction {
+    #[serde(default)]
+    pub will_straddle: Option<bool>,
+    #[serde(default)]
+    pub play_in_bomb_pots: Option<bool>,
+    #[serde(default)]
+    pub auto_muck: Option<bool>,
+    #[serde(default)]
+    pub auto_rebuy: Option<bool>,
+}
+
+impl TryFrom<Map<String, Value>> for PlayerSettingsAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to PlayerSettingsAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_settings() {
+        let value = json!({"willStraddle": true, "playInBombPots": false, "autoMuck": false, "autoRebuy": true});
+        let action: PlayerSettingsAction = serde_json::from_value(value).expect("An error occurred!");
+        assert_eq!(PlayerSettingsAction { will_straddle: Some(true), play_in_bomb_pots: Some(false), auto_muck: Some(false), auto_rebuy: Some(true) }, action);
+
+        let value = json!({});
+        let action: PlayerSettingsAction = serde_json::from_value(value).expect("An error occurred!");
+        assert_eq!(PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: None, auto_muck: None, auto_rebuy: None }, action);
+    }
+}
@@ -0,0 +1,32 @@
+use playing_cards::core::Card;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ShowCardsAction {
+    pub cards: Vec<Card>,   // cards from the player's hand to reveal
+}
+
+impl TryFrom<Map<String, Value>> for ShowCardsAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to ShowCardsAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_show_cards() {
+        let value = json!({"cards": ["As", "Kd"]});
+        let action: ShowCardsAction = ShowCardsAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(ShowCardsAction { cards: Card::vec_from_str("AsKd").unwrap() }, action);
+    }
+}
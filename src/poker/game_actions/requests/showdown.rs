@@ -0,0 +1,31 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ShowdownAction {
+    pub show: bool,     // show the hand at showdown, otherwise it is mucked
+}
+
+impl TryFrom<Map<String, Value>> for ShowdownAction {
+    type Error = &'static str;
+
+    fn try_from(map: Map<String, Value>) -> Result<Self, Self::Error> {
+        serde_json::from_value(Value::Object(map))
+            .map_err(|_| "String keys could not map to ShowdownAction fields")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn deserialize_showdown() {
+        let value = json!({"show": true});
+        let action: ShowdownAction = ShowdownAction::try_from(value.as_object().unwrap().clone()).expect("An error occurred!");
+        assert_eq!(ShowdownAction { show: true }, action);
+    }
+}
@@ -0,0 +1,42 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::poker::LedgerEntry;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalAddChipsResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    is_pending: bool,   // chips are added once the current hand is over
+}
+
+impl PersonalAddChipsResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, is_pending: bool) -> PersonalAddChipsResponse {
+        PersonalAddChipsResponse {
+            req_id: req_id,
+            status: status,
+            is_pending: is_pending,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct LedgerResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    entries: Vec<LedgerEntry>,  // empty unless the ledger was sent to the host
+}
+
+impl LedgerResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, entries: Vec<LedgerEntry>) -> LedgerResponse {
+        LedgerResponse {
+            req_id: req_id,
+            status: status,
+            entries: entries,
+        }
+    }
+}
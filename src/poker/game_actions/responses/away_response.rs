@@ -0,0 +1,38 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicAwayResponse {
+    position: usize,
+    is_away: bool,
+}
+
+impl PublicAwayResponse {
+    pub fn new(position: usize, is_away: bool) -> PublicAwayResponse {
+        PublicAwayResponse {
+            position: position,
+            is_away: is_away,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalAwayResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    is_away: bool,
+}
+
+impl PersonalAwayResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, is_away: bool) -> PersonalAwayResponse {
+        PersonalAwayResponse {
+            req_id: req_id,
+            status: status,
+            is_away: is_away,
+        }
+    }
+}
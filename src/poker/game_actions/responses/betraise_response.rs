@@ -10,10 +10,29 @@ pub struct PublicBetRaiseResponse {
     bet_amount: u64,
 }
 
+impl PublicBetRaiseResponse {
+    pub fn new(position: usize, bet_amount: u64) -> PublicBetRaiseResponse {
+        PublicBetRaiseResponse {
+            position: position,
+            bet_amount: bet_amount,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct PersonalBetRaiseResponse {
     msg_id: Uuid,
     status: StatusCode,
     bet_amount: u64,
-}
\ No newline at end of file
+}
+
+impl PersonalBetRaiseResponse {
+    pub fn new(msg_id: Uuid, status: StatusCode, bet_amount: u64) -> PersonalBetRaiseResponse {
+        PersonalBetRaiseResponse {
+            msg_id: msg_id,
+            status: status,
+            bet_amount: bet_amount,
+        }
+    }
+}
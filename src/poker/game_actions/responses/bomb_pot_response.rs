@@ -0,0 +1,38 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicBombPotVoteResponse {
+    votes: usize,
+    votes_needed: usize,
+    is_next_hand_bomb: bool,
+}
+
+impl PublicBombPotVoteResponse {
+    pub fn new(votes: usize, votes_needed: usize, is_next_hand_bomb: bool) -> PublicBombPotVoteResponse {
+        PublicBombPotVoteResponse {
+            votes: votes,
+            votes_needed: votes_needed,
+            is_next_hand_bomb: is_next_hand_bomb,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalBombPotVoteResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalBombPotVoteResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalBombPotVoteResponse {
+        PersonalBombPotVoteResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
@@ -9,9 +9,26 @@ pub struct PublicCheckCallResponse {
     position: usize,
 }
 
+impl PublicCheckCallResponse {
+    pub fn new(position: usize) -> PublicCheckCallResponse {
+        PublicCheckCallResponse {
+            position: position,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct PersonalCheckCallResponse {
     req_id: Uuid,
     status: StatusCode,
 }
+
+impl PersonalCheckCallResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalCheckCallResponse {
+        PersonalCheckCallResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
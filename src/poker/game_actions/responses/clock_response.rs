@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct ActionClockResponse {
+    position: usize,            // player whose clock is running
+    action_time_left_ms: u64,
+    time_bank_left_ms: u64,     // time bank is only used up once the action time is gone
+    is_paused: bool,
+}
+
+impl ActionClockResponse {
+    pub fn new(position: usize, action_time_left_ms: u64, time_bank_left_ms: u64, is_paused: bool) -> ActionClockResponse {
+        ActionClockResponse {
+            position: position,
+            action_time_left_ms: action_time_left_ms,
+            time_bank_left_ms: time_bank_left_ms,
+            is_paused: is_paused,
+        }
+    }
+}
@@ -13,6 +13,16 @@ pub struct PublicDrawResponse {
     cards_recieved: usize,
 }
 
+impl PublicDrawResponse {
+    pub fn new(position: usize, discard_count: usize, cards_recieved: usize) -> PublicDrawResponse {
+        PublicDrawResponse {
+            position: position,
+            discard_count: discard_count,
+            cards_recieved: cards_recieved,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct PersonalDrawResponse {
@@ -20,3 +30,13 @@ pub struct PersonalDrawResponse {
     status: StatusCode,
     new_cards: Vec<Card>,
 }
+
+impl PersonalDrawResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, new_cards: Vec<Card>) -> PersonalDrawResponse {
+        PersonalDrawResponse {
+            req_id: req_id,
+            status: status,
+            new_cards: new_cards,
+        }
+    }
+}
@@ -9,9 +9,26 @@ pub struct PublicFoldResponse {
     position: usize,
 }
 
+impl PublicFoldResponse {
+    pub fn new(position: usize) -> PublicFoldResponse {
+        PublicFoldResponse {
+            position: position,
+        }
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct PersonalFoldResponse {
     req_id: Uuid,
     status: StatusCode,
 }
+
+impl PersonalFoldResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalFoldResponse {
+        PersonalFoldResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
@@ -1,9 +1,42 @@
+use std::collections::BTreeMap;
+
+use playing_cards::core::Card;
 use serde::Serialize;
 
+use crate::poker::games::Street;
+use crate::poker::BlindPositions;
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all(serialize = "camelCase"))]
 // TODO: Needs to accompany all info
 pub struct GameState {
-    
-}
\ No newline at end of file
+    is_hand_in_progress: bool,
+    is_bomb_pot: bool,
+    street: Option<Street>,
+    action_idx: usize,
+    blind_positions: Option<BlindPositions>,
+    straddle_positions: Vec<usize>,             // seats that straddled, in posting order
+    stacks_bets: BTreeMap<usize, (u64, u64)>,   // (stack, current bet) of every player in the hand
+    players_in_hand: Vec<usize>,
+    board: Vec<Card>,
+    second_board: Vec<Card>,                    // empty unless the game is played with two boards
+    up_cards: BTreeMap<usize, Vec<Card>>,       // face up cards of the players in the hand, in stud games
+}
+
+impl GameState {
+    pub fn new(is_hand_in_progress: bool, is_bomb_pot: bool, street: Option<Street>, action_idx: usize, blind_positions: Option<BlindPositions>, straddle_positions: Vec<usize>, stacks_bets: BTreeMap<usize, (u64, u64)>, players_in_hand: Vec<usize>, board: Vec<Card>, second_board: Vec<Card>, up_cards: BTreeMap<usize, Vec<Card>>) -> GameState {
+        GameState {
+            is_hand_in_progress: is_hand_in_progress,
+            is_bomb_pot: is_bomb_pot,
+            street: street,
+            action_idx: action_idx,
+            blind_positions: blind_positions,
+            straddle_positions: straddle_positions,
+            stacks_bets: stacks_bets,
+            players_in_hand: players_in_hand,
+            board: board,
+            second_board: second_board,
+            up_cards: up_cards,
+        }
+    }
+}
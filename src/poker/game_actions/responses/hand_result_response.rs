@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct HandResultResponse {
+    winnings: HashMap<usize, u64>,  // position to total amount won
+}
+
+impl HandResultResponse {
+    pub fn new(winnings: HashMap<usize, u64>) -> HandResultResponse {
+        HandResultResponse {
+            winnings: winnings,
+        }
+    }
+}
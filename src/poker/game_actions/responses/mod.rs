@@ -18,3 +18,40 @@ pub use self::game_state_response::GameState;
 
 mod response_status_codes;
 pub use self::response_status_codes::StatusCode;
+
+mod hand_result_response;
+pub use self::hand_result_response::HandResultResponse;
+
+mod seat_response;
+pub use self::seat_response::{PublicSitDownResponse, PersonalSitDownResponse,
+                            PublicStandUpResponse, PersonalStandUpResponse,
+                            PublicReserveSeatResponse, PersonalReserveSeatResponse,
+                            PersonalWaitingListResponse};
+
+mod clock_response;
+pub use self::clock_response::ActionClockResponse;
+
+mod away_response;
+pub use self::away_response::{PublicAwayResponse, PersonalAwayResponse};
+
+mod settings_response;
+pub use self::settings_response::PersonalSettingsResponse;
+
+mod bomb_pot_response;
+pub use self::bomb_pot_response::{PublicBombPotVoteResponse, PersonalBombPotVoteResponse};
+
+mod run_it_response;
+pub use self::run_it_response::{RunItOfferResponse, PublicRunItResponse, PersonalRunItResponse,
+                            RunItDecisionResponse, RunoutResultResponse};
+
+mod showdown_response;
+pub use self::showdown_response::{PublicShowHandResponse, PublicMuckHandResponse, PersonalShowHandResponse};
+
+mod table_config_response;
+pub use self::table_config_response::PersonalTableConfigResponse;
+
+mod add_chips_response;
+pub use self::add_chips_response::{PersonalAddChipsResponse, LedgerResponse};
+
+mod undo_response;
+pub use self::undo_response::PersonalUndoResponse;
@@ -1,9 +1,26 @@
 use serde_repr::Serialize_repr;
 
-#[derive(Serialize_repr, Debug)]
+#[derive(Serialize_repr, Debug, PartialEq)]
 #[repr(u8)]
 pub enum StatusCode {
     OK = 0,
     NotYourTurn = 1,
     InvalidBet = 2,
-}
\ No newline at end of file
+    InvalidDraw = 3,
+    MalformedRequest = 4,
+    InvalidSeat = 5,
+    SeatTaken = 6,
+    SeatReserved = 7,
+    AlreadySeated = 8,
+    NotSeated = 9,
+    InvalidBuyIn = 10,
+    TableNotFull = 11,
+    GamePaused = 12,
+    VotingDisabled = 13,
+    InvalidRunItTimes = 14,
+    InvalidShow = 15,
+    InvalidTableConfig = 16,
+    NothingToUndo = 17,
+    ActionFailed = 18,
+    NotHost = 19,
+}
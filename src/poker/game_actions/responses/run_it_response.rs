@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use playing_cards::core::Card;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct RunItOfferResponse {
+    positions: Vec<usize>,  // players that have to agree on the number of runouts
+    max_times: usize,
+    seconds_to_decide: u64, // anyone that has not decided by then runs it once
+}
+
+impl RunItOfferResponse {
+    pub fn new(positions: Vec<usize>, max_times: usize, seconds_to_decide: u64) -> RunItOfferResponse {
+        RunItOfferResponse {
+            positions: positions,
+            max_times: max_times,
+            seconds_to_decide: seconds_to_decide,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicRunItResponse {
+    position: usize,
+    times: usize,
+}
+
+impl PublicRunItResponse {
+    pub fn new(position: usize, times: usize) -> PublicRunItResponse {
+        PublicRunItResponse {
+            position: position,
+            times: times,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalRunItResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalRunItResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalRunItResponse {
+        PersonalRunItResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct RunItDecisionResponse {
+    times: usize,   // lowest number of runouts any player agreed to
+}
+
+impl RunItDecisionResponse {
+    pub fn new(times: usize) -> RunItDecisionResponse {
+        RunItDecisionResponse {
+            times: times,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct RunoutResultResponse {
+    runout_idx: usize,
+    board: Vec<Card>,
+    winnings: HashMap<usize, u64>,  // position to amount won on this runout
+}
+
+impl RunoutResultResponse {
+    pub fn new(runout_idx: usize, board: Vec<Card>, winnings: HashMap<usize, u64>) -> RunoutResultResponse {
+        RunoutResultResponse {
+            runout_idx: runout_idx,
+            board: board,
+            winnings: winnings,
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicSitDownResponse {
+    position: usize,
+    name: String,
+    stack: u64,
+}
+
+impl PublicSitDownResponse {
+    pub fn new(position: usize, name: String, stack: u64) -> PublicSitDownResponse {
+        PublicSitDownResponse {
+            position: position,
+            name: name,
+            stack: stack,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalSitDownResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    position: Option<usize>,
+}
+
+impl PersonalSitDownResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, position: Option<usize>) -> PersonalSitDownResponse {
+        PersonalSitDownResponse {
+            req_id: req_id,
+            status: status,
+            position: position,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicStandUpResponse {
+    position: usize,
+}
+
+impl PublicStandUpResponse {
+    pub fn new(position: usize) -> PublicStandUpResponse {
+        PublicStandUpResponse {
+            position: position,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalStandUpResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalStandUpResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalStandUpResponse {
+        PersonalStandUpResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicReserveSeatResponse {
+    position: usize,
+    seconds_held: u64,
+}
+
+impl PublicReserveSeatResponse {
+    pub fn new(position: usize, seconds_held: u64) -> PublicReserveSeatResponse {
+        PublicReserveSeatResponse {
+            position: position,
+            seconds_held: seconds_held,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalReserveSeatResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalReserveSeatResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalReserveSeatResponse {
+        PersonalReserveSeatResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalWaitingListResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    queue_position: Option<usize>,  // 0 is the next player to be seated
+}
+
+impl PersonalWaitingListResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, queue_position: Option<usize>) -> PersonalWaitingListResponse {
+        PersonalWaitingListResponse {
+            req_id: req_id,
+            status: status,
+            queue_position: queue_position,
+        }
+    }
+}
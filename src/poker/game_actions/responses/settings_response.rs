@@ -0,0 +1,20 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalSettingsResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalSettingsResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalSettingsResponse {
+        PersonalSettingsResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
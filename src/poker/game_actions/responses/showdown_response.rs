@@ -0,0 +1,52 @@
+use playing_cards::core::Card;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+// Only ever sent for cards the player actually revealed
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicShowHandResponse {
+    position: usize,
+    cards: Vec<Card>,
+}
+
+impl PublicShowHandResponse {
+    pub fn new(position: usize, cards: Vec<Card>) -> PublicShowHandResponse {
+        PublicShowHandResponse {
+            position: position,
+            cards: cards,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PublicMuckHandResponse {
+    position: usize,
+}
+
+impl PublicMuckHandResponse {
+    pub fn new(position: usize) -> PublicMuckHandResponse {
+        PublicMuckHandResponse {
+            position: position,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalShowHandResponse {
+    req_id: Uuid,
+    status: StatusCode,
+}
+
+impl PersonalShowHandResponse {
+    pub fn new(req_id: Uuid, status: StatusCode) -> PersonalShowHandResponse {
+        PersonalShowHandResponse {
+            req_id: req_id,
+            status: status,
+        }
+    }
+}
@@ -5,4 +5,12 @@ use serde::Serialize;
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct StartingHandResponse {
     hand: Vec<Card>,
-}
\ No newline at end of file
+}
+
+impl StartingHandResponse {
+    pub fn new(hand: Vec<Card>) -> StartingHandResponse {
+        StartingHandResponse {
+            hand: hand,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalTableConfigResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    error: Option<String>,  // why the config was rejected
+}
+
+impl PersonalTableConfigResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, error: Option<String>) -> PersonalTableConfigResponse {
+        PersonalTableConfigResponse {
+            req_id: req_id,
+            status: status,
+            error: error,
+        }
+    }
+}
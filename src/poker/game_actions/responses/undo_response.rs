@@ -0,0 +1,22 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::StatusCode;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PersonalUndoResponse {
+    req_id: Uuid,
+    status: StatusCode,
+    actions_undone: usize,
+}
+
+impl PersonalUndoResponse {
+    pub fn new(req_id: Uuid, status: StatusCode, actions_undone: usize) -> PersonalUndoResponse {
+        PersonalUndoResponse {
+            req_id: req_id,
+            status: status,
+            actions_undone: actions_undone,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use playing_cards::core::Card;
+use playing_cards::poker::Rank;
+
+use crate::poker::games::{BadugiEvaluator, DeuceToSevenEvaluator, DrawDeck, GameVariation, LowEvaluator, Street};
+use crate::poker::Player;
+
+// Low hand the pot is split with in the split Badugi games
+enum SplitLow {
+    DeuceToSeven(DeuceToSevenEvaluator),    // Badeucy
+    AceToFive(LowEvaluator),                // Badacey
+}
+
+impl SplitLow {
+    fn evaluate_hand(& self, hand: &Vec<Card>) -> Option<Rank> {
+        match self {
+            SplitLow::DeuceToSeven(eval) => eval.evaluate_hand(hand, &Vec::new()),
+            SplitLow::AceToFive(eval) => eval.evaluate_hand(hand, &Vec::new()),
+        }
+    }
+}
+
+// Triple draw Badugi, or one of the split games where the best badugi splits the pot with the best five card low
+pub struct Badugi {
+    deck: DrawDeck,
+    eval: BadugiEvaluator,
+    split_low: Option<SplitLow>,
+    hand_size: usize,
+}
+
+impl Badugi {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 6;
+    const DRAW_COUNT: usize = 3;
+
+    pub fn new() -> Badugi {
+        Badugi {
+            deck: DrawDeck::new(),
+            eval: BadugiEvaluator::new(),
+            split_low: None,
+            hand_size: 4,
+        }
+    }
+
+    // Badugi and 2-7, where aces are high for both halves
+    pub fn new_badeucy() -> Badugi {
+        Badugi {
+            deck: DrawDeck::new(),
+            eval: BadugiEvaluator::ace_high(),
+            split_low: Some(SplitLow::DeuceToSeven(DeuceToSevenEvaluator{})),
+            hand_size: 5,
+        }
+    }
+
+    // Badugi and A-5
+    pub fn new_badacey() -> Badugi {
+        Badugi {
+            deck: DrawDeck::new(),
+            eval: BadugiEvaluator::new(),
+            split_low: Some(SplitLow::AceToFive(LowEvaluator::new())),
+            hand_size: 5,
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= Badugi::MIN_PLAYER_COUNT && count <= Badugi::MAX_PLAYER_COUNT
+    }
+}
+
+impl GameVariation for Badugi {
+    fn get_min_player_count(& self) -> usize {
+        Badugi::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        Badugi::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.deck.reset();
+        self.deck.deal_hands(players, self.hand_size)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        DrawDeck::get_streets(Badugi::DRAW_COUNT)
+    }
+
+    fn draw_cards(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        self.deck.draw(player, discards)
+    }
+
+    // Badugi rankings, followed by the low rankings in the split games
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| self.eval.evaluate_hand(p.get_hand(), &Vec::new()).map(|rank| (*pos, rank)))
+            .collect();
+
+        match &self.split_low {
+            Some(split_low) => {
+                let low_rankings = players.iter()
+                    .filter(|(_, p)| p.is_in_hand)
+                    .filter_map(|(pos, p)| split_low.evaluate_hand(p.get_hand()).map(|rank| (*pos, rank)))
+                    .collect();
+                vec![rankings, low_rankings]
+            },
+            None => vec![rankings],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deal_hand_sizes() {
+        for (mut game, hand_size) in [(Badugi::new(), 4), (Badugi::new_badeucy(), 5), (Badugi::new_badacey(), 5)] {
+            let mut players = get_players(&["", "", ""]);
+            assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+            assert!(players.values().all(|p| p.get_hand().len() == hand_size));
+            assert_eq!(7, game.get_streets().len());
+        }
+    }
+
+    #[test]
+    fn badugi_single_objective() {
+        let game = Badugi::new();
+        let players = get_players(&["As2d3c4h", "2s3d4c5h", "As2s3c4h"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        assert!(rankings[0][&1] > rankings[0][&2]);
+    }
+
+    #[test]
+    fn badeucy_splits_badugi_and_deuce_to_seven() {
+        let game = Badugi::new_badeucy();
+        // a 7-4-3-2 badugi that makes the best 2-7 low, against a badugi that has to play the ace high
+        let players = get_players(&["2s3d4c7h5s", "As2d3c4hKh"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        assert!(rankings[1][&0] > rankings[1][&1]);
+    }
+
+    #[test]
+    fn badacey_splits_badugi_and_ace_to_five() {
+        let game = Badugi::new_badacey();
+        let players = get_players(&["As2d3c4h5h", "2s3d4c5hKd"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        // the wheel beats a king low
+        assert!(rankings[1][&0] > rankings[1][&1]);
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use playing_cards::core::{Card, CardDeck, Value};
+
+use crate::poker::games::Street;
+use crate::poker::Player;
+
+// Deck and community cards of a flop game. Every street of the board is dealt after burning a card
+pub struct CommunityBoard {
+    deck: CardDeck,
+    lowest_value: Value,    // cards below it are stripped out of the deck
+    cards_left: usize,
+    is_double: bool,        // a second board is dealt from the same deck, street by street with the first
+
+    pub cards: Vec<Card>,
+    pub second_cards: Vec<Card>,
+}
+
+impl CommunityBoard {
+    const DECK_SIZE: usize = 52;
+    const SIZES: [usize; 4] = [0, 3, 4, 5];    // size of the board once each street is dealt (preflop, flop, turn, river)
+
+    pub fn new() -> CommunityBoard {
+        CommunityBoard::new_stripped(Value::Two)
+    }
+
+    // Deck without the cards below lowest_value, like the 36 card deck short deck is played with
+    pub fn new_stripped(lowest_value: Value) -> CommunityBoard {
+        CommunityBoard {
+            deck: CardDeck::new().unwrap(),
+            lowest_value: lowest_value,
+            cards_left: CommunityBoard::get_deck_size(lowest_value),
+            is_double: false,
+
+            cards: Vec::new(),
+            second_cards: Vec::new(),
+        }
+    }
+
+    pub fn new_double_board() -> CommunityBoard {
+        let mut board = CommunityBoard::new();
+        board.is_double = true;
+        board
+    }
+
+    // Starts the hand over with a fresh deck and an empty board
+    pub fn reset(&mut self) {
+        self.deck = CardDeck::new().unwrap();
+        self.cards_left = CommunityBoard::get_deck_size(self.lowest_value);
+        self.cards.clear();
+        self.second_cards.clear();
+    }
+
+    pub fn get_board_count(& self) -> usize {
+        if self.is_double { 2 } else { 1 }
+    }
+
+    fn get_deck_size(lowest_value: Value) -> usize {
+        CommunityBoard::DECK_SIZE - 4 * lowest_value as usize
+    }
+
+    // One betting street for every street of the board
+    pub fn get_streets() -> Vec<Street> {
+        vec![Street::Betting; CommunityBoard::SIZES.len()]
+    }
+
+    // Most players that can be dealt hand_size cards while leaving enough for every board and its burns
+    pub fn get_max_player_count(hand_size: usize, board_count: usize) -> usize {
+        let board_cards = CommunityBoard::SIZES[CommunityBoard::SIZES.len() - 1] + CommunityBoard::SIZES.len() - 1;
+        (CommunityBoard::DECK_SIZE - board_cards * board_count) / hand_size
+    }
+
+    // Stripped cards are thrown away as they come off the deck, which leaves the rest in the order they were shuffled
+    fn deal(&mut self, count: usize) -> Result<Vec<Card>, &'static str> {
+        if count > self.cards_left {
+            return Err("Not enough cards left in the deck");
+        }
+
+        let mut cards = Vec::new();
+        while cards.len() < count {
+            match self.deck.deal_cards(count - cards.len()) {
+                (Some(dealt), _) => {
+                    cards.extend(dealt.into_iter().filter(|card| card.value as usize >= self.lowest_value as usize));
+                },
+                _ => return Err("Not enough cards left in the deck"),
+            }
+        }
+        self.cards_left -= count;
+        Ok(cards)
+    }
+
+    fn burn_and_deal(&mut self, count: usize) -> Result<Vec<Card>, &'static str> {
+        self.deal(1)?;
+        self.deal(count)
+    }
+
+    pub fn deal_hands(&mut self, players: &mut HashMap<usize, Player>, hand_size: usize) -> Result<(), &'static str> {
+        for (_, p) in players.iter_mut().filter(|(_, p)| p.is_in_hand) {
+            p.set_new_hand(self.deal(hand_size)?);
+        }
+        Ok(())
+    }
+
+    // Deals the board up to the size it has on the given street. Streets that were already dealt deal nothing. With two
+    // boards, the street is dealt to the first board and then the second
+    pub fn deal_street(&mut self, street_idx: usize) -> Result<Vec<Card>, &'static str> {
+        let board_size = match CommunityBoard::SIZES.get(street_idx) {
+            Some(board_size) => *board_size,
+            None => return Ok(Vec::new()),
+        };
+        if self.cards.len() >= board_size {
+            return Ok(Vec::new());
+        }
+
+        let mut cards = self.burn_and_deal(board_size - self.cards.len())?;
+        self.cards.extend(cards.iter().cloned());
+        if self.is_double {
+            let second_cards = self.burn_and_deal(board_size - self.second_cards.len())?;
+            self.second_cards.extend(second_cards.iter().cloned());
+            cards.extend(second_cards);
+        }
+        Ok(cards)
+    }
+
+    // Burns and deals the rest of the streets on every runout
+    fn get_cards_per_runout(& self) -> usize {
+        CommunityBoard::SIZES.iter()
+            .filter(|board_size| **board_size > self.cards.len())
+            .count() + CommunityBoard::SIZES[CommunityBoard::SIZES.len() - 1] - self.cards.len()
+    }
+
+    // How many times the rest of the board can be run out, capped at max_runouts. Two boards are only ever run out once
+    pub fn get_max_runouts(& self, max_runouts: usize) -> usize {
+        let cards_per_runout = self.get_cards_per_runout();
+        if cards_per_runout == 0 || self.is_double {
+            return 1;
+        }
+        std::cmp::max(1, std::cmp::min(max_runouts, self.cards_left / cards_per_runout))
+    }
+
+    pub fn deal_runouts(&mut self, count: usize) -> Result<Vec<Vec<Card>>, &'static str> {
+        let board_len = self.cards.len();
+        let mut runouts = Vec::new();
+        for _ in 0..count {
+            let mut runout = Vec::new();
+            for board_size in CommunityBoard::SIZES.iter().filter(|board_size| **board_size > board_len) {
+                let cards = self.burn_and_deal(board_size - board_len - runout.len())?;
+                runout.extend(cards);
+            }
+            runouts.push(runout);
+        }
+        Ok(runouts)
+    }
+
+    // Board with the cards of a runout added on
+    pub fn with_runout(& self, runout: &Vec<Card>) -> Vec<Card> {
+        self.cards.iter().chain(runout.iter()).cloned().collect()
+    }
+}
+
+// Every way of picking count cards out of cards, keeping their order
+pub fn get_combinations(cards: &Vec<Card>, count: usize) -> Vec<Vec<Card>> {
+    if count == 0 {
+        return vec![Vec::new()];
+    }
+    if cards.len() < count {
+        return Vec::new();
+    }
+
+    let rest = cards[1..].to_vec();
+    let mut combinations: Vec<Vec<Card>> = get_combinations(&rest, count - 1).into_iter()
+        .map(|mut combination| {
+            combination.insert(0, cards[0]);
+            combination
+        })
+        .collect();
+    combinations.extend(get_combinations(&rest, count));
+    combinations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deal_streets_with_burns() {
+        let mut board = CommunityBoard::new();
+
+        assert_eq!(Ok(Vec::new()), board.deal_street(0));
+        assert_eq!(3, board.deal_street(1).unwrap().len());
+        // dealing a street twice does nothing
+        assert_eq!(Ok(Vec::new()), board.deal_street(1));
+        assert_eq!(1, board.deal_street(2).unwrap().len());
+        assert_eq!(1, board.deal_street(3).unwrap().len());
+        assert_eq!(Ok(Vec::new()), board.deal_street(4));
+        assert_eq!(5, board.cards.len());
+
+        // 3 burns and 5 board cards
+        assert_eq!(52 - 8, board.cards_left);
+        assert!(board.deal(52 - 8).is_ok());
+        assert!(board.deal(1).is_err());
+    }
+
+    #[test]
+    fn runouts_deal_rest_of_board() {
+        let mut board = CommunityBoard::new();
+        let _ = board.deal_street(1);
+        assert_eq!(3, board.get_max_runouts(3));
+
+        let runouts = board.deal_runouts(2).unwrap();
+        assert_eq!(2, runouts.len());
+        assert!(runouts.iter().all(|runout| runout.len() == 2));
+        assert!(runouts[0].iter().all(|card| !runouts[1].contains(card)));
+        assert_eq!(3, board.cards.len());
+        assert_eq!(5, board.with_runout(&runouts[0]).len());
+    }
+
+    #[test]
+    fn runouts_limited_by_deck() {
+        let mut board = CommunityBoard::new();
+        let mut players: HashMap<usize, Player> = (0..10)
+            .map(|pos| (pos, Player::new(pos, format!("Player {}", pos), 100)))
+            .collect();
+        assert_eq!(Ok(()), board.deal_hands(&mut players, 4));
+
+        // 12 cards left and every runout from preflop takes 8
+        assert_eq!(1, board.get_max_runouts(3));
+        let _ = board.deal_street(3);
+        assert_eq!(1, board.get_max_runouts(3));
+    }
+
+    #[test]
+    fn double_board_deals_both_boards() {
+        let mut board = CommunityBoard::new_double_board();
+
+        assert_eq!(6, board.deal_street(1).unwrap().len());
+        assert_eq!(2, board.deal_street(2).unwrap().len());
+        assert_eq!(2, board.deal_street(3).unwrap().len());
+        assert_eq!(5, board.cards.len());
+        assert_eq!(5, board.second_cards.len());
+        assert!(board.cards.iter().all(|card| !board.second_cards.contains(card)));
+        // 6 burns and 10 board cards
+        assert_eq!(52 - 16, board.cards_left);
+
+        board.reset();
+        assert!(board.second_cards.is_empty());
+        let _ = board.deal_street(1);
+        assert_eq!(1, board.get_max_runouts(3));
+    }
+
+    #[test]
+    fn stripped_deck_deals_no_low_cards() {
+        let mut board = CommunityBoard::new_stripped(Value::Six);
+        let mut players: HashMap<usize, Player> = (0..8)
+            .map(|pos| (pos, Player::new(pos, format!("Player {}", pos), 100)))
+            .collect();
+        assert_eq!(Ok(()), board.deal_hands(&mut players, 2));
+        for street_idx in 1..4 {
+            assert!(board.deal_street(street_idx).is_ok());
+        }
+        assert_eq!(36 - 16 - 8, board.cards_left);
+
+        let mut all_cards: Vec<Card> = players.values().flat_map(|p| p.get_hand().clone()).collect();
+        all_cards.extend(board.cards.iter().cloned());
+        all_cards.extend(board.deal(36 - 16 - 8).unwrap());
+        assert!(all_cards.iter().all(|card| card.value as usize >= Value::Six as usize));
+        assert!(all_cards.iter().enumerate().all(|(idx, card)| !all_cards[..idx].contains(card)));
+        assert!(board.deal(1).is_err());
+
+        board.reset();
+        assert_eq!(36, board.cards_left);
+    }
+
+    #[test]
+    fn max_players_by_hand_size() {
+        assert_eq!(22, CommunityBoard::get_max_player_count(2, 1));
+        assert_eq!(11, CommunityBoard::get_max_player_count(4, 1));
+        assert_eq!(8, CommunityBoard::get_max_player_count(5, 1));
+        assert_eq!(7, CommunityBoard::get_max_player_count(6, 1));
+        // 16 cards for two boards and their burns
+        assert_eq!(18, CommunityBoard::get_max_player_count(2, 2));
+        assert_eq!(9, CommunityBoard::get_max_player_count(4, 2));
+        assert_eq!(7, CommunityBoard::get_max_player_count(5, 2));
+    }
+
+    #[test]
+    fn combinations() {
+        let cards = Card::vec_from_str("AsKsQsJs").unwrap();
+        let combinations = get_combinations(&cards, 2);
+        assert_eq!(6, combinations.len());
+        assert_eq!(Card::vec_from_str("AsKs").unwrap(), combinations[0]);
+        assert_eq!(Card::vec_from_str("QsJs").unwrap(), combinations[5]);
+        assert_eq!(1, get_combinations(&cards, 4).len());
+        assert!(get_combinations(&cards, 5).is_empty());
+    }
+}
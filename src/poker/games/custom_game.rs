@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+
+use playing_cards::core::{Card, Value};
+use playing_cards::poker::{Evaluator, HighEvaluator, Rank};
+use serde::{Deserialize, Serialize};
+
+use crate::poker::games::{BadugiEvaluator, DeuceToSevenEvaluator, DrawDeck, GameVariation, LowEvaluator, Street, get_combinations};
+use crate::poker::{BettingStructure, Player};
+
+// What a hand is ranked by. The pot is split evenly between every objective of the game
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Objective {
+    High,
+    Low8,           // ace to five, eight or better to qualify
+    DeuceToSeven,
+    Badugi,
+}
+
+// Cards dealt at the start of a street, or a draw where nothing is dealt
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StreetSpec {
+    pub down_cards: usize,      // dealt face down to every player in the hand
+    pub up_cards: usize,        // dealt face up to every player in the hand
+    pub board_cards: usize,     // dealt to the board after a burn
+    pub is_draw: bool,
+}
+
+// Rules of a game the host made up, read from JSON instead of being one of the games the server comes with
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameSpec {
+    pub name: String,
+    pub lowest_rank: char,                  // cards below it are stripped from the deck, '6' for a short deck
+    pub streets: Vec<StreetSpec>,
+    pub hole_cards_used: Option<usize>,     // exactly this many hole cards play with the board, like the 2 in Omaha
+    pub betting_structure: BettingStructure,
+    pub objectives: Vec<Objective>,
+}
+
+impl GameSpec {
+    const HAND_SIZE: usize = 5;     // cards in a high or low hand
+
+    // Texas Hold'em, as a starting point to change from
+    pub fn new() -> GameSpec {
+        GameSpec {
+            name: "Hold'em".to_string(),
+            lowest_rank: '2',
+            streets: vec![
+                StreetSpec { down_cards: 2, ..StreetSpec::default() },
+                StreetSpec { board_cards: 3, ..StreetSpec::default() },
+                StreetSpec { board_cards: 1, ..StreetSpec::default() },
+                StreetSpec { board_cards: 1, ..StreetSpec::default() },
+            ],
+            hole_cards_used: None,
+            betting_structure: BettingStructure::NoLimit,
+            objectives: vec![Objective::High],
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<GameSpec, std::string::String> {
+        let spec: GameSpec = serde_json::from_str(json).map_err(|e| format!("Invalid game spec: {}", e))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn get_lowest_value(& self) -> Option<Value> {
+        Card::vec_from_str(&format!("{}s", self.lowest_rank)).ok()
+            .and_then(|cards| cards.first().map(|card| card.value))
+    }
+
+    pub fn get_hand_size(& self) -> usize {
+        self.streets.iter().map(|street| street.down_cards + street.up_cards).sum()
+    }
+
+    pub fn get_board_size(& self) -> usize {
+        self.streets.iter().map(|street| street.board_cards).sum()
+    }
+
+    pub fn has_draws(& self) -> bool {
+        self.streets.iter().any(|street| street.is_draw)
+    }
+
+    // Most players that can be dealt in while leaving enough for the board and its burns. Draws are not counted, the
+    // discards get reshuffled once the deck runs out
+    pub fn get_max_player_count(& self) -> usize {
+        let deck_size = self.get_lowest_value().map_or(0, DrawDeck::get_deck_size);
+        let burns = self.streets.iter().filter(|street| street.board_cards > 0).count();
+        let hand_size = self.get_hand_size();
+        if hand_size == 0 {
+            return 0;
+        }
+        deck_size.saturating_sub(self.get_board_size() + burns) / hand_size
+    }
+
+    pub fn validate(& self) -> Result<(), std::string::String> {
+        if self.get_lowest_value().is_none() {
+            return Err(format!("{} is not a card rank", self.lowest_rank));
+        }
+        match self.streets.first() {
+            Some(first) if !first.is_draw && first.down_cards + first.up_cards > 0 => (),
+            _ => return Err(format!("The first street must deal cards to the players")),
+        }
+        if self.streets.iter().any(|street| street.is_draw && street.down_cards + street.up_cards + street.board_cards > 0) {
+            return Err(format!("Nothing can be dealt on a draw"));
+        }
+        if self.has_draws() && self.streets.iter().any(|street| street.up_cards > 0) {
+            return Err(format!("Games with draws can not deal face up cards"));
+        }
+        if self.objectives.is_empty() {
+            return Err(format!("The game needs at least one objective"));
+        }
+
+        let hand_size = self.get_hand_size();
+        let board_size = self.get_board_size();
+        if let Some(hole_cards_used) = self.hole_cards_used {
+            if self.objectives.contains(&Objective::Badugi) {
+                return Err(format!("Badugi hands can not be made with a set number of hole cards"));
+            }
+            if hole_cards_used > std::cmp::min(hand_size, GameSpec::HAND_SIZE) || GameSpec::HAND_SIZE - hole_cards_used > board_size {
+                return Err(format!("Hands can not be made with exactly {} hole cards", hole_cards_used));
+            }
+        }
+        if self.objectives.iter().any(|objective| *objective != Objective::Badugi) && hand_size + board_size < GameSpec::HAND_SIZE {
+            return Err(format!("Players need at least {} cards to make a hand", GameSpec::HAND_SIZE));
+        }
+        if self.get_max_player_count() < CustomGame::MIN_PLAYER_COUNT {
+            return Err(format!("The deck does not have enough cards for {} players", CustomGame::MIN_PLAYER_COUNT));
+        }
+        Ok(())
+    }
+}
+
+impl Default for GameSpec {
+    fn default() -> GameSpec {
+        GameSpec::new()
+    }
+}
+
+// Game played by the rules of a GameSpec. Like every flop game it is played with blinds, so action goes by the button
+// even when cards are dealt face up
+pub struct CustomGame {
+    spec: GameSpec,
+    deck: DrawDeck,
+    dealt_streets: usize,
+
+    board: Vec<Card>,
+}
+
+impl CustomGame {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 10;
+
+    // The spec is expected to be validated already
+    pub fn new(spec: GameSpec) -> CustomGame {
+        let lowest_value = spec.get_lowest_value().unwrap_or(Value::Two);
+        CustomGame {
+            spec: spec,
+            deck: DrawDeck::new_stripped(lowest_value),
+            dealt_streets: 0,
+
+            board: Vec::new(),
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= self.get_min_player_count() && count <= self.get_max_player_count()
+    }
+
+    fn deal_street_cards(&mut self, players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &'static str> {
+        let street = match self.spec.streets.get(street_idx) {
+            Some(street) => *street,
+            None => return Ok(Vec::new()),
+        };
+        if street_idx < self.dealt_streets {
+            return Ok(Vec::new());
+        }
+        self.dealt_streets = street_idx + 1;
+
+        for (_, player) in players.iter_mut().filter(|(_, p)| p.is_in_hand) {
+            let down_cards = self.deck.deal(street.down_cards)?;
+            let up_cards = self.deck.deal(street.up_cards)?;
+            player.deal_cards(down_cards, false);
+            player.deal_cards(up_cards, true);
+        }
+
+        if street.board_cards == 0 {
+            return Ok(Vec::new());
+        }
+        self.deck.deal(1)?;
+        let cards = self.deck.deal(street.board_cards)?;
+        self.board.extend(cards.iter().cloned());
+        Ok(cards)
+    }
+
+    fn evaluate_objective(objective: Objective, hand: &Vec<Card>, board: &Vec<Card>) -> Option<Rank> {
+        match objective {
+            Objective::High => HighEvaluator{}.evaluate_hand(hand, board).ok().and_then(|ranks| ranks.into_iter().next()),
+            Objective::Low8 => LowEvaluator::eight_or_better().evaluate_hand(hand, board),
+            Objective::DeuceToSeven => DeuceToSevenEvaluator{}.evaluate_hand(hand, board),
+            Objective::Badugi => BadugiEvaluator::new().evaluate_hand(hand, board),
+        }
+    }
+
+    // Best hand for the objective, made with exactly hole_cards_used of the player's cards if the game says so
+    fn get_best_hand(& self, objective: Objective, hand: &Vec<Card>) -> Option<Rank> {
+        let hole_cards_used = match self.spec.hole_cards_used {
+            Some(hole_cards_used) => hole_cards_used,
+            None => return CustomGame::evaluate_objective(objective, hand, &self.board),
+        };
+
+        let board_combinations = get_combinations(&self.board, GameSpec::HAND_SIZE - hole_cards_used);
+        get_combinations(hand, hole_cards_used).iter()
+            .flat_map(|hand_cards| {
+                board_combinations.iter().filter_map(move |board_cards| CustomGame::evaluate_objective(objective, hand_cards, board_cards))
+            })
+            .max()
+    }
+}
+
+impl GameVariation for CustomGame {
+    fn get_min_player_count(& self) -> usize {
+        CustomGame::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        std::cmp::min(CustomGame::MAX_PLAYER_COUNT, self.spec.get_max_player_count())
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.deck.reset();
+        self.dealt_streets = 0;
+        self.board.clear();
+        for (_, p) in players.iter_mut().filter(|(_, p)| p.is_in_hand) {
+            p.set_new_hand(Vec::new());
+        }
+        self.deal_street_cards(players, 0)?;
+        Ok(())
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        self.spec.streets.iter()
+            .map(|street| if street.is_draw { Street::Draw } else { Street::Betting })
+            .collect()
+    }
+
+    fn deal_street(&mut self, players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+        self.deal_street_cards(players, street_idx)
+    }
+
+    fn draw_cards(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        if !self.spec.has_draws() {
+            return Err("Cards can not be drawn in this game");
+        }
+        self.deck.draw(player, discards)
+    }
+
+    // One map per objective, in the order the spec lists them. An objective only ranks the players that qualify for it
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        self.spec.objectives.iter()
+            .map(|objective| {
+                players.iter()
+                    .filter(|(_, p)| p.is_in_hand)
+                    .filter_map(|(pos, p)| self.get_best_hand(*objective, p.get_hand()).map(|rank| (*pos, rank)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn get_board(& self) -> Vec<Card> {
+        self.board.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    fn get_spec(value: serde_json::Value) -> GameSpec {
+        GameSpec::from_json(&value.to_string()).unwrap()
+    }
+
+    #[test]
+    fn default_spec_is_holdem() {
+        let spec = GameSpec::new();
+        assert_eq!(Ok(()), spec.validate());
+        assert_eq!(22, spec.get_max_player_count());
+
+        let mut game = CustomGame::new(spec);
+        assert_eq!(10, game.get_max_player_count());
+        assert_eq!(vec![Street::Betting; 4], game.get_streets());
+
+        let mut players = get_players(&["", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        assert!(players.values().all(|p| p.get_hand().len() == 2 && p.get_up_cards().is_empty()));
+        assert_eq!(3, game.deal_street(&mut players, 1).unwrap().len());
+        // streets are only dealt once
+        assert!(game.deal_street(&mut players, 1).unwrap().is_empty());
+        assert_eq!(1, game.deal_street(&mut players, 2).unwrap().len());
+        assert_eq!(1, game.deal_street(&mut players, 3).unwrap().len());
+        assert_eq!(5, game.get_board().len());
+    }
+
+    #[test]
+    fn short_deck_stud_from_json() {
+        let spec = get_spec(json!({
+            "name": "Short Deck Stud",
+            "lowestRank": "6",
+            "streets": [{"downCards": 2, "upCards": 1}, {"upCards": 1}, {"upCards": 1}, {"upCards": 1}, {"downCards": 1}],
+            "bettingStructure": "FixedLimit",
+        }));
+        assert_eq!(vec![Objective::High], spec.objectives);
+        // 36 cards for 7 each
+        assert_eq!(5, spec.get_max_player_count());
+
+        let mut game = CustomGame::new(spec);
+        let mut players = get_players(&["", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        for street_idx in 1..5 {
+            assert!(game.deal_street(&mut players, street_idx).unwrap().is_empty());
+        }
+        assert!(players.values().all(|p| p.get_hand().len() == 7 && p.get_up_cards().len() == 4));
+        assert!(players.values()
+            .flat_map(|p| p.get_hand().iter())
+            .all(|card| card.value as usize >= Value::Six as usize));
+        assert_eq!(3, game.evaluate_all_hands(&players)[0].len());
+    }
+
+    #[test]
+    fn draw_game_with_two_objectives() {
+        let spec = get_spec(json!({
+            "name": "Badeucy Single Draw",
+            "streets": [{"downCards": 5}, {"isDraw": true}, {}],
+            "bettingStructure": "FixedLimit",
+            "objectives": ["badugi", "deuceToSeven"],
+        }));
+        let mut game = CustomGame::new(spec);
+        assert_eq!(vec![Street::Betting, Street::Draw, Street::Betting], game.get_streets());
+
+        let mut players = get_players(&["", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        let player = players.get_mut(&0).unwrap();
+        let discards = player.get_hand()[..2].to_vec();
+        assert_eq!(2, game.draw_cards(player, &discards).unwrap().len());
+
+        let players = get_players(&["2s3d4c7h5s", "As2d3c4hKh"]);
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        // A-2-3-4 is the best badugi, while 7-5-4-3-2 is the best 2-7 hand
+        assert!(rankings[0][&1] > rankings[0][&0]);
+        assert!(rankings[1][&0] > rankings[1][&1]);
+    }
+
+    #[test]
+    fn hole_cards_used_like_omaha_hi_lo() {
+        let spec = get_spec(json!({
+            "streets": [{"downCards": 4}, {"boardCards": 3}, {"boardCards": 1}, {"boardCards": 1}],
+            "holeCardsUsed": 2,
+            "bettingStructure": "PotLimit",
+            "objectives": ["high", "low8"],
+        }));
+        let mut game = CustomGame::new(spec);
+        game.board = Card::vec_from_str("AhKh7h2h3c").unwrap();
+        // a single heart does not make a flush, and the pair of deuces in hand can not make a low
+        let players = get_players(&["Qh6s5s9c", "2c2dKsQs"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert!(rankings[0][&1] > rankings[0][&0]);
+        assert_eq!(1, rankings[1].len());
+        assert!(rankings[1].contains_key(&0));
+    }
+
+    #[test]
+    fn invalid_specs() {
+        let invalid = [
+            json!({"lowestRank": "1"}),
+            json!({"streets": [{"boardCards": 3}, {"downCards": 2}]}),
+            json!({"streets": [{"downCards": 5}, {"isDraw": true, "downCards": 1}, {}]}),
+            json!({"streets": [{"downCards": 4, "upCards": 1}, {"isDraw": true}, {}]}),
+            json!({"objectives": []}),
+            json!({"holeCardsUsed": 3}),
+            json!({"streets": [{"downCards": 4}], "objectives": ["badugi"], "holeCardsUsed": 2}),
+            json!({"streets": [{"downCards": 3}]}),
+            json!({"streets": [{"downCards": 30}], "objectives": ["badugi"]}),
+            json!({"objectives": ["razz"]}),
+        ];
+        for spec in invalid {
+            assert!(GameSpec::from_json(&spec.to_string()).is_err(), "{} should be invalid", spec);
+        }
+    }
+}
@@ -1,3 +0,0 @@
-use crate::poker::games::FiveCardDraw;
-
-pub type DefaultGame = FiveCardDraw;
\ No newline at end of file
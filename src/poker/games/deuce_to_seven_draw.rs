@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use playing_cards::core::Card;
+use playing_cards::poker::Rank;
+
+use crate::poker::games::{DeuceToSevenEvaluator, DrawDeck, GameVariation, Street};
+use crate::poker::Player;
+
+// Deuce to seven lowball, played with one draw (single draw) or three (triple draw)
+pub struct DeuceToSevenDraw {
+    deck: DrawDeck,
+    eval: DeuceToSevenEvaluator,
+    draw_count: usize,
+}
+
+impl DeuceToSevenDraw {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 6;
+    const HAND_SIZE: usize = 5;
+
+    pub fn new_single_draw() -> DeuceToSevenDraw {
+        DeuceToSevenDraw::new(1)
+    }
+
+    pub fn new_triple_draw() -> DeuceToSevenDraw {
+        DeuceToSevenDraw::new(3)
+    }
+
+    fn new(draw_count: usize) -> DeuceToSevenDraw {
+        DeuceToSevenDraw {
+            deck: DrawDeck::new(),
+            eval: DeuceToSevenEvaluator{},
+            draw_count: draw_count,
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= DeuceToSevenDraw::MIN_PLAYER_COUNT && count <= DeuceToSevenDraw::MAX_PLAYER_COUNT
+    }
+}
+
+impl GameVariation for DeuceToSevenDraw {
+    fn get_min_player_count(& self) -> usize {
+        DeuceToSevenDraw::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        DeuceToSevenDraw::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.deck.reset();
+        self.deck.deal_hands(players, DeuceToSevenDraw::HAND_SIZE)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        DrawDeck::get_streets(self.draw_count)
+    }
+
+    fn draw_cards(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        self.deck.draw(player, discards)
+    }
+
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| self.eval.evaluate_hand(p.get_hand(), &Vec::new()).map(|rank| (*pos, rank)))
+            .collect();
+        vec![rankings]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn draw_rounds() {
+        assert_eq!(vec![Street::Betting, Street::Draw, Street::Betting], DeuceToSevenDraw::new_single_draw().get_streets());
+
+        let game = DeuceToSevenDraw::new_triple_draw();
+        let streets = game.get_streets();
+        assert_eq!(7, streets.len());
+        assert_eq!(3, streets.iter().filter(|street| **street == Street::Draw).count());
+        // the big bet comes in after the second draw
+        assert_eq!(4, game.get_big_bet_street_idx());
+        assert_eq!(2, DeuceToSevenDraw::new_single_draw().get_big_bet_street_idx());
+    }
+
+    #[test]
+    fn deal_and_draw() {
+        let mut game = DeuceToSevenDraw::new_triple_draw();
+        let mut players = get_players(&["", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        assert!(players.values().all(|p| p.get_hand().len() == 5));
+
+        let player = players.get_mut(&0).unwrap();
+        for _ in 0..3 {
+            let discards = player.get_hand()[..2].to_vec();
+            assert_eq!(2, game.draw_cards(player, &discards).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn lowest_hand_wins() {
+        let game = DeuceToSevenDraw::new_single_draw();
+        let players = get_players(&["7s5d4c3h2s", "As5d4c3h2c", "6s5h4h3d2d", "8h6h4h3h2h"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        // the ace high beats the straight and the flush
+        assert!(rankings[0][&1] > rankings[0][&2]);
+        assert!(rankings[0][&1] > rankings[0][&3]);
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use playing_cards::core::{Card, CardDeck, Value};
+use rand::seq::SliceRandom;
+
+use crate::poker::games::Street;
+use crate::poker::Player;
+
+// Deck of a draw game. Once the deck runs out, the cards discarded so far are shuffled and dealt from instead
+pub struct DrawDeck {
+    deck: CardDeck,
+    lowest_value: Value,        // cards below it are stripped out of the deck
+    cards_left: usize,
+
+    discards: Vec<Card>,        // discarded cards that have not been reshuffled yet
+    reshuffled: Vec<Card>,      // discards shuffled back in after the deck ran out
+}
+
+impl DrawDeck {
+    const DECK_SIZE: usize = 52;
+
+    pub fn new() -> DrawDeck {
+        DrawDeck::new_stripped(Value::Two)
+    }
+
+    pub fn new_stripped(lowest_value: Value) -> DrawDeck {
+        DrawDeck {
+            deck: CardDeck::new().unwrap(),
+            lowest_value: lowest_value,
+            cards_left: DrawDeck::get_deck_size(lowest_value),
+
+            discards: Vec::new(),
+            reshuffled: Vec::new(),
+        }
+    }
+
+    // Starts the hand over with a fresh deck
+    pub fn reset(&mut self) {
+        *self = DrawDeck::new_stripped(self.lowest_value);
+    }
+
+    pub fn get_deck_size(lowest_value: Value) -> usize {
+        DrawDeck::DECK_SIZE - 4 * lowest_value as usize
+    }
+
+    // A round of betting before the first draw and after every draw
+    pub fn get_streets(draw_count: usize) -> Vec<Street> {
+        let mut streets = vec![Street::Betting];
+        for _ in 0..draw_count {
+            streets.push(Street::Draw);
+            streets.push(Street::Betting);
+        }
+        streets
+    }
+
+    // Stripped cards are thrown away as they come off the deck
+    pub fn deal(&mut self, count: usize) -> Result<Vec<Card>, &'static str> {
+        let mut cards = Vec::new();
+        let from_deck = std::cmp::min(count, self.cards_left);
+        while cards.len() < from_deck {
+            match self.deck.deal_cards(from_deck - cards.len()) {
+                (Some(dealt), _) => {
+                    cards.extend(dealt.into_iter().filter(|card| card.value as usize >= self.lowest_value as usize));
+                },
+                _ => return Err("Not enough cards left in the deck"),
+            }
+        }
+        self.cards_left -= from_deck;
+
+        let still_needed = count - cards.len();
+        if still_needed > self.reshuffled.len() {
+            self.discards.shuffle(&mut rand::thread_rng());
+            self.reshuffled.extend(self.discards.drain(..));
+        }
+        if still_needed > self.reshuffled.len() {
+            return Err("Not enough cards left in the deck");
+        }
+        cards.extend(self.reshuffled.drain(..still_needed));
+        Ok(cards)
+    }
+
+    pub fn deal_hands(&mut self, players: &mut HashMap<usize, Player>, hand_size: usize) -> Result<(), &'static str> {
+        for (_, p) in players.iter_mut().filter(|(_, p)| p.is_in_hand) {
+            p.set_new_hand(self.deal(hand_size)?);
+        }
+        Ok(())
+    }
+
+    // Replaces the discards in the player's hand. A player never gets back the cards they just threw away
+    pub fn draw(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &'static str> {
+        if !player.can_discard(discards) {
+            return Err("Discarded cards are not in the player's hand");
+        }
+
+        let cards = self.deal(discards.len())?;
+        let draw = discards.iter().cloned().zip(cards.iter().cloned()).collect();
+        if player.draw_cards(draw).is_err() {
+            return Err("Could not replace cards in the player's hand");
+        }
+        self.discards.extend(discards.iter().cloned());
+
+        Ok(cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(count: usize) -> HashMap<usize, Player> {
+        (0..count)
+            .map(|pos| (pos, Player::new(pos, format!("Player {}", pos), 100)))
+            .collect()
+    }
+
+    #[test]
+    fn draw_replaces_discards() {
+        let mut deck = DrawDeck::new();
+        let mut players = get_players(2);
+        assert_eq!(Ok(()), deck.deal_hands(&mut players, 5));
+
+        let player = players.get_mut(&0).unwrap();
+        let kept = player.get_hand()[2..].to_vec();
+        let discards = player.get_hand()[..2].to_vec();
+        let cards = deck.draw(player, &discards).unwrap();
+
+        assert_eq!(2, cards.len());
+        assert!(cards.iter().all(|card| !discards.contains(card)));
+        assert!(player.get_hand().iter().all(|card| kept.contains(card) || cards.contains(card)));
+        assert_eq!(5, player.get_hand().len());
+    }
+
+    #[test]
+    fn cards_not_in_hand_can_not_be_discarded() {
+        let mut deck = DrawDeck::new();
+        let mut players = get_players(2);
+        assert_eq!(Ok(()), deck.deal_hands(&mut players, 5));
+
+        let other_card = players[&1].get_hand()[0];
+        let player = players.get_mut(&0).unwrap();
+        let hand = player.get_hand().clone();
+
+        assert!(deck.draw(player, &vec![other_card]).is_err());
+        assert!(deck.draw(player, &vec![hand[0], hand[0]]).is_err());
+        assert_eq!(&hand, player.get_hand());
+        assert_eq!(52 - 10, deck.cards_left);
+    }
+
+    #[test]
+    fn discards_reshuffled_when_deck_runs_out() {
+        let mut deck = DrawDeck::new();
+        let mut players = get_players(6);
+        assert_eq!(Ok(()), deck.deal_hands(&mut players, 5));
+
+        // 22 cards left: the first four players draw five, which leaves 2 in the deck
+        for pos in 0..4 {
+            let player = players.get_mut(&pos).unwrap();
+            let discards = player.get_hand().clone();
+            assert_eq!(5, deck.draw(player, &discards).unwrap().len());
+        }
+        assert_eq!(2, deck.cards_left);
+
+        // the last 2 cards of the deck and 3 of the reshuffled discards, never the player's own discards
+        let player = players.get_mut(&4).unwrap();
+        let discards = player.get_hand().clone();
+        let cards = deck.draw(player, &discards).unwrap();
+        assert_eq!(5, cards.len());
+        assert!(cards.iter().all(|card| !discards.contains(card)));
+        assert_eq!(0, deck.cards_left);
+        assert_eq!(17, deck.reshuffled.len());
+
+        // every card is still only in one place
+        let mut all_cards: Vec<Card> = players.values().flat_map(|p| p.get_hand().clone()).collect();
+        all_cards.extend(deck.reshuffled.iter().cloned());
+        all_cards.extend(deck.discards.iter().cloned());
+        assert_eq!(52, all_cards.len());
+        assert!(all_cards.iter().enumerate().all(|(idx, card)| !all_cards[..idx].contains(card)));
+    }
+}
@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 
-use playing_cards::core::{Card, CardDeck};
-use playing_cards::poker::{HighEvaluator, Rank};
+use playing_cards::core::Card;
+use playing_cards::poker::{Evaluator, HighEvaluator, Rank};
 
-use crate::poker::games::GameVariation;
+use crate::poker::games::{DrawDeck, GameVariation, Street};
 use crate::poker::Player;
 
 
 pub struct FiveCardDraw {
-    deck: CardDeck,
+    deck: DrawDeck,
     eval: HighEvaluator,
 
     board: Vec<Card>,
@@ -17,38 +17,123 @@ pub struct FiveCardDraw {
 impl FiveCardDraw {
     const MIN_PLAYER_COUNT: usize = 2;
     const MAX_PLAYER_COUNT: usize = 6;
+    const HAND_SIZE: usize = 5;
 
     pub fn new() -> FiveCardDraw {
         FiveCardDraw {
-            deck: CardDeck::new().unwrap(),
+            deck: DrawDeck::new(),
             eval: HighEvaluator{},
 
             board: Vec::new(),
         }
     }
 
-    fn check_player_condition(& self, players:& HashMap<u32, Player>) -> bool {
-        players.len() >= FiveCardDraw::MIN_PLAYER_COUNT && players.len() <= FiveCardDraw::MAX_PLAYER_COUNT
+    fn check_player_condition(& self, players:& HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= FiveCardDraw::MIN_PLAYER_COUNT && count <= FiveCardDraw::MAX_PLAYER_COUNT
     }
 }
 
 impl GameVariation for FiveCardDraw {
-    fn start_normal(&mut self, players:&mut HashMap<u32, Player>, btn_idx: usize) -> Result<(), &str> {
-        if self.check_player_condition(players) {
+    fn get_min_player_count(& self) -> usize {
+        FiveCardDraw::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        FiveCardDraw::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players:&mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
             return Err("Does not meet player requirements");
         }
 
-        players.iter_mut().for_each(|x| {
-            let (cards, _) = self.deck.deal_cards(5);
-            x.1.set_new_hand(cards.unwrap());
-        });
+        self.deck.reset();
+        self.deck.deal_hands(players, FiveCardDraw::HAND_SIZE)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        vec![Street::Betting, Street::Draw, Street::Betting]
+    }
+
+    fn draw_cards(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        self.deck.draw(player, discards)
+    }
 
-        Ok(())
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| {
+                self.eval.evaluate_hand(p.get_hand(), &self.board).ok()
+                    .and_then(|ranks| ranks.into_iter().next())
+                    .map(|rank| (*pos, rank))
+            })
+            .collect();
+        vec![rankings]
     }
 
-    fn evaluate_all_hands(& self) -> Vec<Vec<(&Player, Rank)>> {
-        // calls evaluator
-        todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
     }
 
+    #[test]
+    fn deal_five_cards() {
+        let mut game = FiveCardDraw::new();
+        let mut players = get_players(&["", "", ""]);
+        players.get_mut(&2).unwrap().is_in_hand = false;
+
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        assert_eq!(5, players[&0].get_hand().len());
+        assert_eq!(5, players[&1].get_hand().len());
+        assert_eq!(0, players[&2].get_hand().len());
+    }
+
+    #[test]
+    fn player_count_requirements() {
+        let mut game = FiveCardDraw::new();
+        assert!(game.start_normal(&mut get_players(&[""]), 0).is_err());
+        assert!(game.start_normal(&mut get_players(&["", "", "", "", "", "", ""]), 0).is_err());
+        assert_eq!(Ok(()), game.start_normal(&mut get_players(&["", "", "", "", "", ""]), 0));
+    }
+
+    #[test]
+    fn draw_validates_discards() {
+        let mut game = FiveCardDraw::new();
+        let mut players = get_players(&["", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+
+        let other_card = players[&1].get_hand()[0];
+        let player = players.get_mut(&0).unwrap();
+        assert!(game.draw_cards(player, &vec![other_card]).is_err());
+
+        let discards = player.get_hand()[..3].to_vec();
+        assert_eq!(3, game.draw_cards(player, &discards).unwrap().len());
+        assert_eq!(Ok(Vec::new()), game.draw_cards(player, &Vec::new()));
+    }
+
+    #[test]
+    fn evaluate_high_hands() {
+        let game = FiveCardDraw::new();
+        let mut players = get_players(&["AsAhKd7c2s", "QcQd3s3d4d", "9d8d4c3h2h"]);
+        players.get_mut(&2).unwrap().is_in_hand = false;
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert_eq!(2, rankings[0].len());
+        // two pair beats a pair of aces
+        assert!(rankings[0][&1] > rankings[0][&0]);
+    }
 }
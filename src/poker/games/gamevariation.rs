@@ -1,13 +1,94 @@
 use std::collections::HashMap;
 
 use playing_cards::core::Card;
+use serde::Serialize;
 
 use super::super::player::Player;
+use super::super::pots::OddChipPriority;
 use playing_cards::poker::Rank;
 
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Street {
+    Betting,    // round of betting between all players in the hand that are not all-in
+    Draw,       // each player in the hand may discard and replace cards (starting left of the button)
+}
+
 pub trait GameVariation {
 
-    fn start_normal(&mut self, players:&mut HashMap<u32, Player>, btn_idx: usize) -> Result<(), &str>;
+    fn get_min_player_count(& self) -> usize;
+
+    // Number of seats at the table
+    fn get_max_player_count(& self) -> usize;
+
+    fn start_normal(&mut self, players:&mut HashMap<usize, Player>, btn_idx: usize) -> Result<(), &str>;
+
+    // All streets of the hand in order, the first street being the one played right after the initial deal
+    fn get_streets(& self) -> Vec<Street>;
+
+    // Street fixed limit betting moves up from the small bet to the big bet, halfway through the betting streets
+    fn get_big_bet_street_idx(& self) -> usize {
+        let betting_streets: Vec<usize> = self.get_streets().iter()
+            .enumerate()
+            .filter(|(_, street)| **street == Street::Betting)
+            .map(|(idx, _)| idx)
+            .collect();
+        betting_streets.get(betting_streets.len() / 2).cloned().unwrap_or(0)
+    }
+
+    // Replaces the discarded cards in the player's hand and returns the cards that were drawn
+    fn draw_cards(&mut self, player: &mut Player, discards: &Vec<Card>) -> Result<Vec<Card>, &str>;
+
+    // Rankings of all players still in the hand, one map per objective the pot is split by
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>>;
+
+    // Deals the cards that come out at the start of the street, to the players still in the hand or to the board,
+    // and returns the community cards. Streets that were already dealt (or have nothing to deal) deal nothing
+    fn deal_street(&mut self, _players: &mut HashMap<usize, Player>, _street_idx: usize) -> Result<Vec<Card>, &str> {
+        Ok(Vec::new())
+    }
+
+    // Stud games are played with antes and a bring-in instead of blinds
+    fn has_blinds(& self) -> bool {
+        true
+    }
+
+    // Player that has to bring in the first betting street, if the game has a bring-in
+    fn get_bring_in_idx(& self, _players: &HashMap<usize, Player>) -> Option<usize> {
+        None
+    }
+
+    // Player that acts first on the street when it is decided by the cards showing instead of the button
+    fn get_first_to_act(& self, _players: &HashMap<usize, Player>, _street_idx: usize) -> Option<usize> {
+        None
+    }
+
+    // Community cards dealt so far
+    fn get_board(& self) -> Vec<Card> {
+        Vec::new()
+    }
+
+    // Cards of the second board in double board games, empty for every other game
+    fn get_second_board(& self) -> Vec<Card> {
+        Vec::new()
+    }
+
+    // Who gets the odd chips when a pot does not split evenly between the objectives
+    fn get_odd_chip_priority(& self) -> OddChipPriority {
+        OddChipPriority::OOP
+    }
+
+    // Most times the rest of the hand can be run out when everyone is all-in. Games with nothing left to deal can only run it once
+    fn get_max_runouts(& self) -> usize {
+        1
+    }
+
+    // Deals the rest of the board count times from the same deck, one set of cards per runout
+    fn deal_runouts(&mut self, _count: usize) -> Result<Vec<Vec<Card>>, &str> {
+        Err("Game cannot run it more than once")
+    }
 
-    fn evaluate_all_hands(& self) -> Vec<Vec<(&Player, Rank)>>;
+    // Same as evaluate_all_hands, but with the board of the given runout
+    fn evaluate_runout(& self, players: &HashMap<usize, Player>, _runout: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        self.evaluate_all_hands(players)
+    }
 }
@@ -0,0 +1,282 @@
+use playing_cards::core::{Card, Value};
+use playing_cards::poker::{HighRank, Rank};
+
+use crate::poker::games::get_combinations;
+
+// Ace to five lowball, where aces are low and straights and flushes do not count against the hand.
+// Lows are given as high ranks with the best low having the highest strength, so they can be compared the same
+// way as every other objective
+pub struct LowEvaluator {
+    qualifier: Option<u32>,     // highest card a low can have, None when every hand makes a low
+}
+
+impl LowEvaluator {
+    const HAND_SIZE: usize = 5;
+    const BASE: u32 = 14;
+
+    pub fn new() -> LowEvaluator {
+        LowEvaluator {
+            qualifier: None,
+        }
+    }
+
+    // Only five different cards of eight or lower make a low
+    pub fn eight_or_better() -> LowEvaluator {
+        LowEvaluator {
+            qualifier: Some(8),
+        }
+    }
+
+    pub fn get_ace_low_value(card: &Card) -> u32 {
+        if card.value == Value::Ace {
+            1
+        } else {
+            card.value as u32 + 2
+        }
+    }
+
+    // Lower is better: no pair beats one pair beats two pair and so on, then the highest cards are compared
+    fn get_low_score(& self, cards: &Vec<Card>) -> Option<u32> {
+        let mut counts = [0u32; 14];
+        for card in cards {
+            counts[LowEvaluator::get_ace_low_value(card) as usize] += 1;
+        }
+
+        let mut groups: Vec<(u32, u32)> = (1..14)
+            .filter(|value| counts[*value] > 0)
+            .map(|value| (counts[value], value as u32))
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+
+        if let Some(qualifier) = self.qualifier {
+            if groups.len() < LowEvaluator::HAND_SIZE || groups[0].1 > qualifier {
+                return None;
+            }
+        }
+
+        let category = match (groups[0].0, groups.get(1).map_or(0, |group| group.0)) {
+            (1, _) => 0,
+            (2, 1) => 1,
+            (2, 2) => 2,
+            (3, 1) => 3,
+            (3, 2) => 4,
+            _ => 5,
+        };
+        let kickers = groups.iter()
+            .flat_map(|(count, value)| std::iter::repeat(*value).take(*count as usize))
+            .fold(0, |score, value| score * LowEvaluator::BASE + value);
+        Some(category * LowEvaluator::BASE.pow(LowEvaluator::HAND_SIZE as u32) + kickers)
+    }
+
+    // Best low made with any five of the player's cards and the board, None when no five of them make a low
+    pub fn evaluate_hand(& self, player_hand: &Vec<Card>, board: &Vec<Card>) -> Option<Rank> {
+        let cards: Vec<Card> = player_hand.iter().chain(board.iter()).cloned().collect();
+        let worst_score = 6 * LowEvaluator::BASE.pow(LowEvaluator::HAND_SIZE as u32);
+        get_combinations(&cards, LowEvaluator::HAND_SIZE).iter()
+            .filter_map(|hand| self.get_low_score(hand))
+            .min()
+            .map(|score| Rank::High(HighRank::new(worst_score - score)))
+    }
+}
+
+// Deuce to seven lowball, the reverse of high hands: aces are always high, and straights and flushes count against the hand.
+// Like LowEvaluator, the best low has the highest strength
+pub struct DeuceToSevenEvaluator {}
+
+impl DeuceToSevenEvaluator {
+    const HAND_SIZE: usize = 5;
+    const BASE: u32 = 15;
+
+    // Higher is worse, the same as a high hand where the ace can not play low in a straight
+    fn get_high_score(cards: &Vec<Card>) -> u32 {
+        let mut counts = [0u32; 15];
+        for card in cards {
+            counts[card.value as usize + 2] += 1;
+        }
+
+        let mut groups: Vec<(u32, u32)> = (2..15)
+            .filter(|value| counts[*value] > 0)
+            .map(|value| (counts[value], value as u32))
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+
+        let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+        let is_straight = groups.len() == DeuceToSevenEvaluator::HAND_SIZE && groups[0].1 - groups[4].1 == 4;
+        let category = match (is_straight, is_flush, groups[0].0, groups.get(1).map_or(0, |group| group.0)) {
+            (true, true, _, _) => 8,
+            (_, _, 4, _) => 7,
+            (_, _, 3, 2) => 6,
+            (_, true, _, _) => 5,
+            (true, _, _, _) => 4,
+            (_, _, 3, _) => 3,
+            (_, _, 2, 2) => 2,
+            (_, _, 2, _) => 1,
+            _ => 0,
+        };
+        let kickers = groups.iter()
+            .flat_map(|(count, value)| std::iter::repeat(*value).take(*count as usize))
+            .fold(0, |score, value| score * DeuceToSevenEvaluator::BASE + value);
+        category * DeuceToSevenEvaluator::BASE.pow(DeuceToSevenEvaluator::HAND_SIZE as u32) + kickers
+    }
+
+    // Best low made with any five of the player's cards and the board. Every five cards make a 2-7 low
+    pub fn evaluate_hand(& self, player_hand: &Vec<Card>, board: &Vec<Card>) -> Option<Rank> {
+        let cards: Vec<Card> = player_hand.iter().chain(board.iter()).cloned().collect();
+        let worst_score = 9 * DeuceToSevenEvaluator::BASE.pow(DeuceToSevenEvaluator::HAND_SIZE as u32);
+        get_combinations(&cards, DeuceToSevenEvaluator::HAND_SIZE).iter()
+            .map(|hand| DeuceToSevenEvaluator::get_high_score(hand))
+            .min()
+            .map(|score| Rank::High(HighRank::new(worst_score - score)))
+    }
+}
+
+// Badugi, where the best four cards of different suits and different ranks make the hand. A hand with more cards always beats
+// one with fewer, then the lowest highest card wins. Like LowEvaluator, the best hand has the highest strength
+pub struct BadugiEvaluator {
+    is_ace_low: bool,   // aces play high in Badeucy
+}
+
+impl BadugiEvaluator {
+    const HAND_SIZE: usize = 4;
+    const BASE: u32 = 15;
+
+    pub fn new() -> BadugiEvaluator {
+        BadugiEvaluator {
+            is_ace_low: true,
+        }
+    }
+
+    pub fn ace_high() -> BadugiEvaluator {
+        BadugiEvaluator {
+            is_ace_low: false,
+        }
+    }
+
+    fn get_value(& self, card: &Card) -> u32 {
+        if self.is_ace_low {
+            LowEvaluator::get_ace_low_value(card)
+        } else {
+            card.value as u32 + 2
+        }
+    }
+
+    // Lower is better. None when the cards share a suit or a rank
+    fn get_badugi_score(& self, cards: &Vec<Card>) -> Option<u32> {
+        let is_badugi = cards.iter().enumerate().all(|(idx, card)| {
+            cards[..idx].iter().all(|other| other.suit != card.suit && other.value != card.value)
+        });
+        if !is_badugi {
+            return None;
+        }
+
+        let mut values: Vec<u32> = cards.iter().map(|card| self.get_value(card)).collect();
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        let missing_cards = (BadugiEvaluator::HAND_SIZE - cards.len()) as u32;
+        let kickers = values.iter().fold(0, |score, value| score * BadugiEvaluator::BASE + value);
+        Some(missing_cards * BadugiEvaluator::BASE.pow(BadugiEvaluator::HAND_SIZE as u32) + kickers)
+    }
+
+    // Best badugi made with any of the player's cards and the board
+    pub fn evaluate_hand(& self, player_hand: &Vec<Card>, board: &Vec<Card>) -> Option<Rank> {
+        let cards: Vec<Card> = player_hand.iter().chain(board.iter()).cloned().collect();
+        let worst_score = BadugiEvaluator::HAND_SIZE as u32 * BadugiEvaluator::BASE.pow(BadugiEvaluator::HAND_SIZE as u32);
+        (1..=std::cmp::min(BadugiEvaluator::HAND_SIZE, cards.len())).rev()
+            .filter_map(|count| {
+                get_combinations(&cards, count).iter()
+                    .filter_map(|hand| self.get_badugi_score(hand))
+                    .min()
+            })
+            .next()
+            .map(|score| Rank::High(HighRank::new(worst_score - score)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(eval: &LowEvaluator, cards: &str) -> Option<Rank> {
+        eval.evaluate_hand(&Card::vec_from_str(cards).unwrap(), &Vec::new())
+    }
+
+    #[test]
+    fn wheel_is_best_low() {
+        let eval = LowEvaluator::eight_or_better();
+        let wheel = evaluate(&eval, "As2s3s4s5s").unwrap();
+
+        assert!(wheel > evaluate(&eval, "As2d3c4h6s").unwrap());
+        assert!(evaluate(&eval, "As2d3c4h6s").unwrap() > evaluate(&eval, "As2d3c5h6s").unwrap());
+        assert!(evaluate(&eval, "8s4d3c2hAs").unwrap() > evaluate(&eval, "8s5d3c2hAs").unwrap());
+        assert!(evaluate(&eval, "7s6d5c4h3s").unwrap() > evaluate(&eval, "8s4d3c2hAs").unwrap());
+    }
+
+    #[test]
+    fn eight_or_better_qualifier() {
+        let eval = LowEvaluator::eight_or_better();
+
+        assert!(evaluate(&eval, "8s7d6c5h4s").is_some());
+        assert_eq!(None, evaluate(&eval, "9s4d3c2hAs"));
+        assert_eq!(None, evaluate(&eval, "As2d3c4hAh"));
+        // best five of seven
+        assert_eq!(evaluate(&eval, "As2d3c4h7s"), evaluate(&eval, "As2d3c4h7sKdKc"));
+    }
+
+    #[test]
+    fn pairs_count_against_low() {
+        let eval = LowEvaluator::new();
+
+        assert!(evaluate(&eval, "KsQdJcTh9s").unwrap() > evaluate(&eval, "As2d3c4hAh").unwrap());
+        assert!(evaluate(&eval, "As2d3c4hAh").unwrap() > evaluate(&eval, "As2d3c3hAh").unwrap());
+        assert!(evaluate(&eval, "As2d2c3hAh").unwrap() > evaluate(&eval, "AsAd2cAh3h").unwrap());
+    }
+
+    fn evaluate_deuce_to_seven(cards: &str) -> Rank {
+        DeuceToSevenEvaluator{}.evaluate_hand(&Card::vec_from_str(cards).unwrap(), &Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn deuce_to_seven_number_one() {
+        let number_one = evaluate_deuce_to_seven("7s5d4c3h2s");
+
+        assert!(number_one > evaluate_deuce_to_seven("7s6d4c3h2s"));
+        assert!(evaluate_deuce_to_seven("7s6d4c3h2s") > evaluate_deuce_to_seven("8s5d4c3h2s"));
+        assert!(evaluate_deuce_to_seven("KsQdJc9h8s") > evaluate_deuce_to_seven("AsQdJc9h8s"));
+        assert!(evaluate_deuce_to_seven("AsKdQcJh9s") > evaluate_deuce_to_seven("2s2d3c4h5s"));
+    }
+
+    #[test]
+    fn deuce_to_seven_straights_and_flushes_count() {
+        // 6-5-4-3-2 is a straight, A-2-3-4-5 is only ace high
+        assert!(evaluate_deuce_to_seven("8s6d4c3h2s") > evaluate_deuce_to_seven("6s5d4c3h2s"));
+        assert!(evaluate_deuce_to_seven("As5d4c3h2s") > evaluate_deuce_to_seven("6s5d4c3h2s"));
+        assert!(evaluate_deuce_to_seven("8s6d4c3h2s") > evaluate_deuce_to_seven("7s5s4s3s2s"));
+        assert!(evaluate_deuce_to_seven("2s2d3c3h4s") > evaluate_deuce_to_seven("7s5s4s3s2s"));
+    }
+
+    fn evaluate_badugi(eval: &BadugiEvaluator, cards: &str) -> Rank {
+        eval.evaluate_hand(&Card::vec_from_str(cards).unwrap(), &Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn badugi_more_cards_wins() {
+        let eval = BadugiEvaluator::new();
+
+        assert!(evaluate_badugi(&eval, "As2d3c4h") > evaluate_badugi(&eval, "As2d3c5h"));
+        assert!(evaluate_badugi(&eval, "KsQdJcTh") > evaluate_badugi(&eval, "As2d3c3h"));
+        // a three card badugi of A23 beats a three card badugi of A24
+        assert!(evaluate_badugi(&eval, "As2d3c3h") > evaluate_badugi(&eval, "As2d4c4h"));
+        assert!(evaluate_badugi(&eval, "As2s3c4h") > evaluate_badugi(&eval, "AsAdAcAh"));
+    }
+
+    #[test]
+    fn badugi_best_four_of_five() {
+        let eval = BadugiEvaluator::new();
+        assert_eq!(evaluate_badugi(&eval, "As2d3c4h"), evaluate_badugi(&eval, "As2d3c4hKh"));
+        assert_eq!(evaluate_badugi(&eval, "As2d3c4h"), evaluate_badugi(&eval, "Ks2d3c4hAs"));
+
+        // aces are the worst card when they play high
+        let eval = BadugiEvaluator::ace_high();
+        assert!(evaluate_badugi(&eval, "2s3d4c5h") > evaluate_badugi(&eval, "As2d3c4h"));
+        assert!(evaluate_badugi(&eval, "2s3d4cKh") > evaluate_badugi(&eval, "As2d3c4h"));
+    }
+}
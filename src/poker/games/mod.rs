@@ -3,8 +3,35 @@ pub use self::gamevariation::*;
 
 mod evaluator_results;
 
+mod draw_deck;
+pub use self::draw_deck::*;
+
 mod five_card_draw;
 pub use self::five_card_draw::*;
 
-mod default_game;
-pub use self::default_game::DefaultGame;
+mod deuce_to_seven_draw;
+pub use self::deuce_to_seven_draw::*;
+
+mod badugi;
+pub use self::badugi::*;
+
+mod low_evaluator;
+pub use self::low_evaluator::*;
+
+mod community_board;
+pub use self::community_board::*;
+
+mod texas_holdem;
+pub use self::texas_holdem::*;
+
+mod omaha;
+pub use self::omaha::*;
+
+mod seven_card_stud;
+pub use self::seven_card_stud::*;
+
+mod short_deck_holdem;
+pub use self::short_deck_holdem::*;
+
+mod custom_game;
+pub use self::custom_game::*;
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use playing_cards::core::Card;
+use playing_cards::poker::{Evaluator, HighEvaluator, Rank};
+
+use crate::poker::games::{CommunityBoard, GameVariation, LowEvaluator, Street, get_combinations};
+use crate::poker::pots::OddChipPriority;
+use crate::poker::Player;
+
+pub struct Omaha {
+    eval: HighEvaluator,
+    low_eval: Option<LowEvaluator>,     // splits the pot with the best eight or better low in hi-lo
+    hand_size: usize,   // 4, 5 or 6 hole cards
+
+    board: CommunityBoard,
+}
+
+impl Omaha {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 10;
+    const MAX_RUNOUTS: usize = 3;
+    const HAND_CARDS_USED: usize = 2;
+    const BOARD_CARDS_USED: usize = 3;
+
+    pub fn new(hand_size: usize) -> Omaha {
+        Omaha {
+            eval: HighEvaluator{},
+            low_eval: None,
+            hand_size: hand_size,
+
+            board: CommunityBoard::new(),
+        }
+    }
+
+    pub fn new_hi_lo(hand_size: usize) -> Omaha {
+        Omaha {
+            eval: HighEvaluator{},
+            low_eval: Some(LowEvaluator::eight_or_better()),
+            hand_size: hand_size,
+
+            board: CommunityBoard::new(),
+        }
+    }
+
+    // Two boards, each one winning half of every pot
+    pub fn new_double_board(hand_size: usize) -> Omaha {
+        Omaha {
+            eval: HighEvaluator{},
+            low_eval: None,
+            hand_size: hand_size,
+
+            board: CommunityBoard::new_double_board(),
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= self.get_min_player_count() && count <= self.get_max_player_count()
+    }
+
+    // Best of every hand made with exactly two of the player's cards and three from the board
+    fn get_best_hand<F>(& self, hand: &Vec<Card>, board_combinations: &Vec<Vec<Card>>, evaluate: F) -> Option<Rank>
+        where F: Fn(&Vec<Card>, &Vec<Card>) -> Option<Rank> {
+        let mut best: Option<Rank> = None;
+        for hand_cards in get_combinations(hand, Omaha::HAND_CARDS_USED) {
+            for board_cards in board_combinations {
+                if let Some(rank) = evaluate(&hand_cards, board_cards) {
+                    if best.as_ref().map_or(true, |best| rank > *best) {
+                        best = Some(rank);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    // In hi-lo, the low rankings only have the players that made a qualifying low
+    fn evaluate_board(& self, players: &HashMap<usize, Player>, board: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        let board_combinations = get_combinations(board, Omaha::BOARD_CARDS_USED);
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| {
+                self.get_best_hand(p.get_hand(), &board_combinations, |hand, board| {
+                    self.eval.evaluate_hand(hand, board).ok().and_then(|ranks| ranks.into_iter().next())
+                }).map(|rank| (*pos, rank))
+            })
+            .collect();
+
+        match &self.low_eval {
+            Some(low_eval) => {
+                let low_rankings = players.iter()
+                    .filter(|(_, p)| p.is_in_hand)
+                    .filter_map(|(pos, p)| {
+                        self.get_best_hand(p.get_hand(), &board_combinations, |hand, board| low_eval.evaluate_hand(hand, board))
+                            .map(|rank| (*pos, rank))
+                    })
+                    .collect();
+                vec![rankings, low_rankings]
+            },
+            None => vec![rankings],
+        }
+    }
+}
+
+impl GameVariation for Omaha {
+    fn get_min_player_count(& self) -> usize {
+        Omaha::MIN_PLAYER_COUNT
+    }
+
+    // Bigger hands and a second board leave fewer cards in the deck
+    fn get_max_player_count(& self) -> usize {
+        std::cmp::min(Omaha::MAX_PLAYER_COUNT, CommunityBoard::get_max_player_count(self.hand_size, self.board.get_board_count()))
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.board.reset();
+        self.board.deal_hands(players, self.hand_size)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        CommunityBoard::get_streets()
+    }
+
+    fn deal_street(&mut self, _players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+        self.board.deal_street(street_idx)
+    }
+
+    fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        Err("Cards can not be drawn in Omaha")
+    }
+
+    // The rankings on the second board follow the ones on the first
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let mut rankings = self.evaluate_board(players, &self.board.cards);
+        if self.board.get_board_count() > 1 {
+            rankings.extend(self.evaluate_board(players, &self.board.second_cards));
+        }
+        rankings
+    }
+
+    fn get_board(& self) -> Vec<Card> {
+        self.board.cards.clone()
+    }
+
+    fn get_second_board(& self) -> Vec<Card> {
+        self.board.second_cards.clone()
+    }
+
+    // The first board gets the odd chip
+    fn get_odd_chip_priority(& self) -> OddChipPriority {
+        if self.board.get_board_count() > 1 { OddChipPriority::RankPriority } else { OddChipPriority::OOP }
+    }
+
+    fn get_max_runouts(& self) -> usize {
+        self.board.get_max_runouts(Omaha::MAX_RUNOUTS)
+    }
+
+    fn deal_runouts(&mut self, count: usize) -> Result<Vec<Vec<Card>>, &str> {
+        self.board.deal_runouts(count)
+    }
+
+    fn evaluate_runout(& self, players: &HashMap<usize, Player>, runout: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        self.evaluate_board(players, &self.board.with_runout(runout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deal_hole_cards() {
+        for hand_size in [4, 5, 6] {
+            let mut game = Omaha::new(hand_size);
+            let mut players = get_players(&["", "", ""]);
+
+            assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+            assert!(players.values().all(|p| p.get_hand().len() == hand_size));
+        }
+    }
+
+    #[test]
+    fn max_players_by_hand_size() {
+        assert_eq!(10, Omaha::new(4).get_max_player_count());
+        assert_eq!(8, Omaha::new(5).get_max_player_count());
+        assert_eq!(7, Omaha::new(6).get_max_player_count());
+        // the second board takes 8 more cards
+        assert_eq!(9, Omaha::new_double_board(4).get_max_player_count());
+
+        let mut game = Omaha::new(6);
+        let mut players = get_players(&["", "", "", "", "", "", "", ""]);
+        assert!(game.start_normal(&mut players, 0).is_err());
+    }
+
+    #[test]
+    fn must_use_two_hole_cards() {
+        let mut game = Omaha::new(4);
+        game.board.cards = Card::vec_from_str("AhKh7h2h3c").unwrap();
+        // a single heart does not make a flush, while a pair in hand makes a set with the board
+        let players = get_players(&["Qh5s6s9c", "2c2d8s9s"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings[0].len());
+        assert!(rankings[0][&1] > rankings[0][&0]);
+    }
+
+    #[test]
+    fn board_does_not_play() {
+        let mut game = Omaha::new(4);
+        game.board.cards = Card::vec_from_str("AhKhQhJhTh").unwrap();
+        let players = get_players(&["2c3c4d5d", "9h8c2s3s"]);
+
+        // the royal flush on the board can not be played, but 9h8c makes a straight with QhJhTh
+        let rankings = game.evaluate_all_hands(&players);
+        assert!(rankings[0][&1] > rankings[0][&0]);
+    }
+
+    #[test]
+    fn double_board_deals_and_ranks_each_board() {
+        let mut game = Omaha::new_double_board(4);
+        let mut players = get_players(&["", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        for street_idx in 1..4 {
+            assert!(game.deal_street(&mut players, street_idx).is_ok());
+        }
+        assert_eq!(5, game.get_board().len());
+        assert_eq!(5, game.get_second_board().len());
+        assert_eq!(1, game.get_max_runouts());
+
+        game.board.cards = Card::vec_from_str("AhKh7h2h3c").unwrap();
+        game.board.second_cards = Card::vec_from_str("9d8d4s5c2s").unwrap();
+        // a flush on the first board, a straight on the second
+        let players = get_players(&["QhJh6s3s", "TdJc7c6c"]);
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        assert!(rankings[1][&1] > rankings[1][&0]);
+    }
+
+    #[test]
+    fn best_of_six_hole_cards() {
+        let mut game = Omaha::new(6);
+        game.board.cards = Card::vec_from_str("9s8s2d3c4h").unwrap();
+        let players = get_players(&["AsKs2c7d6d5c", "AhAdAcKdKcQd"]);
+
+        // 6d5c makes a straight with the low cards on the board, the aces only make a pair
+        let rankings = game.evaluate_all_hands(&players);
+        assert!(rankings[0][&0] > rankings[0][&1]);
+    }
+
+    #[test]
+    fn hi_lo_low_uses_two_hole_cards() {
+        let mut game = Omaha::new_hi_lo(4);
+        game.board.cards = Card::vec_from_str("2h3d4cKsQd").unwrap();
+        // a single low card in hand can not make a low, even with three low cards on the board
+        let players = get_players(&["AsKhKcQs", "Ah5s9d9c", "7s8d9hTc"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert_eq!(3, rankings[0].len());
+        assert_eq!(2, rankings[1].len());
+        assert!(!rankings[1].contains_key(&0));
+        // A2345 beats 87432
+        assert!(rankings[1][&1] > rankings[1][&2]);
+    }
+
+    #[test]
+    fn hi_lo_needs_three_low_board_cards() {
+        let mut game = Omaha::new_hi_lo(4);
+        game.board.cards = Card::vec_from_str("2h3dKcKsQd").unwrap();
+        let players = get_players(&["As4h5c6d", "7s8d9hTc"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert!(rankings[1].is_empty());
+    }
+}
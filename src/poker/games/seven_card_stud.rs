@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use playing_cards::core::{Card, CardDeck, Suit};
+use playing_cards::poker::{Evaluator, HighEvaluator, Rank};
+
+use crate::poker::games::{GameVariation, LowEvaluator, Street};
+use crate::poker::Player;
+
+// Seven card stud, along with stud hi-lo and razz. Instead of sharing a board, every player is dealt their own down cards
+// and up cards. If the deck runs out before the river, a single community card is dealt to everyone instead
+pub struct SevenCardStud {
+    deck: CardDeck,
+    cards_left: usize,
+    high_eval: Option<HighEvaluator>,   // None in razz, where only the low wins
+    low_eval: Option<LowEvaluator>,     // splits the pot with the best eight or better low in hi-lo
+
+    btn_idx: usize,
+    dealt_streets: usize,
+    board: Vec<Card>,                   // the community card, if one had to be dealt
+}
+
+impl SevenCardStud {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 8;
+    const DECK_SIZE: usize = 52;
+    const STREET_CARDS: [(usize, usize); 5] = [(2, 1), (0, 1), (0, 1), (0, 1), (1, 0)];    // (down, up) cards dealt on each street, 3rd street to the river
+
+    pub fn new() -> SevenCardStud {
+        SevenCardStud::with_evaluators(Some(HighEvaluator{}), None)
+    }
+
+    pub fn new_hi_lo() -> SevenCardStud {
+        SevenCardStud::with_evaluators(Some(HighEvaluator{}), Some(LowEvaluator::eight_or_better()))
+    }
+
+    // Best A-5 low takes the whole pot, and every hand makes a low
+    pub fn new_razz() -> SevenCardStud {
+        SevenCardStud::with_evaluators(None, Some(LowEvaluator::new()))
+    }
+
+    fn with_evaluators(high_eval: Option<HighEvaluator>, low_eval: Option<LowEvaluator>) -> SevenCardStud {
+        SevenCardStud {
+            deck: CardDeck::new().unwrap(),
+            cards_left: SevenCardStud::DECK_SIZE,
+            high_eval: high_eval,
+            low_eval: low_eval,
+
+            btn_idx: 0,
+            dealt_streets: 0,
+            board: Vec::new(),
+        }
+    }
+
+    fn is_razz(& self) -> bool {
+        self.high_eval.is_none()
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= SevenCardStud::MIN_PLAYER_COUNT && count <= SevenCardStud::MAX_PLAYER_COUNT
+    }
+
+    // Clubs are the lowest suit, followed by diamonds, hearts and spades
+    fn get_suit_rank(suit: Suit) -> u32 {
+        match suit {
+            Suit::Club => 0,
+            Suit::Diamond => 1,
+            Suit::Heart => 2,
+            Suit::Spade => 3,
+        }
+    }
+
+    // Aces are low in razz and high in every other game
+    fn get_value(& self, card: &Card) -> u32 {
+        if self.is_razz() {
+            LowEvaluator::get_ace_low_value(card)
+        } else {
+            card.value as u32 + 2
+        }
+    }
+
+    // Cards showing as (count, value) groups, the largest group first and then the highest value. Straights and
+    // flushes showing do not count
+    fn get_showing(& self, cards: &Vec<Card>) -> Vec<(usize, u32)> {
+        let mut groups: Vec<(usize, u32)> = Vec::new();
+        for value in cards.iter().map(|card| self.get_value(card)) {
+            match groups.iter_mut().find(|(_, group_value)| *group_value == value) {
+                Some(group) => group.0 += 1,
+                None => groups.push((1, value)),
+            }
+        }
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        groups
+    }
+
+    // Players in the hand starting left of the button, which is the order cards are dealt in and ties are broken by
+    fn get_deal_order(& self, players: &HashMap<usize, Player>) -> Vec<usize> {
+        let mut order: Vec<usize> = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .map(|(pos, _)| *pos)
+            .collect();
+        order.sort_by_key(|pos| (*pos <= self.btn_idx, *pos));
+        order
+    }
+
+    fn deal(&mut self, count: usize) -> Result<Vec<Card>, &'static str> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        match self.deck.deal_cards(count) {
+            (Some(cards), _) => {
+                self.cards_left -= count;
+                Ok(cards)
+            },
+            _ => Err("Not enough cards left in the deck"),
+        }
+    }
+
+    // Deals everyone still in the hand their cards for the street, or a single community card when the deck can not
+    // cover everyone. Returns the community card if one was dealt
+    fn deal_street_cards(&mut self, players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &'static str> {
+        let (down, up) = match SevenCardStud::STREET_CARDS.get(street_idx) {
+            Some(street_cards) => *street_cards,
+            None => return Ok(Vec::new()),
+        };
+        if street_idx < self.dealt_streets {
+            return Ok(Vec::new());
+        }
+        self.dealt_streets = street_idx + 1;
+
+        let order = self.get_deal_order(players);
+        if self.cards_left < order.len() * (down + up) {
+            let cards = self.deal(1)?;
+            self.board.extend(cards.iter().cloned());
+            return Ok(cards);
+        }
+
+        for pos in order {
+            let down_cards = self.deal(down)?;
+            let up_cards = self.deal(up)?;
+            let player = players.get_mut(&pos).unwrap();
+            player.deal_cards(down_cards, false);
+            player.deal_cards(up_cards, true);
+        }
+        Ok(Vec::new())
+    }
+}
+
+impl GameVariation for SevenCardStud {
+    fn get_min_player_count(& self) -> usize {
+        SevenCardStud::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        SevenCardStud::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.deck = CardDeck::new().unwrap();
+        self.cards_left = SevenCardStud::DECK_SIZE;
+        self.btn_idx = btn_idx;
+        self.dealt_streets = 0;
+        self.board.clear();
+
+        for (_, p) in players.iter_mut().filter(|(_, p)| p.is_in_hand) {
+            p.set_new_hand(Vec::new());
+        }
+        self.deal_street_cards(players, 0)?;
+        Ok(())
+    }
+
+    // 3rd street through the river, with the big bet coming in on 5th street
+    fn get_streets(& self) -> Vec<Street> {
+        vec![Street::Betting; SevenCardStud::STREET_CARDS.len()]
+    }
+
+    fn deal_street(&mut self, players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+        self.deal_street_cards(players, street_idx)
+    }
+
+    fn has_blinds(& self) -> bool {
+        false
+    }
+
+    // The lowest door card brings in, or the highest one in razz. Ties in value go by suit
+    fn get_bring_in_idx(& self, players: &HashMap<usize, Player>) -> Option<usize> {
+        let door_cards = self.get_deal_order(players).into_iter()
+            .filter_map(|pos| players[&pos].get_up_cards().first().map(|card| (pos, (self.get_value(card), SevenCardStud::get_suit_rank(card.suit)))));
+
+        let bring_in = if self.is_razz() {
+            door_cards.max_by_key(|(_, door_card)| *door_card)
+        } else {
+            door_cards.min_by_key(|(_, door_card)| *door_card)
+        };
+        bring_in.map(|(pos, _)| pos)
+    }
+
+    // After 3rd street, the best hand showing acts first (the lowest one in razz). Ties go to the player closest
+    // to the left of the button
+    fn get_first_to_act(& self, players: &HashMap<usize, Player>, street_idx: usize) -> Option<usize> {
+        if street_idx == 0 {
+            return None;
+        }
+
+        let mut first: Option<(usize, Vec<(usize, u32)>)> = None;
+        for pos in self.get_deal_order(players) {
+            let showing = self.get_showing(players[&pos].get_up_cards());
+            let is_better = first.as_ref().map_or(true, |(_, best)| {
+                if self.is_razz() { showing < *best } else { showing > *best }
+            });
+            if is_better {
+                first = Some((pos, showing));
+            }
+        }
+        first.map(|(pos, _)| pos)
+    }
+
+    fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        Err("Cards can not be drawn in stud")
+    }
+
+    // Best five of each player's seven cards (six and the community card if one was dealt). In hi-lo, the low rankings
+    // only have the players that made a qualifying low
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let high_rankings = self.high_eval.as_ref().map(|eval| {
+            players.iter()
+                .filter(|(_, p)| p.is_in_hand)
+                .filter_map(|(pos, p)| {
+                    eval.evaluate_hand(p.get_hand(), &self.board).ok()
+                        .and_then(|ranks| ranks.into_iter().next())
+                        .map(|rank| (*pos, rank))
+                })
+                .collect()
+        });
+        let low_rankings = self.low_eval.as_ref().map(|eval| {
+            players.iter()
+                .filter(|(_, p)| p.is_in_hand)
+                .filter_map(|(pos, p)| eval.evaluate_hand(p.get_hand(), &self.board).map(|rank| (*pos, rank)))
+                .collect()
+        });
+
+        high_rankings.into_iter().chain(low_rankings).collect()
+    }
+
+    fn get_board(& self) -> Vec<Card> {
+        self.board.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hands are given as down cards followed by up cards, split by a '/'
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                let (down, up) = hand.split_once('/').unwrap_or((hand, ""));
+                player.deal_cards(Card::vec_from_str(down).unwrap(), false);
+                player.deal_cards(Card::vec_from_str(up).unwrap(), true);
+                (pos, player)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deal_up_and_down_cards() {
+        let mut game = SevenCardStud::new();
+        let mut players = get_players(&["", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        assert!(players.values().all(|p| p.get_hand().len() == 3 && p.get_up_cards().len() == 1));
+
+        // 3rd street was dealt with the hand
+        assert_eq!(Ok(Vec::new()), game.deal_street(&mut players, 0));
+        for street_idx in 1..4 {
+            assert_eq!(Ok(Vec::new()), game.deal_street(&mut players, street_idx));
+        }
+        assert!(players.values().all(|p| p.get_hand().len() == 6 && p.get_up_cards().len() == 4));
+
+        // the river is dealt face down
+        assert_eq!(Ok(Vec::new()), game.deal_street(&mut players, 4));
+        assert!(players.values().all(|p| p.get_hand().len() == 7 && p.get_up_cards().len() == 4));
+        assert_eq!(5, game.get_streets().len());
+        assert_eq!(2, game.get_big_bet_street_idx());
+    }
+
+    #[test]
+    fn community_card_when_deck_runs_out() {
+        let mut game = SevenCardStud::new();
+        let mut players = get_players(&["", "", "", "", "", "", "", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        for street_idx in 1..4 {
+            assert_eq!(Ok(Vec::new()), game.deal_street(&mut players, street_idx));
+        }
+
+        // 4 cards are left for 8 players
+        assert_eq!(1, game.deal_street(&mut players, 4).unwrap().len());
+        assert_eq!(1, game.get_board().len());
+        assert!(players.values().all(|p| p.get_hand().len() == 6));
+        assert_eq!(8, game.evaluate_all_hands(&players)[0].len());
+    }
+
+    #[test]
+    fn lowest_door_card_brings_in() {
+        let game = SevenCardStud::new();
+        // the deuce of clubs is the lowest suit
+        let players = get_players(&["AsAh/2d", "KsKh/2c", "QsQh/3c"]);
+        assert_eq!(Some(1), game.get_bring_in_idx(&players));
+
+        // aces are high
+        let players = get_players(&["2s2h/Ad", "KsKh/Kc"]);
+        assert_eq!(Some(1), game.get_bring_in_idx(&players));
+    }
+
+    #[test]
+    fn razz_highest_door_card_brings_in() {
+        let game = SevenCardStud::new_razz();
+        // kings are high and aces are low, and spades is the highest suit
+        let players = get_players(&["2s3h/Kd", "4s5h/Ks", "6s7h/Ac"]);
+        assert_eq!(Some(1), game.get_bring_in_idx(&players));
+    }
+
+    #[test]
+    fn best_hand_showing_acts_first() {
+        let mut game = SevenCardStud::new();
+        game.btn_idx = 1;
+        assert_eq!(None, game.get_first_to_act(&get_players(&["2s3h/Ad", "4s5h/Ks"]), 0));
+
+        // a pair showing beats ace king
+        let players = get_players(&["2s3h/AdKd", "4s5h/7s7c", "6s8h/QcJc"]);
+        assert_eq!(Some(1), game.get_first_to_act(&players, 1));
+
+        // the same hand showing goes to the player closest to the left of the button
+        let players = get_players(&["2s3h/AdKd", "4s5h/QsJc", "6s8h/AcKc"]);
+        assert_eq!(Some(2), game.get_first_to_act(&players, 1));
+    }
+
+    #[test]
+    fn razz_lowest_hand_showing_acts_first() {
+        let game = SevenCardStud::new_razz();
+        // a pair is the worst hand showing, and aces are low
+        let players = get_players(&["KsKh/2d2c", "QsQh/6c4d", "JsJh/As7c"]);
+        assert_eq!(Some(1), game.get_first_to_act(&players, 1));
+    }
+
+    #[test]
+    fn hi_lo_splits_high_and_low() {
+        let game = SevenCardStud::new_hi_lo();
+        let players = get_players(&["AsAh/AdKc7h5s2d", "2c3d/4h5c8s9dTh", "KsKd/QhQcJdJs9c"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&2]);
+        assert!(rankings[0][&2] > rankings[0][&1]);
+        // only the eight low qualifies
+        assert_eq!(1, rankings[1].len());
+        assert!(rankings[1].contains_key(&1));
+    }
+
+    #[test]
+    fn razz_single_low_objective() {
+        let game = SevenCardStud::new_razz();
+        let players = get_players(&["AsKh/2d3c4h5sKd", "6c7d/8h9cTsJdQh"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use playing_cards::core::{Card, Value};
+use playing_cards::poker::{HighRank, Rank};
+
+use crate::poker::games::{get_combinations, CommunityBoard, GameVariation, Street};
+use crate::poker::Player;
+
+// High hands of the 36 card deck. A-6-7-8-9 is the lowest straight and, with fewer cards of every suit, a flush beats a
+// full house
+pub struct ShortDeckEvaluator {}
+
+impl ShortDeckEvaluator {
+    const HAND_SIZE: usize = 5;
+    const BASE: u32 = 15;
+
+    // Higher is better
+    fn get_score(cards: &Vec<Card>) -> u32 {
+        let mut counts = [0u32; 15];
+        for card in cards {
+            counts[card.value as usize + 2] += 1;
+        }
+
+        let mut groups: Vec<(u32, u32)> = (2..15)
+            .filter(|value| counts[*value] > 0)
+            .map(|value| (counts[value], value as u32))
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+
+        // the ace plays below the six, the way it plays below the two in a full deck
+        let is_low_straight = groups.iter().map(|(_, value)| *value).eq([14, 9, 8, 7, 6]);
+        if is_low_straight {
+            groups.rotate_left(1);
+            groups[4].1 = 5;
+        }
+
+        let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+        let is_straight = groups.len() == ShortDeckEvaluator::HAND_SIZE && groups[0].1 - groups[4].1 == 4;
+        let category = match (is_straight, is_flush, groups[0].0, groups.get(1).map_or(0, |group| group.0)) {
+            (true, true, _, _) => 8,
+            (_, _, 4, _) => 7,
+            (_, true, _, _) => 6,
+            (_, _, 3, 2) => 5,
+            (true, _, _, _) => 4,
+            (_, _, 3, _) => 3,
+            (_, _, 2, 2) => 2,
+            (_, _, 2, _) => 1,
+            _ => 0,
+        };
+        let kickers = groups.iter()
+            .flat_map(|(count, value)| std::iter::repeat(*value).take(*count as usize))
+            .fold(0, |score, value| score * ShortDeckEvaluator::BASE + value);
+        category * ShortDeckEvaluator::BASE.pow(ShortDeckEvaluator::HAND_SIZE as u32) + kickers
+    }
+
+    // Best five of the player's cards and the board
+    pub fn evaluate_hand(& self, player_hand: &Vec<Card>, board: &Vec<Card>) -> Option<Rank> {
+        let cards: Vec<Card> = player_hand.iter().chain(board.iter()).cloned().collect();
+        get_combinations(&cards, ShortDeckEvaluator::HAND_SIZE).iter()
+            .map(|hand| ShortDeckEvaluator::get_score(hand))
+            .max()
+            .map(|score| Rank::High(HighRank::new(score)))
+    }
+}
+
+// Hold'em dealt from a deck without the twos through fives
+pub struct ShortDeckHoldem {
+    eval: ShortDeckEvaluator,
+
+    board: CommunityBoard,
+}
+
+impl ShortDeckHoldem {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 9;
+    const HAND_SIZE: usize = 2;
+    const MAX_RUNOUTS: usize = 3;
+
+    pub fn new() -> ShortDeckHoldem {
+        ShortDeckHoldem {
+            eval: ShortDeckEvaluator{},
+
+            board: CommunityBoard::new_stripped(Value::Six),
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= ShortDeckHoldem::MIN_PLAYER_COUNT && count <= ShortDeckHoldem::MAX_PLAYER_COUNT
+    }
+
+    fn evaluate_board(& self, players: &HashMap<usize, Player>, board: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| self.eval.evaluate_hand(p.get_hand(), board).map(|rank| (*pos, rank)))
+            .collect();
+        vec![rankings]
+    }
+}
+
+impl GameVariation for ShortDeckHoldem {
+    fn get_min_player_count(& self) -> usize {
+        ShortDeckHoldem::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        ShortDeckHoldem::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.board.reset();
+        self.board.deal_hands(players, ShortDeckHoldem::HAND_SIZE)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        CommunityBoard::get_streets()
+    }
+
+    fn deal_street(&mut self, _players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+        self.board.deal_street(street_idx)
+    }
+
+    fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        Err("Cards can not be drawn in Short Deck Hold'em")
+    }
+
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        self.evaluate_board(players, &self.board.cards)
+    }
+
+    fn get_board(& self) -> Vec<Card> {
+        self.board.cards.clone()
+    }
+
+    fn get_max_runouts(& self) -> usize {
+        self.board.get_max_runouts(ShortDeckHoldem::MAX_RUNOUTS)
+    }
+
+    fn deal_runouts(&mut self, count: usize) -> Result<Vec<Vec<Card>>, &str> {
+        self.board.deal_runouts(count)
+    }
+
+    fn evaluate_runout(& self, players: &HashMap<usize, Player>, runout: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        self.evaluate_board(players, &self.board.with_runout(runout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    fn rank(cards: &str) -> Rank {
+        ShortDeckEvaluator{}.evaluate_hand(&Card::vec_from_str(cards).unwrap(), &Vec::new()).unwrap()
+    }
+
+    #[test]
+    fn deal_from_short_deck() {
+        let mut game = ShortDeckHoldem::new();
+        let mut players = get_players(&["", "", ""]);
+
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        for street_idx in 1..4 {
+            assert!(game.deal_street(&mut players, street_idx).is_ok());
+        }
+        assert_eq!(5, game.get_board().len());
+        assert!(players.values()
+            .flat_map(|p| p.get_hand().iter())
+            .chain(game.get_board().iter())
+            .all(|card| card.value as usize >= Value::Six as usize));
+    }
+
+    #[test]
+    fn flush_beats_full_house() {
+        assert!(rank("Jh9h8h7h6h") > rank("AsAdAcKsKd"));
+        assert!(rank("AsAdAcKsKd") > rank("AhKdQcJsTs"));
+        assert!(rank("6s6d6c6hAh") > rank("KhJh9h7h6h"));
+        // higher flushes still beat lower ones
+        assert!(rank("AhJh9h7h6h") > rank("KhQhJh9h7h"));
+    }
+
+    #[test]
+    fn ace_plays_low_in_straight() {
+        assert!(rank("As6d7c8h9s") > rank("AsAdKcQhJs"));
+        // lowest straight
+        assert!(rank("6s7d8c9hTs") > rank("As6d7c8h9s"));
+        assert!(rank("AhKdQcJsTs") > rank("6s7d8c9hTs"));
+        // and the lowest straight flush
+        assert!(rank("Ah6h7h8h9h") > rank("AsAdAcAhKs"));
+        assert!(rank("6h7h8h9hTh") > rank("Ah6h7h8h9h"));
+    }
+
+    #[test]
+    fn best_hand_on_board() {
+        let mut game = ShortDeckHoldem::new();
+        game.board.cards = Card::vec_from_str("Ac6h7s8hKh").unwrap();
+        // the straight with the board's ace, a flush and aces up
+        let players = get_players(&["9c9d", "QhJh", "AdKd"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert!(rankings[0][&1] > rankings[0][&0]);
+        assert!(rankings[0][&0] > rankings[0][&2]);
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use playing_cards::core::Card;
+use playing_cards::poker::{Evaluator, HighEvaluator, Rank};
+
+use crate::poker::games::{CommunityBoard, GameVariation, LowEvaluator, Street};
+use crate::poker::pots::OddChipPriority;
+use crate::poker::Player;
+
+pub struct TexasHoldem {
+    eval: HighEvaluator,
+    low_eval: Option<LowEvaluator>,     // splits the pot with the best eight or better low in hi-lo
+
+    board: CommunityBoard,
+}
+
+impl TexasHoldem {
+    const MIN_PLAYER_COUNT: usize = 2;
+    const MAX_PLAYER_COUNT: usize = 10;
+    const HAND_SIZE: usize = 2;
+    const MAX_RUNOUTS: usize = 3;
+
+    pub fn new() -> TexasHoldem {
+        TexasHoldem {
+            eval: HighEvaluator{},
+            low_eval: None,
+
+            board: CommunityBoard::new(),
+        }
+    }
+
+    pub fn new_hi_lo() -> TexasHoldem {
+        TexasHoldem {
+            eval: HighEvaluator{},
+            low_eval: Some(LowEvaluator::eight_or_better()),
+
+            board: CommunityBoard::new(),
+        }
+    }
+
+    // Two boards, each one winning half of every pot
+    pub fn new_double_board() -> TexasHoldem {
+        TexasHoldem {
+            eval: HighEvaluator{},
+            low_eval: None,
+
+            board: CommunityBoard::new_double_board(),
+        }
+    }
+
+    fn check_player_condition(& self, players: &HashMap<usize, Player>) -> bool {
+        let count = players.values().filter(|p| p.is_in_hand).count();
+        count >= TexasHoldem::MIN_PLAYER_COUNT && count <= TexasHoldem::MAX_PLAYER_COUNT
+    }
+
+    // Best five of the seven cards made with the player's two hole cards and the five on the board. In hi-lo, the
+    // low rankings only have the players that made a qualifying low
+    fn evaluate_board(& self, players: &HashMap<usize, Player>, board: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        let rankings = players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .filter_map(|(pos, p)| {
+                self.eval.evaluate_hand(p.get_hand(), board).ok()
+                    .and_then(|ranks| ranks.into_iter().next())
+                    .map(|rank| (*pos, rank))
+            })
+            .collect();
+
+        match &self.low_eval {
+            Some(low_eval) => {
+                let low_rankings = players.iter()
+                    .filter(|(_, p)| p.is_in_hand)
+                    .filter_map(|(pos, p)| low_eval.evaluate_hand(p.get_hand(), board).map(|rank| (*pos, rank)))
+                    .collect();
+                vec![rankings, low_rankings]
+            },
+            None => vec![rankings],
+        }
+    }
+}
+
+impl GameVariation for TexasHoldem {
+    fn get_min_player_count(& self) -> usize {
+        TexasHoldem::MIN_PLAYER_COUNT
+    }
+
+    fn get_max_player_count(& self) -> usize {
+        TexasHoldem::MAX_PLAYER_COUNT
+    }
+
+    fn start_normal(&mut self, players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+        if !self.check_player_condition(players) {
+            return Err("Does not meet player requirements");
+        }
+
+        self.board.reset();
+        self.board.deal_hands(players, TexasHoldem::HAND_SIZE)
+    }
+
+    fn get_streets(& self) -> Vec<Street> {
+        CommunityBoard::get_streets()
+    }
+
+    fn deal_street(&mut self, _players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+        self.board.deal_street(street_idx)
+    }
+
+    fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+        Err("Cards can not be drawn in Texas Hold'em")
+    }
+
+    // The rankings on the second board follow the ones on the first
+    fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+        let mut rankings = self.evaluate_board(players, &self.board.cards);
+        if self.board.get_board_count() > 1 {
+            rankings.extend(self.evaluate_board(players, &self.board.second_cards));
+        }
+        rankings
+    }
+
+    fn get_board(& self) -> Vec<Card> {
+        self.board.cards.clone()
+    }
+
+    fn get_second_board(& self) -> Vec<Card> {
+        self.board.second_cards.clone()
+    }
+
+    // The first board gets the odd chip
+    fn get_odd_chip_priority(& self) -> OddChipPriority {
+        if self.board.get_board_count() > 1 { OddChipPriority::RankPriority } else { OddChipPriority::OOP }
+    }
+
+    fn get_max_runouts(& self) -> usize {
+        self.board.get_max_runouts(TexasHoldem::MAX_RUNOUTS)
+    }
+
+    fn deal_runouts(&mut self, count: usize) -> Result<Vec<Vec<Card>>, &str> {
+        self.board.deal_runouts(count)
+    }
+
+    fn evaluate_runout(& self, players: &HashMap<usize, Player>, runout: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+        self.evaluate_board(players, &self.board.with_runout(runout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_players(hands: &[&str]) -> HashMap<usize, Player> {
+        hands.iter()
+            .enumerate()
+            .map(|(pos, hand)| {
+                let mut player = Player::new(pos, format!("Player {}", pos), 100);
+                player.set_new_hand(Card::vec_from_str(hand).unwrap());
+                (pos, player)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deal_hole_cards() {
+        let mut game = TexasHoldem::new();
+        let mut players = get_players(&["", "", ""]);
+        players.get_mut(&2).unwrap().is_in_hand = false;
+
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+        assert_eq!(2, players[&0].get_hand().len());
+        assert_eq!(2, players[&1].get_hand().len());
+        assert_eq!(0, players[&2].get_hand().len());
+        assert!(game.get_board().is_empty());
+    }
+
+    #[test]
+    fn not_enough_players() {
+        let mut game = TexasHoldem::new();
+        let mut players = get_players(&[""]);
+        assert!(game.start_normal(&mut players, 0).is_err());
+    }
+
+    #[test]
+    fn deal_board_streets() {
+        let mut game = TexasHoldem::new();
+        let mut players = get_players(&["", ""]);
+        assert_eq!(Ok(()), game.start_normal(&mut players, 0));
+
+        assert_eq!(Ok(Vec::new()), game.deal_street(&mut players, 0));
+        assert_eq!(3, game.deal_street(&mut players, 1).unwrap().len());
+        assert_eq!(1, game.deal_street(&mut players, 2).unwrap().len());
+        assert_eq!(1, game.deal_street(&mut players, 3).unwrap().len());
+        assert_eq!(5, game.get_board().len());
+        assert_eq!(1, game.get_max_runouts());
+    }
+
+    #[test]
+    fn best_five_of_seven() {
+        let mut game = TexasHoldem::new();
+        game.board.cards = Card::vec_from_str("AhKhQh2c2d").unwrap();
+        let players = get_players(&["JhTh", "2s2h", "AsAc"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(1, rankings.len());
+        assert_eq!(3, rankings[0].len());
+        // royal flush beats quad deuces which beats aces full
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        assert!(rankings[0][&1] > rankings[0][&2]);
+    }
+
+    #[test]
+    fn board_plays() {
+        let mut game = TexasHoldem::new();
+        game.board.cards = Card::vec_from_str("AhKdQsJcTh").unwrap();
+        let players = get_players(&["2c3d", "4s5s"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(rankings[0][&0], rankings[0][&1]);
+    }
+
+    #[test]
+    fn hi_lo_splits_high_and_low() {
+        let mut game = TexasHoldem::new_hi_lo();
+        game.board.cards = Card::vec_from_str("Ah5d7cKsKd").unwrap();
+        let players = get_players(&["2c3d", "KcQh", "9s9h"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&1] > rankings[0][&0]);
+        assert!(rankings[0][&1] > rankings[0][&2]);
+        // only A2357 makes a low, the other players pair the board
+        assert_eq!(1, rankings[1].len());
+        assert!(rankings[1].contains_key(&0));
+    }
+
+    #[test]
+    fn hi_lo_without_qualifying_low() {
+        let mut game = TexasHoldem::new_hi_lo();
+        game.board.cards = Card::vec_from_str("AhKdQc9s2d").unwrap();
+        let players = get_players(&["3c4d", "JcTh"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[1].is_empty());
+    }
+
+    #[test]
+    fn double_board_ranks_each_board() {
+        let mut game = TexasHoldem::new_double_board();
+        game.board.cards = Card::vec_from_str("AhKhQh2c2d").unwrap();
+        game.board.second_cards = Card::vec_from_str("9s8s3d3c5h").unwrap();
+        let players = get_players(&["JhTh", "7s6s"]);
+
+        let rankings = game.evaluate_all_hands(&players);
+        assert_eq!(2, rankings.len());
+        assert!(rankings[0][&0] > rankings[0][&1]);
+        assert!(rankings[1][&1] > rankings[1][&0]);
+        assert_eq!(OddChipPriority::RankPriority, game.get_odd_chip_priority());
+        assert_eq!(OddChipPriority::OOP, TexasHoldem::new().get_odd_chip_priority());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use playing_cards::core::Card;
+use serde::Serialize;
+
+use crate::poker::{BlindPositions, LoggedAction, TableConfig};
+use crate::poker::pots::PartialPot;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct SeatHistory {
+    pub position: usize,
+    pub name: String,
+    pub stack: u64,     // stack before any blinds or antes were posted
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct DrawHistory {
+    pub position: usize,
+    pub discarded: Vec<Card>,
+    pub drawn: Vec<Card>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct SidePotHistory {
+    pub amount: u64,
+    pub eligible_players: BTreeSet<usize>,
+}
+
+impl SidePotHistory {
+    pub fn from_pots(pots: &Vec<PartialPot>) -> Vec<SidePotHistory> {
+        pots.iter()
+            .filter(|sidepot| sidepot.amount > 0)
+            .map(|sidepot| SidePotHistory {
+                amount: sidepot.amount,
+                eligible_players: sidepot.elegible_players.iter().cloned().collect(),
+            })
+            .collect()
+    }
+}
+
+// Everything that happened in a single hand, written out once the hand is over
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct HandHistory {
+    pub hand_number: u64,
+    pub started_at: u64,                        // seconds since the unix epoch
+    pub config: TableConfig,
+    pub seats: Vec<SeatHistory>,                // everyone dealt into the hand
+    pub blinds: Option<BlindPositions>,
+    pub straddles: Vec<usize>,
+    pub ante_amt: u64,                          // the bomb amount if this was a bomb pot
+    pub is_bomb_pot: bool,
+    pub hole_cards: BTreeMap<usize, Vec<Card>>, // as dealt, before any draws. In stud, every card dealt to the player
+    pub up_cards: BTreeMap<usize, Vec<Card>>,   // the cards of hole_cards that were dealt face up, in stud
+    pub actions: Vec<LoggedAction>,
+    pub draws: Vec<DrawHistory>,
+    pub board: Vec<Card>,
+    pub second_board: Vec<Card>,                // only in double board games
+    pub runouts: Vec<Vec<Card>>,                // extra boards if the hand was run more than once
+    pub side_pots: Vec<SidePotHistory>,
+    pub rake: u64,
+    pub winnings: Vec<HashMap<usize, u64>>,     // what each runout paid out, a single entry if it was run once
+    pub shown_hands: BTreeMap<usize, Vec<Card>>,
+    pub final_stacks: BTreeMap<usize, u64>,
+}
+
+impl HandHistory {
+    pub fn get_file_name(& self) -> String {
+        format!("hand_{}_{}.json", self.started_at, self.hand_number)
+    }
+
+    // Writes the hand as a JSON document into dir and returns the path of the file
+    pub fn write_to(& self, dir: &Path) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(self.get_file_name());
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::Value;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn get_history() -> HandHistory {
+        HandHistory {
+            hand_number: 3,
+            started_at: 1650000000,
+            config: TableConfig::new(),
+            seats: vec![
+                SeatHistory { position: 0, name: "Player 0".to_string(), stack: 200 },
+                SeatHistory { position: 2, name: "Player 2".to_string(), stack: 100 },
+            ],
+            blinds: None,
+            straddles: Vec::new(),
+            ante_amt: 0,
+            is_bomb_pot: false,
+            hole_cards: BTreeMap::from([(0, Card::vec_from_str("AsKs").unwrap()), (2, Card::vec_from_str("2d2c").unwrap())]),
+            up_cards: BTreeMap::new(),
+            actions: vec![LoggedAction::BetRaise { position: 0, amount: 100 }, LoggedAction::CheckCall { position: 2 }],
+            draws: Vec::new(),
+            board: Vec::new(),
+            second_board: Vec::new(),
+            runouts: Vec::new(),
+            side_pots: vec![SidePotHistory { amount: 200, eligible_players: BTreeSet::from([0, 2]) }],
+            rake: 0,
+            winnings: vec![HashMap::from([(2, 200)])],
+            shown_hands: BTreeMap::new(),
+            final_stacks: BTreeMap::from([(0, 100), (2, 200)]),
+        }
+    }
+
+    #[test]
+    fn side_pots_skip_empty_pots() {
+        let pots = vec![
+            PartialPot { amount: 30, elegible_players: HashSet::from([1, 0]) },
+            PartialPot { amount: 0, elegible_players: HashSet::from([1]) },
+        ];
+        assert_eq!(vec![SidePotHistory { amount: 30, eligible_players: BTreeSet::from([0, 1]) }], SidePotHistory::from_pots(&pots));
+    }
+
+    #[test]
+    fn write_hand_history() {
+        let dir = std::env::temp_dir().join(format!("hand_history_{}", Uuid::new_v4()));
+        let history = get_history();
+
+        let path = history.write_to(&dir).expect("Could not write hand history");
+        assert_eq!(dir.join("hand_1650000000_3.json"), path);
+
+        let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(3, json["handNumber"]);
+        assert_eq!(100, json["seats"][1]["stack"]);
+        assert_eq!(200, json["sidePots"][0]["amount"]);
+        assert_eq!(200, json["winnings"][0]["2"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum LedgerEntryType {
+    BuyIn,      // chips brought to the table when sitting down
+    Rebuy,      // chips added after busting
+    TopUp,      // chips added while still having a stack
+    AutoRebuy,  // rebuy to the max buy-in made on the player's behalf
+    CashOut,    // chips taken off the table when standing up
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct LedgerEntry {
+    pub position: usize,
+    pub name: String,
+    pub entry_type: LedgerEntryType,
+    pub amount: u64,
+    pub hand_number: u64,   // number of hands dealt before the chips moved
+}
+
+// Record of every chip that entered or left the table
+#[derive(Debug)]
+pub struct Ledger {
+    entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    pub fn new() -> Ledger {
+        Ledger {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, position: usize, name: String, entry_type: LedgerEntryType, amount: u64, hand_number: u64) {
+        self.entries.push(LedgerEntry {
+            position: position,
+            name: name,
+            entry_type: entry_type,
+            amount: amount,
+            hand_number: hand_number,
+        });
+    }
+
+    pub fn get_entries(& self) -> &Vec<LedgerEntry> {
+        &self.entries
+    }
+
+    // Chips brought in minus chips cashed out for every player name. Positive means the player is down
+    pub fn get_balances(& self) -> BTreeMap<String, i64> {
+        let mut balances = BTreeMap::new();
+        for entry in &self.entries {
+            let balance = balances.entry(entry.name.clone()).or_insert(0);
+            match entry.entry_type {
+                LedgerEntryType::CashOut => *balance -= entry.amount as i64,
+                _ => *balance += entry.amount as i64,
+            }
+        }
+        balances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balances_per_player() {
+        let mut ledger = Ledger::new();
+        ledger.record(0, "Alice".to_string(), LedgerEntryType::BuyIn, 200, 0);
+        ledger.record(1, "Bob".to_string(), LedgerEntryType::BuyIn, 100, 0);
+        ledger.record(1, "Bob".to_string(), LedgerEntryType::Rebuy, 100, 4);
+        ledger.record(0, "Alice".to_string(), LedgerEntryType::CashOut, 350, 9);
+
+        assert_eq!(4, ledger.get_entries().len());
+        assert_eq!(BTreeMap::from([("Alice".to_string(), -150), ("Bob".to_string(), 200)]), ledger.get_balances());
+    }
+}
@@ -10,6 +10,36 @@ pub use self::table::*;
 pub mod pots;
 pub use self::pots::*;
 
+pub mod rotation;
+pub use self::rotation::*;
+
+pub mod clock;
+pub use self::clock::*;
+
+pub mod straddle;
+pub use self::straddle::*;
+
+pub mod bomb_pot;
+pub use self::bomb_pot::*;
+
+pub mod table_config;
+pub use self::table_config::*;
+
+pub mod ledger;
+pub use self::ledger::*;
+
+pub mod action_log;
+pub use self::action_log::*;
+
+pub mod audit;
+pub use self::audit::*;
+
+pub mod hand_history;
+pub use self::hand_history::*;
+
+pub mod pokerstars_export;
+pub use self::pokerstars_export::*;
+
 mod action;
 pub use self::action::*;
 
@@ -1,19 +1,31 @@
-use std::fmt::Error;
+use std::time::Duration;
 
 use playing_cards::core::Card;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Player {
     pub table_position: usize,
     pub name: String,
     pub stack: u64,
     pub bet: u64,
     pub is_in_hand: bool,
+    pub time_bank: Duration,    // extra time the player can use once their action clock runs out
     hand: Vec<Card>,
+    up_cards: Vec<Card>,        // cards of the hand dealt face up for everyone to see, in the order they were dealt
 
     // player config stuff
     pub is_away: bool,
     pub play_in_bomb_pots: bool,
+    pub will_straddle: bool,
+    pub auto_muck: bool,        // muck losing hands at showdown instead of showing them
+    pub auto_rebuy: bool,       // rebuy for the max buy-in after busting
+
+    pub pending_chips: u64,     // rebuy or top-up that gets added before the next hand
+
+    // blinds owed before the player can be dealt back in
+    pub missed_sb: bool,    // posted dead
+    pub missed_bb: bool,    // posted live
+    pub wait_for_bb: bool,  // sit out until the big blind comes around instead of posting what is owed
 }
 
 impl Player {
@@ -24,19 +36,98 @@ impl Player {
             stack: starting_stack,
             bet: 0,
             is_in_hand: true,
+            time_bank: Duration::ZERO,
             hand: Vec::new(),
+            up_cards: Vec::new(),
 
             is_away: false,
             play_in_bomb_pots: true,
+            will_straddle: false,
+            auto_muck: true,
+            auto_rebuy: false,
+
+            pending_chips: 0,
+
+            missed_sb: false,
+            missed_bb: false,
+            wait_for_bb: false,
         }
     }
 
     pub fn set_new_hand(&mut self, hand: Vec<Card>) {
         self.hand = hand;
+        self.up_cards.clear();
+    }
+
+    // Adds cards to the hand, like the cards dealt on every street of stud
+    pub fn deal_cards(&mut self, cards: Vec<Card>, is_face_up: bool) {
+        if is_face_up {
+            self.up_cards.extend(cards.iter().cloned());
+        }
+        self.hand.extend(cards);
+    }
+
+    pub fn owes_blinds(& self) -> bool {
+        self.missed_sb || self.missed_bb
+    }
+
+    pub fn get_hand(& self) -> &Vec<Card> {
+        &self.hand
+    }
+
+    pub fn get_up_cards(& self) -> &Vec<Card> {
+        &self.up_cards
     }
 
-    pub fn draw_cards(&self, draw: Vec<(Card, Card)>) -> Result<(), Error> { // All-or-none approach, (Card to discard, card to replace with)
-        // find all
+    // Every discard has to be a different card from the player's hand
+    pub fn can_discard(& self, discards: &Vec<Card>) -> bool {
+        discards.iter().all(|card| self.hand.contains(card)) &&
+            discards.iter().enumerate().all(|(idx, card)| !discards[..idx].contains(card))
+    }
+
+    pub fn draw_cards(&mut self, draw: Vec<(Card, Card)>) -> Result<(), &str> { // All-or-none approach, (Card to discard, card to replace with)
+        let discards = draw.iter().map(|(discard, _)| *discard).collect();
+        if !self.can_discard(&discards) {
+            return Err("Discarded cards are not in the player's hand");
+        }
+
+        for (discard, card) in draw {
+            if let Some(hand_card) = self.hand.iter_mut().find(|hand_card| **hand_card == discard) {
+                *hand_card = card;
+            }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_replaces_discards_in_place() {
+        let mut player = Player::new(0, format!("Player 0"), 100);
+        player.set_new_hand(Card::vec_from_str("AsKdQh7c2s").unwrap());
+
+        let draw = Card::vec_from_str("7c2s").unwrap().into_iter()
+            .zip(Card::vec_from_str("AhAd").unwrap())
+            .collect();
+        assert_eq!(Ok(()), player.draw_cards(draw));
+        assert_eq!(&Card::vec_from_str("AsKdQhAhAd").unwrap(), player.get_hand());
+    }
+
+    #[test]
+    fn draw_is_all_or_none() {
+        let mut player = Player::new(0, format!("Player 0"), 100);
+        player.set_new_hand(Card::vec_from_str("AsKdQh7c2s").unwrap());
+
+        // 3h is not in the hand
+        let draw = Card::vec_from_str("7c3h").unwrap().into_iter()
+            .zip(Card::vec_from_str("AhAd").unwrap())
+            .collect();
+        assert!(player.draw_cards(draw).is_err());
+        // the same card can not be discarded twice
+        assert!(!player.can_discard(&Card::vec_from_str("7c7c").unwrap()));
+        assert_eq!(&Card::vec_from_str("AsKdQh7c2s").unwrap(), player.get_hand());
+    }
+}
@@ -0,0 +1,1075 @@
+use std::collections::{BTreeMap, HashMap};
+
+use playing_cards::core::Card;
+
+use crate::poker::{BettingStructure, DrawHistory, GameType, HandHistory, LoggedAction};
+use crate::poker::games::Street;
+
+const BOARD_SIZES: [usize; 4] = [0, 3, 4, 5];              // cards on the board once each street is dealt
+const BOARD_STREET_NAMES: [&str; 4] = ["", "FLOP", "TURN", "RIVER"];
+const RUN_NAMES: [&str; 3] = ["FIRST", "SECOND", "THIRD"];   // also used for draws
+const STUD_STREET_NAMES: [&str; 5] = ["3rd STREET", "4th STREET", "5th STREET", "6th STREET", "RIVER"];
+const STUD_START_CARDS: usize = 3;                          // cards dealt on 3rd street, every later street deals one
+
+fn get_game_name(game_type: GameType) -> &'static str {
+    match game_type {
+        GameType::FiveCardDraw => "5 Card Draw",
+        GameType::SingleDrawDeuceToSeven => "Single Draw 2-7 Lowball",
+        GameType::TripleDrawDeuceToSeven => "Triple Draw 2-7 Lowball",
+        GameType::Badugi => "Badugi",
+        GameType::Badeucy => "Badeucy",
+        GameType::Badacey => "Badacey",
+        GameType::TexasHoldem => "Hold'em",
+        GameType::Omaha => "Omaha",
+        GameType::FiveCardOmaha => "5 Card Omaha",
+        GameType::SixCardOmaha => "6 Card Omaha",
+        GameType::TexasHoldemHiLo => "Hold'em Hi/Lo",
+        GameType::OmahaHiLo => "Omaha Hi/Lo",
+        GameType::FiveCardOmahaHiLo => "5 Card Omaha Hi/Lo",
+        GameType::SevenCardStud => "7 Card Stud",
+        GameType::SevenCardStudHiLo => "7 Card Stud Hi/Lo",
+        GameType::Razz => "Razz",
+        GameType::ShortDeckHoldem => "6+ Hold'em",
+        GameType::DoubleBoardHoldem => "Double Board Hold'em",
+        GameType::DoubleBoardOmaha => "Double Board Omaha",
+        GameType::Custom => "Custom",
+    }
+}
+
+fn get_betting_structure_name(betting_structure: BettingStructure) -> &'static str {
+    match betting_structure {
+        BettingStructure::NoLimit => "No Limit",
+        BettingStructure::PotLimit => "Pot Limit",
+        BettingStructure::FixedLimit => "Limit",
+    }
+}
+
+// Seconds since the unix epoch as "YYYY/MM/DD HH:MM:SS"
+fn format_timestamp(secs: u64) -> String {
+    let time = secs % 86400;
+
+    // days since the epoch to a date in the gregorian calendar, counting eras of 400 years starting on March 1st
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+    format!("{}/{:02}/{:02} {:02}:{:02}:{:02}", year, month, day, time / 3600, time % 3600 / 60, time % 60)
+}
+
+fn format_cards(cards: &Vec<Card>) -> String {
+    cards.iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn format_card_count(count: usize) -> String {
+    if count == 1 {
+        format!("1 card")
+    } else {
+        format!("{} cards", count)
+    }
+}
+
+fn format_ordinal(n: usize) -> String {
+    match n {
+        1 => format!("1st"),
+        2 => format!("2nd"),
+        3 => format!("3rd"),
+        _ => format!("{}th", n),
+    }
+}
+
+// Replays the hand from the action log, keeping track of the chips needed to write out every bet
+struct PokerStarsWriter<'a> {
+    history: &'a HandHistory,
+    streets: Vec<Street>,
+    is_stud: bool,
+    hero: Option<usize>,
+    lines: Vec<String>,
+
+    stacks: BTreeMap<usize, u64>,       // chips behind, not counting the current bet
+    bets: BTreeMap<usize, u64>,         // bets of the current street
+    folded: HashMap<usize, usize>,      // street each player folded on
+    returned: HashMap<usize, u64>,      // uncalled bets given back to each player
+    board_shown: usize,                 // board cards written out so far
+    stud_streets_shown: usize,          // stud streets written out so far
+    bring_in: Option<u64>,              // bring-in that can still be completed
+}
+
+impl<'a> PokerStarsWriter<'a> {
+    fn new(history: &'a HandHistory, hero: Option<usize>) -> PokerStarsWriter<'a> {
+        let game = history.config.create_game();
+        PokerStarsWriter {
+            history: history,
+            streets: game.get_streets(),
+            is_stud: !game.has_blinds(),
+            hero: hero,
+            lines: Vec::new(),
+            stacks: history.seats.iter().map(|seat| (seat.position, seat.stack)).collect(),
+            bets: BTreeMap::new(),
+            folded: HashMap::new(),
+            returned: HashMap::new(),
+            board_shown: 0,
+            stud_streets_shown: 0,
+            bring_in: None,
+        }
+    }
+
+    fn get_name(& self, pos: &usize) -> &'a str {
+        self.history.seats.iter()
+            .find(|seat| seat.position == *pos)
+            .map_or("", |seat| seat.name.as_str())
+    }
+
+    fn is_draw_game(& self) -> bool {
+        self.streets.contains(&Street::Draw)
+    }
+
+    fn get_largest_bet(& self) -> u64 {
+        self.bets.values().max().cloned().unwrap_or(0)
+    }
+
+    // Moves chips from behind the player into their bet, returning how much was added
+    fn put_in(&mut self, pos: &usize, bet: u64) -> u64 {
+        let current = self.bets.get(pos).cloned().unwrap_or(0);
+        let stack = self.stacks.entry(*pos).or_insert(0);
+        let added = std::cmp::min(bet.saturating_sub(current), *stack);
+        *stack -= added;
+        self.bets.insert(*pos, current + added);
+        added
+    }
+
+    fn get_all_in_suffix(& self, pos: &usize) -> &'static str {
+        if self.stacks.get(pos) == Some(&0) {
+            " and is all-in"
+        } else {
+            ""
+        }
+    }
+
+    fn get_winnings(& self, pos: &usize) -> u64 {
+        let won: u64 = self.history.winnings.iter()
+            .map(|winnings| winnings.get(pos).cloned().unwrap_or(0))
+            .sum();
+        won.saturating_sub(self.returned.get(pos).cloned().unwrap_or(0))
+    }
+
+    fn write_header(&mut self, table_name: &str) {
+        let config = &self.history.config;
+        let btn_idx = self.history.blinds.as_ref().map_or(0, |blinds| blinds.btn_idx);
+
+        // limit games go by the small and big bet instead of the blinds
+        let betting_structure = config.get_betting_structure();
+        let game_name = match &config.custom_game {
+            Some(spec) if config.game_type == GameType::Custom => spec.name.as_str(),
+            _ => get_game_name(config.game_type),
+        };
+        let stakes = match betting_structure {
+            BettingStructure::FixedLimit => (config.bb_amt, config.bb_amt * 2),
+            _ => (config.sb_amt, config.bb_amt),
+        };
+        self.lines.push(format!("PokerStars Hand #{}: {} {} ({}/{}) - {} UTC",
+            self.history.hand_number,
+            game_name,
+            get_betting_structure_name(betting_structure),
+            stakes.0,
+            stakes.1,
+            format_timestamp(self.history.started_at)));
+        if self.is_stud {
+            self.lines.push(format!("Table '{}' {}-max", table_name, config.seat_count));
+        } else {
+            self.lines.push(format!("Table '{}' {}-max Seat #{} is the button", table_name, config.seat_count, btn_idx + 1));
+        }
+        for seat in &self.history.seats {
+            self.lines.push(format!("Seat {}: {} ({} in chips)", seat.position + 1, seat.name, seat.stack));
+        }
+    }
+
+    fn write_ante(&mut self, pos: &usize, ante: u64) {
+        self.stacks.entry(*pos).and_modify(|stack| *stack -= ante);
+        self.lines.push(format!("{}: posts the ante {}", self.get_name(pos), ante));
+    }
+
+    fn write_blind(&mut self, pos: &usize, live: u64, dead: u64) {
+        self.stacks.entry(*pos).and_modify(|stack| *stack -= dead);
+        self.put_in(pos, live);
+
+        let is_sb = self.history.blinds.as_ref().map_or(false, |blinds| blinds.sb_idx == *pos && !blinds.is_sb_dead);
+        let is_bb = self.history.blinds.as_ref().map_or(false, |blinds| blinds.bb_idx == *pos);
+        let post = if live > 0 && dead > 0 {
+            format!("posts small & big blinds {}", live + dead)
+        } else if dead > 0 {
+            format!("posts small blind {}", dead)
+        } else if is_sb && !is_bb {
+            format!("posts small blind {}", live)
+        } else if self.history.straddles.contains(pos) {
+            format!("posts straddle {}", live)
+        } else {
+            format!("posts big blind {}", live)
+        };
+        self.lines.push(format!("{}: {}{}", self.get_name(pos), post, self.get_all_in_suffix(pos)));
+    }
+
+    // Everyone antes first, then the blinds and straddles are posted in order followed by any missed blinds
+    fn write_posts(&mut self) {
+        let history = self.history;
+        let mut blinds = Vec::new();
+        for action in &history.actions {
+            if let LoggedAction::Posted { position, ante, live, dead } = action {
+                if *ante > 0 {
+                    self.write_ante(position, *ante);
+                }
+                if *live > 0 || *dead > 0 {
+                    blinds.push((*position, *live, *dead));
+                }
+            }
+        }
+
+        let get_post_order = |pos: &usize| match &history.blinds {
+            Some(blinds) if blinds.sb_idx == *pos && !blinds.is_sb_dead => 0,
+            Some(blinds) if blinds.bb_idx == *pos => 1,
+            _ => history.straddles.iter().position(|straddle| straddle == pos).map_or(history.straddles.len() + 2, |i| i + 2),
+        };
+        blinds.sort_by_key(|(pos, _, _)| get_post_order(pos));
+        for (pos, live, dead) in blinds {
+            self.write_blind(&pos, live, dead);
+        }
+    }
+
+    fn write_deal(&mut self) {
+        if self.is_stud {
+            self.write_stud_streets(0);
+            return;
+        }
+
+        if self.is_draw_game() {
+            self.lines.push(format!("*** DEALING HANDS ***"));
+        } else {
+            self.lines.push(format!("*** HOLE CARDS ***"));
+        }
+        if let Some(hero) = self.hero {
+            if let Some(cards) = self.history.hole_cards.get(&hero) {
+                self.lines.push(format!("Dealt to {} [{}]", self.get_name(&hero), format_cards(cards)));
+            }
+        }
+    }
+
+    // Writes every stud street up to street_idx that has not been written yet. Hero sees all of their cards, while only
+    // the up cards of everyone else are shown. Streets nobody was dealt on are left out
+    fn write_stud_streets(&mut self, street_idx: usize) {
+        let history = self.history;
+        while self.stud_streets_shown <= street_idx && self.stud_streets_shown < STUD_STREET_NAMES.len() {
+            let stud_street = self.stud_streets_shown;
+            self.stud_streets_shown += 1;
+
+            // the cards dealt before the street and the ones the street added
+            let (prev_count, count) = if stud_street == 0 { (0, STUD_START_CARDS) } else { (STUD_START_CARDS + stud_street - 1, STUD_START_CARDS + stud_street) };
+            let mut lines = Vec::new();
+            for seat in &history.seats {
+                let pos = seat.position;
+                let hand = match history.hole_cards.get(&pos) {
+                    Some(hand) if hand.len() >= count && !self.folded.contains_key(&pos) => hand,
+                    _ => continue,
+                };
+
+                let is_hero = self.hero == Some(pos);
+                let up_cards = history.up_cards.get(&pos).cloned().unwrap_or_default();
+                let get_visible = |cards: &[Card]| -> Vec<Card> {
+                    cards.iter().filter(|card| is_hero || up_cards.contains(card)).cloned().collect()
+                };
+                let (before, dealt) = (get_visible(&hand[..prev_count]), get_visible(&hand[prev_count..count]));
+                if dealt.is_empty() {
+                    continue;
+                }
+                if before.is_empty() {
+                    lines.push(format!("Dealt to {} [{}]", seat.name, format_cards(&dealt)));
+                } else {
+                    lines.push(format!("Dealt to {} [{}] [{}]", seat.name, format_cards(&before), format_cards(&dealt)));
+                }
+            }
+
+            let is_community_card = stud_street == STUD_STREET_NAMES.len() - 1 && !history.board.is_empty();
+            if lines.is_empty() && !is_community_card {
+                continue;
+            }
+            if is_community_card {
+                self.lines.push(format!("*** {} *** [{}]", STUD_STREET_NAMES[stud_street], format_cards(&history.board)));
+                self.board_shown = history.board.len();
+            } else {
+                self.lines.push(format!("*** {} ***", STUD_STREET_NAMES[stud_street]));
+            }
+            self.lines.extend(lines);
+        }
+    }
+
+    fn write_bring_in(&mut self, pos: &usize, amount: u64) {
+        self.put_in(pos, amount);
+        self.bring_in = Some(amount);
+        self.lines.push(format!("{}: brings in for {}{}", self.get_name(pos), amount, self.get_all_in_suffix(pos)));
+    }
+
+    fn write_check_call(&mut self, pos: &usize) {
+        let name = self.get_name(pos);
+        let largest_bet = self.get_largest_bet();
+        let added = self.put_in(pos, largest_bet);
+        if added == 0 {
+            self.lines.push(format!("{}: checks", name));
+        } else {
+            self.lines.push(format!("{}: calls {}{}", name, added, self.get_all_in_suffix(pos)));
+        }
+    }
+
+    fn write_bet_raise(&mut self, pos: &usize, amount: u64) {
+        let name = self.get_name(pos);
+        let largest_bet = self.get_largest_bet();
+        let added = self.put_in(pos, amount);
+        let line = if amount <= largest_bet {
+            // shoving for less than the bet is just a call
+            format!("{}: calls {}", name, added)
+        } else if largest_bet == 0 {
+            format!("{}: bets {}", name, amount)
+        } else if self.bring_in == Some(largest_bet) {
+            self.bring_in = None;
+            format!("{}: completes it to {}", name, amount)
+        } else {
+            format!("{}: raises {} to {}", name, amount - largest_bet, amount)
+        };
+        self.lines.push(format!("{}{}", line, self.get_all_in_suffix(pos)));
+    }
+
+    fn write_draw(&mut self, pos: &usize, discard_count: usize, draw: Option<&DrawHistory>, hero_hand: &mut Vec<Card>) {
+        let name = self.get_name(pos);
+        if discard_count == 0 {
+            self.lines.push(format!("{}: stands pat", name));
+            return;
+        }
+
+        match draw {
+            Some(draw) if self.hero == Some(*pos) => {
+                hero_hand.retain(|card| !draw.discarded.contains(card));
+                self.lines.push(format!("{}: discards {} [{}]", name, format_card_count(discard_count), format_cards(&draw.discarded)));
+                self.lines.push(format!("Dealt to {} [{}] [{}]", name, format_cards(hero_hand), format_cards(&draw.drawn)));
+                hero_hand.extend(draw.drawn.iter().cloned());
+            },
+            _ => {
+                self.lines.push(format!("{}: discards {}", name, format_card_count(discard_count)));
+            },
+        }
+    }
+
+    // The part of the largest bet nobody could match goes back to the player that bet it
+    fn return_uncalled_bet(&mut self) {
+        let mut bets: Vec<(usize, u64)> = self.bets.iter().map(|(pos, bet)| (*pos, *bet)).collect();
+        bets.sort_by(|a, b| b.1.cmp(&a.1));
+        self.bets.clear();
+        self.bring_in = None;
+
+        if let Some((pos, largest_bet)) = bets.first().cloned() {
+            let second_bet = bets.get(1).map_or(0, |(_, bet)| *bet);
+            if largest_bet > second_bet {
+                let uncalled = largest_bet - second_bet;
+                *self.stacks.entry(pos).or_insert(0) += uncalled;
+                *self.returned.entry(pos).or_insert(0) += uncalled;
+                self.lines.push(format!("Uncalled bet ({}) returned to {}", uncalled, self.get_name(&pos)));
+            }
+        }
+    }
+
+    // Writes a header for every street of the board dealt after the first from cards. Each header shows the board
+    // before the street followed by the cards the street added
+    fn write_board_streets(&mut self, board: &Vec<Card>, from: usize, prefix: &str) {
+        for street_idx in 1..BOARD_SIZES.len() {
+            let (prev_size, size) = (BOARD_SIZES[street_idx - 1], BOARD_SIZES[street_idx]);
+            if size <= from || size > board.len() {
+                continue;
+            }
+
+            let new_cards = format_cards(&board[prev_size..size].to_vec());
+            if prev_size == 0 {
+                self.lines.push(format!("*** {}{} *** [{}]", prefix, BOARD_STREET_NAMES[street_idx], new_cards));
+            } else {
+                self.lines.push(format!("*** {}{} *** [{}] [{}]", prefix, BOARD_STREET_NAMES[street_idx], format_cards(&board[..prev_size].to_vec()), new_cards));
+            }
+        }
+    }
+
+    // Writes out whatever the rest of the hand was dealt on the board, once on its own and then for every runout
+    fn write_rest_of_board(&mut self) {
+        if self.is_stud {
+            self.write_stud_streets(STUD_STREET_NAMES.len() - 1);
+            return;
+        }
+
+        let history = self.history;
+        let board = &history.board;
+        for (prefix, board) in self.get_boards() {
+            self.write_board_streets(&board, self.board_shown, &prefix);
+        }
+        self.board_shown = board.len();
+
+        for (run_name, runout) in RUN_NAMES.iter().zip(history.runouts.iter()) {
+            let runout_board = board.iter().chain(runout.iter()).cloned().collect();
+            self.write_board_streets(&runout_board, board.len(), &format!("{} ", run_name));
+        }
+    }
+
+    fn write_street_start(&mut self, street_idx: usize) {
+        if self.is_stud {
+            self.write_stud_streets(street_idx);
+        } else if self.streets.get(street_idx) == Some(&Street::Draw) {
+            let draw_count = self.streets[..=street_idx].iter().filter(|street| **street == Street::Draw).count();
+            self.lines.push(format!("*** {} DRAW ***", RUN_NAMES[std::cmp::min(draw_count, RUN_NAMES.len()) - 1]));
+        } else if let Some(board_size) = BOARD_SIZES.get(street_idx) {
+            let board_size = std::cmp::min(*board_size, self.history.board.len());
+            for (prefix, board) in self.get_boards() {
+                self.write_board_streets(&board[..board_size].to_vec(), self.board_shown, &prefix);
+            }
+            self.board_shown = std::cmp::max(self.board_shown, board_size);
+        }
+    }
+
+    // Every board of the hand along with what its streets are prefixed with. Double board games write each street
+    // for the first board and then the second, the same way a hand run twice does
+    fn get_boards(& self) -> Vec<(String, Vec<Card>)> {
+        if self.history.second_board.is_empty() {
+            return vec![(String::new(), self.history.board.clone())];
+        }
+        vec![
+            (format!("{} ", RUN_NAMES[0]), self.history.board.clone()),
+            (format!("{} ", RUN_NAMES[1]), self.history.second_board.clone()),
+        ]
+    }
+
+    fn get_remaining_players(& self) -> Vec<usize> {
+        self.history.seats.iter()
+            .map(|seat| seat.position)
+            .filter(|pos| !self.folded.contains_key(pos))
+            .collect()
+    }
+
+    fn write_showdown(&mut self) {
+        let remaining = self.get_remaining_players();
+        if remaining.len() > 1 {
+            self.lines.push(format!("*** SHOW DOWN ***"));
+            for pos in &remaining {
+                let line = match self.history.shown_hands.get(pos) {
+                    Some(cards) => format!("{}: shows [{}]", self.get_name(pos), format_cards(cards)),
+                    None => format!("{}: mucks hand", self.get_name(pos)),
+                };
+                self.lines.push(line);
+            }
+        }
+
+        for pos in &remaining {
+            let won = self.get_winnings(pos);
+            if won > 0 {
+                self.lines.push(format!("{} collected {} from pot", self.get_name(pos), won));
+            }
+        }
+
+        if remaining.len() == 1 && !self.history.shown_hands.contains_key(&remaining[0]) {
+            self.lines.push(format!("{}: doesn't show hand", self.get_name(&remaining[0])));
+        }
+    }
+
+    fn get_fold_description(& self, street_idx: usize) -> String {
+        if self.is_stud {
+            return match street_idx {
+                0 => format!("folded on the 3rd Street"),
+                1 => format!("folded on the 4th Street"),
+                2 => format!("folded on the 5th Street"),
+                3 => format!("folded on the 6th Street"),
+                _ => format!("folded on the River"),
+            };
+        }
+
+        if !self.is_draw_game() {
+            return match street_idx {
+                0 => format!("folded before Flop"),
+                1 => format!("folded on the Flop"),
+                2 => format!("folded on the Turn"),
+                _ => format!("folded on the River"),
+            };
+        }
+
+        let draws_before = self.streets[..street_idx].iter().filter(|street| **street == Street::Draw).count();
+        let total_draws = self.streets.iter().filter(|street| **street == Street::Draw).count();
+        if draws_before == 0 {
+            format!("folded before the Draw")
+        } else if total_draws == 1 {
+            format!("folded after the Draw")
+        } else {
+            format!("folded after the {} Draw", format_ordinal(draws_before))
+        }
+    }
+
+    fn write_summary(&mut self) {
+        self.lines.push(format!("*** SUMMARY ***"));
+
+        let returned: u64 = self.returned.values().sum();
+        // the side pots are what is left over once the rake is taken out
+        let total_pot = self.history.side_pots.iter().map(|side_pot| side_pot.amount).sum::<u64>() - returned + self.history.rake;
+        let contested: Vec<u64> = self.history.side_pots.iter()
+            .filter(|side_pot| side_pot.eligible_players.len() > 1)
+            .map(|side_pot| side_pot.amount)
+            .collect();
+        let mut pot_line = format!("Total pot {}", total_pot);
+        if contested.len() > 1 {
+            pot_line.push_str(&format!(" Main pot {}.", contested[0]));
+            if contested.len() == 2 {
+                pot_line.push_str(&format!(" Side pot {}.", contested[1]));
+            } else {
+                for (i, amount) in contested.iter().enumerate().skip(1) {
+                    pot_line.push_str(&format!(" Side pot-{} {}.", i, amount));
+                }
+            }
+        }
+        pot_line.push_str(&format!(" | Rake {}", self.history.rake));
+        self.lines.push(pot_line);
+
+        if self.history.runouts.len() > 1 {
+            let times = match self.history.runouts.len() {
+                2 => format!("twice"),
+                count => format!("{} times", count),
+            };
+            self.lines.push(format!("Hand was run {}", times));
+            for (run_name, runout) in RUN_NAMES.iter().zip(self.history.runouts.iter()) {
+                let runout_board = self.history.board.iter().chain(runout.iter()).cloned().collect();
+                self.lines.push(format!("{} Board [{}]", run_name, format_cards(&runout_board)));
+            }
+        } else if !self.history.board.is_empty() {
+            for (prefix, board) in self.get_boards() {
+                self.lines.push(format!("{}Board [{}]", prefix, format_cards(&board)));
+            }
+        }
+
+        let is_showdown = self.get_remaining_players().len() > 1;
+        for seat in &self.history.seats {
+            let pos = seat.position;
+            let mut tag = String::new();
+            if let Some(blinds) = self.history.blinds.as_ref().filter(|_| !self.is_stud) {
+                if blinds.btn_idx == pos {
+                    tag.push_str(" (button)");
+                }
+                // nobody posts blinds when the button antes
+                if !self.history.config.is_button_ante {
+                    if blinds.sb_idx == pos && !blinds.is_sb_dead {
+                        tag.push_str(" (small blind)");
+                    } else if blinds.bb_idx == pos {
+                        tag.push_str(" (big blind)");
+                    }
+                }
+            }
+            let won = self.get_winnings(&pos);
+            let result = if let Some(street_idx) = self.folded.get(&pos) {
+                self.get_fold_description(*street_idx)
+            } else if let Some(cards) = self.history.shown_hands.get(&pos) {
+                if won > 0 {
+                    format!("showed [{}] and won ({})", format_cards(cards), won)
+                } else {
+                    format!("showed [{}] and lost", format_cards(cards))
+                }
+            } else if is_showdown {
+                format!("mucked")
+            } else {
+                format!("collected ({})", won)
+            };
+            self.lines.push(format!("Seat {}: {}{} {}", pos + 1, seat.name, tag, result));
+        }
+    }
+
+    fn write(mut self, table_name: &str) -> String {
+        let history = self.history;
+        self.write_header(table_name);
+
+        self.write_posts();
+        self.write_deal();
+
+        // every draw in the log has its cards recorded in the same order
+        let mut draws = history.draws.iter();
+        let mut street_idx = 0;
+        let mut hero_hand = self.hero
+            .and_then(|hero| history.hole_cards.get(&hero))
+            .cloned()
+            .unwrap_or_else(|| Vec::new());
+        for action in &history.actions {
+            match action {
+                LoggedAction::Fold { position } => {
+                    self.folded.insert(*position, street_idx);
+                    self.lines.push(format!("{}: folds", self.get_name(position)));
+                },
+                LoggedAction::CheckCall { position } => self.write_check_call(position),
+                LoggedAction::BringIn { position, amount } => self.write_bring_in(position, *amount),
+                LoggedAction::BetRaise { position, amount } => self.write_bet_raise(position, *amount),
+                LoggedAction::Draw { position, discard_count } => self.write_draw(position, *discard_count, draws.next(), &mut hero_hand),
+                LoggedAction::StreetEnded { street_idx: ended_idx } => {
+                    self.return_uncalled_bet();
+                    street_idx = ended_idx + 1;
+                    self.write_street_start(street_idx);
+                },
+                LoggedAction::HandEnded { .. } => {
+                    self.return_uncalled_bet();
+                    self.write_rest_of_board();
+                    self.write_showdown();
+                },
+                LoggedAction::HandStarted { .. } | LoggedAction::Posted { .. } => (),
+            }
+        }
+
+        self.write_summary();
+        self.lines.push(String::new());
+        self.lines.join("\n")
+    }
+}
+
+// Writes the hand out the way PokerStars does, so it can be imported into hand trackers.
+// Only the hole cards of hero (if given) are written out, everyone else's cards are only seen if they were shown
+pub fn export_pokerstars(history: &HandHistory, table_name: &str, hero: Option<usize>) -> String {
+    PokerStarsWriter::new(history, hero).write(table_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use crate::poker::{BlindPositions, SeatHistory, SidePotHistory, TableConfig, RakeSettings};
+
+    use super::*;
+
+    fn get_history(seats: Vec<(usize, &str, u64)>, actions: Vec<LoggedAction>) -> HandHistory {
+        HandHistory {
+            hand_number: 7,
+            started_at: 1650000000,
+            config: TableConfig::new(),
+            seats: seats.iter()
+                .map(|(pos, name, stack)| SeatHistory { position: *pos, name: name.to_string(), stack: *stack })
+                .collect(),
+            blinds: Some(BlindPositions { btn_idx: 0, sb_idx: 1, bb_idx: 2, is_sb_dead: false }),
+            straddles: Vec::new(),
+            ante_amt: 0,
+            is_bomb_pot: false,
+            hole_cards: BTreeMap::new(),
+            up_cards: BTreeMap::new(),
+            actions: actions,
+            draws: Vec::new(),
+            board: Vec::new(),
+            second_board: Vec::new(),
+            runouts: Vec::new(),
+            side_pots: Vec::new(),
+            rake: 0,
+            winnings: Vec::new(),
+            shown_hands: BTreeMap::new(),
+            final_stacks: BTreeMap::new(),
+        }
+    }
+
+    fn get_draw(position: usize, discarded: &str, drawn: &str) -> DrawHistory {
+        DrawHistory {
+            position: position,
+            discarded: Card::vec_from_str(discarded).unwrap(),
+            drawn: Card::vec_from_str(drawn).unwrap(),
+        }
+    }
+
+    fn get_holdem_history(seats: Vec<(usize, &str, u64)>, actions: Vec<LoggedAction>) -> HandHistory {
+        let mut history = get_history(seats, actions);
+        history.config.game_type = GameType::TexasHoldem;
+        history
+    }
+
+    #[test]
+    fn timestamp_format() {
+        assert_eq!("1970/01/01 00:00:00", format_timestamp(0));
+        assert_eq!("2022/04/15 05:20:00", format_timestamp(1650000000));
+        assert_eq!("2024/02/29 23:59:59", format_timestamp(1709251199));
+    }
+
+    #[test]
+    fn five_card_draw_no_limit_showdown() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 6 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::Draw { position: 2, discard_count: 3 },
+            LoggedAction::Draw { position: 0, discard_count: 1 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::BetRaise { position: 0, amount: 10 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 33)]) },
+        ]);
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd7c2s").unwrap()),
+            (1, Card::vec_from_str("9d8d4c3h2h").unwrap()),
+            (2, Card::vec_from_str("QcQd5h6s8c").unwrap()),
+        ]);
+        history.draws = vec![get_draw(2, "5h6s8c", "3s3d4d"), get_draw(0, "2s", "Kc")];
+        history.side_pots = vec![SidePotHistory { amount: 33, eligible_players: BTreeSet::from([0, 2]) }];
+        history.winnings = vec![HashMap::from([(0, 33)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd7cKc").unwrap()),
+            (2, Card::vec_from_str("QcQd3s3d4d").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/five_card_draw_nl_showdown.txt"), export_pokerstars(&history, "Home Game", Some(0)));
+    }
+
+    #[test]
+    fn five_card_draw_no_limit_side_pot() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 30), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 30), (2, 150)]) },
+            LoggedAction::Posted { position: 0, ante: 1, live: 0, dead: 0 },
+            LoggedAction::Posted { position: 1, ante: 1, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 1, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 10 },
+            LoggedAction::BetRaise { position: 1, amount: 29 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::Draw { position: 1, discard_count: 2 },
+            LoggedAction::Draw { position: 2, discard_count: 1 },
+            LoggedAction::Draw { position: 0, discard_count: 0 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::BetRaise { position: 2, amount: 50 },
+            LoggedAction::BetRaise { position: 0, amount: 170 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 50), (1, 90), (2, 240)]) },
+        ]);
+        history.config.ante_amt = 1;
+        history.ante_amt = 1;
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("9s9h4d4c2s").unwrap()),
+            (1, Card::vec_from_str("AcAdJh5d2c").unwrap()),
+            (2, Card::vec_from_str("Th9cJc8sKd").unwrap()),
+        ]);
+        history.draws = vec![get_draw(1, "5d2c", "AhJs"), get_draw(2, "Kd", "7d")];
+        history.side_pots = vec![
+            SidePotHistory { amount: 90, eligible_players: BTreeSet::from([0, 1, 2]) },
+            SidePotHistory { amount: 240, eligible_players: BTreeSet::from([0, 2]) },
+            SidePotHistory { amount: 50, eligible_players: BTreeSet::from([0]) },
+        ];
+        history.winnings = vec![HashMap::from([(0, 50), (1, 90), (2, 240)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("9s9h4d4c2s").unwrap()),
+            (1, Card::vec_from_str("AcAdJhAhJs").unwrap()),
+            (2, Card::vec_from_str("Th9cJc8s7d").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/five_card_draw_nl_side_pot.txt"), export_pokerstars(&history, "Home Game", Some(1)));
+    }
+
+    #[test]
+    fn five_card_draw_no_limit_fold_win() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::Fold { position: 0 },
+            LoggedAction::BetRaise { position: 1, amount: 6 },
+            LoggedAction::Fold { position: 2 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(1, 8)]) },
+        ]);
+        history.side_pots = vec![SidePotHistory { amount: 8, eligible_players: BTreeSet::from([1]) }];
+        history.winnings = vec![HashMap::from([(1, 8)])];
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/five_card_draw_nl_fold_win.txt"), export_pokerstars(&history, "Home Game", None));
+    }
+
+    #[test]
+    fn holdem_no_limit_showdown() {
+        let mut history = get_holdem_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::BetRaise { position: 2, amount: 4 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::BetRaise { position: 2, amount: 10 },
+            LoggedAction::BetRaise { position: 0, amount: 30 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 73)]) },
+        ]);
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("7s7h").unwrap()),
+            (1, Card::vec_from_str("Jd4d").unwrap()),
+            (2, Card::vec_from_str("KcQc").unwrap()),
+        ]);
+        history.board = Card::vec_from_str("Ks7d2c9hAs").unwrap();
+        history.side_pots = vec![SidePotHistory { amount: 73, eligible_players: BTreeSet::from([0, 2]) }];
+        history.winnings = vec![HashMap::from([(0, 73)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("7s7h").unwrap()),
+            (2, Card::vec_from_str("KcQc").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/holdem_nl_showdown.txt"), export_pokerstars(&history, "Home Game", Some(2)));
+    }
+
+    #[test]
+    fn holdem_no_limit_showdown_raked() {
+        let mut history = get_holdem_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::BetRaise { position: 2, amount: 4 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::BetRaise { position: 2, amount: 10 },
+            LoggedAction::BetRaise { position: 0, amount: 30 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 70)]) },
+        ]);
+        history.config.rake_settings = RakeSettings { percent: 5.0, cap: Some(5) };
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("7s7h").unwrap()),
+            (1, Card::vec_from_str("Jd4d").unwrap()),
+            (2, Card::vec_from_str("KcQc").unwrap()),
+        ]);
+        history.board = Card::vec_from_str("Ks7d2c9hAs").unwrap();
+        history.side_pots = vec![SidePotHistory { amount: 70, eligible_players: BTreeSet::from([0, 2]) }];
+        history.rake = 3;
+        history.winnings = vec![HashMap::from([(0, 70)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("7s7h").unwrap()),
+            (2, Card::vec_from_str("KcQc").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/holdem_nl_showdown_raked.txt"), export_pokerstars(&history, "Home Game", Some(2)));
+    }
+
+    #[test]
+    fn holdem_no_limit_all_in_run_twice() {
+        let mut history = get_holdem_history(vec![(0, "Alice", 100), (1, "Bob", 100)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 100), (1, 100)]) },
+            LoggedAction::Posted { position: 0, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 1, ante: 0, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 100 },
+            LoggedAction::CheckCall { position: 1 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 100), (1, 100)]) },
+        ]);
+        history.blinds = Some(BlindPositions { btn_idx: 0, sb_idx: 0, bb_idx: 1, is_sb_dead: false });
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("AhAd").unwrap()),
+            (1, Card::vec_from_str("KsKc").unwrap()),
+        ]);
+        history.runouts = vec![Card::vec_from_str("2c7d9hTs3c").unwrap(), Card::vec_from_str("Kd8s4h5c6d").unwrap()];
+        history.side_pots = vec![SidePotHistory { amount: 200, eligible_players: BTreeSet::from([0, 1]) }];
+        history.winnings = vec![HashMap::from([(0, 100)]), HashMap::from([(1, 100)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("AhAd").unwrap()),
+            (1, Card::vec_from_str("KsKc").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/holdem_nl_run_twice.txt"), export_pokerstars(&history, "Home Game", Some(0)));
+    }
+
+    #[test]
+    fn omaha_pot_limit_showdown() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 7 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::BetRaise { position: 2, amount: 15 },
+            LoggedAction::BetRaise { position: 0, amount: 60 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 135)]) },
+        ]);
+        history.config.game_type = GameType::Omaha;
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd2c").unwrap()),
+            (1, Card::vec_from_str("Jd4d8h3s").unwrap()),
+            (2, Card::vec_from_str("9h9dTcQc").unwrap()),
+        ]);
+        history.board = Card::vec_from_str("Ad7c9s5hJs").unwrap();
+        history.side_pots = vec![SidePotHistory { amount: 135, eligible_players: BTreeSet::from([0, 2]) }];
+        history.winnings = vec![HashMap::from([(0, 135)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd2c").unwrap()),
+            (2, Card::vec_from_str("9h9dTcQc").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/omaha_pl_showdown.txt"), export_pokerstars(&history, "Home Game", Some(0)));
+    }
+
+    #[test]
+    fn double_board_omaha_split() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 7 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::BetRaise { position: 2, amount: 15 },
+            LoggedAction::BetRaise { position: 0, amount: 60 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 68), (2, 67)]) },
+        ]);
+        history.config.game_type = GameType::DoubleBoardOmaha;
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd2c").unwrap()),
+            (1, Card::vec_from_str("Jd4d8h3s").unwrap()),
+            (2, Card::vec_from_str("9h9dTcQc").unwrap()),
+        ]);
+        history.board = Card::vec_from_str("Ad7c9s5hJs").unwrap();
+        history.second_board = Card::vec_from_str("9c6d2h4sKs").unwrap();
+        history.side_pots = vec![SidePotHistory { amount: 135, eligible_players: BTreeSet::from([0, 2]) }];
+        // the first board gets the odd chip
+        history.winnings = vec![HashMap::from([(0, 68), (2, 67)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("AsAhKd2c").unwrap()),
+            (2, Card::vec_from_str("9h9dTcQc").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/double_board_omaha_split.txt"), export_pokerstars(&history, "Home Game", Some(0)));
+    }
+
+    #[test]
+    fn triple_draw_limit_showdown() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 },
+            LoggedAction::BetRaise { position: 0, amount: 4 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::Draw { position: 2, discard_count: 2 },
+            LoggedAction::Draw { position: 0, discard_count: 1 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::BetRaise { position: 0, amount: 2 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::Draw { position: 2, discard_count: 1 },
+            LoggedAction::Draw { position: 0, discard_count: 0 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::BetRaise { position: 2, amount: 4 },
+            LoggedAction::BetRaise { position: 0, amount: 8 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 4 },
+            LoggedAction::Draw { position: 2, discard_count: 0 },
+            LoggedAction::Draw { position: 0, discard_count: 0 },
+            LoggedAction::StreetEnded { street_idx: 5 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 6 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(0, 29)]) },
+        ]);
+        history.config.game_type = GameType::TripleDrawDeuceToSeven;
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("7s5d4c3hKd").unwrap()),
+            (1, Card::vec_from_str("9d8d4d3s2h").unwrap()),
+            (2, Card::vec_from_str("8h6h3dQsJc").unwrap()),
+        ]);
+        history.draws = vec![
+            get_draw(2, "QsJc", "2c4s"),
+            get_draw(0, "Kd", "2s"),
+            get_draw(2, "8h", "7d"),
+            get_draw(0, "", ""),
+            get_draw(2, "", ""),
+            get_draw(0, "", ""),
+        ];
+        history.side_pots = vec![SidePotHistory { amount: 29, eligible_players: BTreeSet::from([0, 2]) }];
+        history.winnings = vec![HashMap::from([(0, 29)])];
+        history.shown_hands = BTreeMap::from([
+            (0, Card::vec_from_str("7s5d4c3h2s").unwrap()),
+            (2, Card::vec_from_str("6h3d2c4s7d").unwrap()),
+        ]);
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/triple_draw_limit_showdown.txt"), export_pokerstars(&history, "Home Game", Some(2)));
+    }
+
+    #[test]
+    fn razz_limit_fold_win() {
+        let mut history = get_history(vec![(0, "Alice", 200), (1, "Bob", 100), (2, "Carol", 150)], vec![
+            LoggedAction::HandStarted { hand_number: 7, btn_idx: 0, stacks: BTreeMap::from([(0, 200), (1, 100), (2, 150)]) },
+            LoggedAction::Posted { position: 0, ante: 1, live: 0, dead: 0 },
+            LoggedAction::Posted { position: 1, ante: 1, live: 0, dead: 0 },
+            LoggedAction::Posted { position: 2, ante: 1, live: 0, dead: 0 },
+            LoggedAction::BringIn { position: 1, amount: 1 },
+            LoggedAction::BetRaise { position: 2, amount: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::Fold { position: 1 },
+            LoggedAction::StreetEnded { street_idx: 0 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::BetRaise { position: 2, amount: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 1 },
+            LoggedAction::BetRaise { position: 0, amount: 4 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::StreetEnded { street_idx: 2 },
+            LoggedAction::CheckCall { position: 2 },
+            LoggedAction::CheckCall { position: 0 },
+            LoggedAction::StreetEnded { street_idx: 3 },
+            LoggedAction::BetRaise { position: 2, amount: 4 },
+            LoggedAction::Fold { position: 0 },
+            LoggedAction::HandEnded { winnings: HashMap::from([(2, 24)]) },
+        ]);
+        history.config.game_type = GameType::Razz;
+        history.ante_amt = 1;
+        history.hole_cards = BTreeMap::from([
+            (0, Card::vec_from_str("9hQs3d4c5d8sKh").unwrap()),
+            (1, Card::vec_from_str("2s6cKc").unwrap()),
+            (2, Card::vec_from_str("2d3sAc7h6d4s5c").unwrap()),
+        ]);
+        history.up_cards = BTreeMap::from([
+            (0, Card::vec_from_str("3d4c5d8s").unwrap()),
+            (1, Card::vec_from_str("Kc").unwrap()),
+            (2, Card::vec_from_str("Ac7h6d4s").unwrap()),
+        ]);
+        history.side_pots = vec![SidePotHistory { amount: 24, eligible_players: BTreeSet::from([2]) }];
+        history.winnings = vec![HashMap::from([(2, 24)])];
+
+        assert_eq!(include_str!("../../tests/golden/pokerstars/razz_limit_fold_win.txt"), export_pokerstars(&history, "Home Game", Some(2)));
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashMap};
+
+use playing_cards::poker::Rank;
+
+use super::{NoLimitPot, Pot, PartialPot, OddChipPriority, RakeSettings};
+use crate::poker::Player;
+
+// Every bet and raise is a fixed amount: the small bet (the big blind) on the early streets and the big bet
+// (twice the big blind) on the later ones. A street is capped once there is a bet and three raises
+#[derive(Debug, PartialEq, Clone)]
+pub struct FixedLimitPot {
+    pot: NoLimitPot,
+    small_bet: u64,
+    is_big_bet: bool,   // whether the current street is played with the big bet
+}
+
+impl FixedLimitPot {
+    const MAX_BETS: u64 = 4;
+
+    pub fn new() -> FixedLimitPot {
+        FixedLimitPot {
+            pot: NoLimitPot::new(),
+            small_bet: 0,
+            is_big_bet: false,
+        }
+    }
+
+    pub fn get_bet_size(& self) -> u64 {
+        if self.is_big_bet {
+            self.small_bet * 2
+        } else {
+            self.small_bet
+        }
+    }
+
+    fn get_largest_bet(& self) -> u64 {
+        self.pot.get_all_player_stacks_bets().values().map(|(_, bet)| *bet).max().unwrap_or(0)
+    }
+}
+
+impl Pot for FixedLimitPot {
+    fn clone_box(& self) -> Box<dyn Pot + Send> {
+        Box::new(self.clone())
+    }
+
+    fn set_big_bet(&mut self, is_big_bet: bool) {
+        self.is_big_bet = is_big_bet;
+    }
+
+    fn get_all_player_stacks_bets(& self) -> &BTreeMap<usize, (u64, u64)> {
+        self.pot.get_all_player_stacks_bets()
+    }
+
+    fn get_player_stack_bet(& self, pos: &usize) -> Result<&(u64, u64), std::string::String> {
+        self.pot.get_player_stack_bet(pos)
+    }
+
+    fn get_largest_bet_idxes(& self) -> Option<(usize, usize)> {
+        self.pot.get_largest_bet_idxes()
+    }
+
+    fn get_pots(& self) -> &Vec<PartialPot> {
+        self.pot.get_pots()
+    }
+
+    fn get_total_pot(& self) -> u64 {
+        self.pot.get_total_pot()
+    }
+
+    fn get_rake(& self) -> u64 {
+        self.pot.get_rake()
+    }
+
+    fn set_rake_settings(&mut self, settings: RakeSettings) {
+        self.pot.set_rake_settings(settings)
+    }
+
+    fn is_bomb_pot(& self) -> bool {
+        self.pot.is_bomb_pot()
+    }
+
+    fn is_pot_contested(& self) -> bool {
+        self.pot.is_pot_contested()
+    }
+
+    fn post_before_deal(&mut self, bb_idx: &usize) -> Result<(), &str> {
+        self.pot.post_before_deal(bb_idx)
+    }
+
+    fn post_blinds_before_deal(&mut self, sb_idx: Option<&usize>, bb_idx: &usize) -> Result<(), &str> {
+        self.pot.post_blinds_before_deal(sb_idx, bb_idx)
+    }
+
+    fn post_missed_blinds(&mut self, pos: &usize, post_bb: bool, post_dead_sb: bool) -> Result<(), &str> {
+        self.pot.post_missed_blinds(pos, post_bb, post_dead_sb)
+    }
+
+    fn post_straddles(&mut self, straddle_idxes: &Vec<usize>) -> Result<(), &str> {
+        self.pot.post_straddles(straddle_idxes)
+    }
+
+    fn post_antes_before_deal(&mut self) -> Result<(), &str> {
+        self.pot.post_antes_before_deal()
+    }
+
+    fn post_button_ante(&mut self, btn_idx: &usize) -> Result<(), &str> {
+        self.pot.post_button_ante(btn_idx)
+    }
+
+    fn post_bring_in(&mut self, pos: &usize) -> Result<u64, &str> {
+        self.pot.post_bring_in(pos)
+    }
+
+    fn bet_or_shove(&mut self, pos: &usize, bet: u64) -> Result<u64, std::string::String> {
+        let bet_size = self.get_bet_size();
+        let largest_bet = self.get_largest_bet();
+        if largest_bet >= bet_size * FixedLimitPot::MAX_BETS {
+            return Err(format!("Betting is capped at {}", largest_bet));
+        }
+
+        // a bring-in (or an all in for less than a bet) is completed up to a full bet. A player without enough
+        // for a full raise can only go all in
+        let raise_to = if largest_bet < bet_size { bet_size } else { largest_bet + bet_size };
+        let stack = self.pot.get_player_stack_bet(pos)?.0;
+        let is_shove = bet >= stack && stack <= raise_to;
+        if bet != raise_to && !is_shove {
+            return Err(format!("Bet of {} is not the fixed limit bet of {}", bet, raise_to));
+        }
+        self.pot.bet_or_shove(pos, bet)
+    }
+
+    fn check_call(&mut self, pos: &usize) -> Result<u64, std::string::String> {
+        self.pot.check_call(pos)
+    }
+
+    fn fold(&mut self, pos: &usize) -> Result<(), std::string::String> {
+        self.pot.fold(pos)
+    }
+
+    fn fold_out_of_turn(&mut self, pos: &usize) -> Result<(), std::string::String> {
+        self.pot.fold_out_of_turn(pos)
+    }
+
+    fn reset_pot(&mut self, players: &HashMap<usize, Player>, sb: u64, bb: u64, ante: u64, is_bomb: bool) -> Result<(), &str> {
+        self.small_bet = bb;
+        self.is_big_bet = false;
+        self.pot.reset_pot(players, sb, bb, ante, is_bomb)
+    }
+
+    fn collect_bets(&mut self) {
+        self.pot.collect_bets()
+    }
+
+    fn distribute_pot(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<HashMap<usize, u64>, &str> {
+        self.pot.distribute_pot(players, hand_rankings, btn_idx, odd_chip)
+    }
+
+    fn distribute_pot_runouts(&mut self, players: &mut HashMap<usize, Player>, runout_rankings: &Vec<Vec<HashMap<usize, Rank>>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<Vec<HashMap<usize, u64>>, &str> {
+        self.pot.distribute_pot_runouts(players, runout_rankings, btn_idx, odd_chip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_pot(stacks: &[u64]) -> FixedLimitPot {
+        let players: HashMap<usize, Player> = stacks.iter()
+            .enumerate()
+            .map(|(pos, stack)| (pos, Player::new(pos, format!("Player {}", pos), *stack)))
+            .collect();
+
+        let mut pot = FixedLimitPot::new();
+        assert_eq!(Ok(()), pot.reset_pot(&players, 1, 2, 0, false));
+        assert_eq!(Ok(()), pot.post_blinds_before_deal(Some(&1), &2));
+        pot
+    }
+
+    #[test]
+    fn raises_are_one_small_bet() {
+        let mut pot = get_pot(&[200, 200, 200]);
+
+        assert!(pot.bet_or_shove(&0, 6).is_err());
+        assert!(pot.bet_or_shove(&0, 3).is_err());
+        assert_eq!(Ok(4), pot.bet_or_shove(&0, 4));
+        assert_eq!(Ok(6), pot.bet_or_shove(&1, 6));
+    }
+
+    #[test]
+    fn capped_after_three_raises() {
+        let mut pot = get_pot(&[200, 200, 200]);
+
+        // the big blind counts as the first bet
+        assert_eq!(Ok(4), pot.bet_or_shove(&0, 4));
+        assert_eq!(Ok(6), pot.bet_or_shove(&1, 6));
+        assert_eq!(Ok(8), pot.bet_or_shove(&2, 8));
+        assert!(pot.bet_or_shove(&0, 10).is_err());
+        assert_eq!(Ok(8), pot.check_call(&0));
+    }
+
+    #[test]
+    fn big_bet_on_later_streets() {
+        let mut pot = get_pot(&[200, 200, 200]);
+        assert!(pot.check_call(&0).is_ok());
+        assert!(pot.check_call(&1).is_ok());
+        assert!(pot.check_call(&2).is_ok());
+        pot.collect_bets();
+
+        pot.set_big_bet(true);
+        assert!(pot.bet_or_shove(&1, 2).is_err());
+        assert_eq!(Ok(4), pot.bet_or_shove(&1, 4));
+        assert_eq!(Ok(8), pot.bet_or_shove(&2, 8));
+    }
+
+    #[test]
+    fn short_stack_can_shove() {
+        let mut pot = get_pot(&[3, 200, 200]);
+        assert_eq!(Ok(3), pot.bet_or_shove(&0, 3));
+        assert!(pot.bet_or_shove(&1, 4).is_err());
+        assert_eq!(Ok(5), pot.bet_or_shove(&1, 5));
+    }
+
+    #[test]
+    fn bring_in_completed_to_small_bet() {
+        let players: HashMap<usize, Player> = (0..3)
+            .map(|pos| (pos, Player::new(pos, format!("Player {}", pos), 200)))
+            .collect();
+        let mut pot = FixedLimitPot::new();
+        assert_eq!(Ok(()), pot.reset_pot(&players, 1, 2, 1, false));
+        assert_eq!(Ok(()), pot.post_antes_before_deal());
+        assert_eq!(Ok(1), pot.post_bring_in(&1));
+        assert_eq!(3, pot.get_total_pot());
+
+        assert_eq!(Ok(1), pot.check_call(&2));
+        assert!(pot.bet_or_shove(&0, 3).is_err());
+        assert_eq!(Ok(2), pot.bet_or_shove(&0, 2));
+        assert_eq!(Ok(4), pot.bet_or_shove(&1, 4));
+    }
+}
@@ -4,5 +4,14 @@ pub use self::pot::*;
 mod odd_chip;
 pub use self::odd_chip::OddChipPriority;
 
+mod rake;
+pub use self::rake::RakeSettings;
+
 mod no_limit_pot;
 pub use self::no_limit_pot::*;
+
+mod pot_limit_pot;
+pub use self::pot_limit_pot::*;
+
+mod fixed_limit_pot;
+pub use self::fixed_limit_pot::*;
@@ -3,10 +3,10 @@ use std::ops::Bound::{Excluded, Included, Unbounded};
 
 use playing_cards::poker::Rank;
 
-use super::{Pot, PartialPot, OddChipPriority};
+use super::{Pot, PartialPot, OddChipPriority, RakeSettings};
 use crate::poker::{Player};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct NoLimitPot {
     pots: Vec<PartialPot>,
 
@@ -22,6 +22,9 @@ pub struct NoLimitPot {
     bb_amt: u64,
     ante_amt: u64,          // if this is a bomb pot, the ante gets used as the bomb amount. Blinds are then not posted
     is_bomb_pot: bool,
+
+    rake_settings: RakeSettings,
+    rake: u64,              // taken out of the contested pots once the hand is settled
 }
 
 impl NoLimitPot {
@@ -39,6 +42,9 @@ impl NoLimitPot {
             bb_amt: 0,
             ante_amt: 0,
             is_bomb_pot: false,
+
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         }
     }
 
@@ -56,8 +62,9 @@ impl NoLimitPot {
         }
     }
 
-    fn post_blind_amt(&mut self, bb_pos: &usize, sb_pos: &usize) -> Result<(), &str> {
-        if !self.player_stacks_bets.contains_key(bb_pos) || !self.player_stacks_bets.contains_key(sb_pos) {
+    // A small blind position of None means the small blind is dead (nobody posts it)
+    fn post_blind_amt(&mut self, bb_pos: &usize, sb_pos: Option<&usize>) -> Result<(), &str> {
+        if !self.player_stacks_bets.contains_key(bb_pos) || sb_pos.map_or(false, |sb_pos| !self.player_stacks_bets.contains_key(sb_pos)) {
             return Err("Positions are not valid positions");
         }
 
@@ -69,7 +76,7 @@ impl NoLimitPot {
             self.bet_sizes.insert(bet_size);
         }
 
-        {
+        if let Some(sb_pos) = sb_pos {
             let mut sb_stack = self.player_stacks_bets.get_mut(sb_pos).unwrap();
             let bet_size = std::cmp::min(sb_stack.0, self.sb_amt);
             sb_stack.1 = bet_size;
@@ -93,6 +100,22 @@ impl NoLimitPot {
         self.collect_bets();
     }
 
+    // Money that goes straight into the pot without counting towards the player's bet
+    fn post_dead(&mut self, pos: &usize, amount: u64) {
+        let stack_bet = self.player_stacks_bets.get_mut(pos).unwrap();
+        let dead_amt = std::cmp::min(stack_bet.0 - stack_bet.1, amount);
+        stack_bet.0 -= dead_amt;
+
+        if self.pots.is_empty() {
+            let p: Vec<usize> = self.player_stacks_bets.keys().cloned().collect();
+            self.pots.push(PartialPot {
+                amount: 0,
+                elegible_players: HashSet::from_iter(p),
+            });
+        }
+        self.pots.last_mut().unwrap().amount += dead_amt;
+    }
+
     fn can_pos_raise(& self, pos: &usize) -> bool {
         if let Some((lb, llb)) = self.largest_bet_idxes {
             llb < lb && (*pos > lb || *pos < llb) ||
@@ -102,10 +125,202 @@ impl NoLimitPot {
             true
         }
     }
+
+    // Every side pot with chips in it needs an eligible player with a hand ranking, so a pot is either paid out in full or not at all
+    fn check_rankings(& self, hand_rankings: &Vec<HashMap<usize, Rank>>) -> Result<(), &'static str> {
+        if hand_rankings.len() == 0 {
+            return Err("hand_rankings was of length 0");
+        }
+        let has_unranked_pot = self.pots.iter()
+            .filter(|sidepot| sidepot.amount > 0)
+            .any(|sidepot| !hand_rankings.iter().any(|ranks| sidepot.elegible_players.iter().any(|pos| ranks.contains_key(pos))));
+        if has_unranked_pot {
+            return Err("No eligible player of a side pot has a hand ranking");
+        }
+        Ok(())
+    }
+
+    // Takes the rake out of the pots, main pot first. Pots with a single eligible player are uncalled bets and never get raked
+    fn take_rake(&mut self) {
+        let contested = self.pots.iter()
+            .filter(|sidepot| sidepot.elegible_players.len() > 1)
+            .map(|sidepot| sidepot.amount)
+            .sum();
+        let mut rake_left = self.rake_settings.get_rake(contested);
+        for sidepot in self.pots.iter_mut().filter(|sidepot| sidepot.elegible_players.len() > 1) {
+            let taken = std::cmp::min(rake_left, sidepot.amount);
+            sidepot.amount -= taken;
+            rake_left -= taken;
+            self.rake += taken;
+        }
+    }
+
+    // Pays out every side pot, the rankings have to be checked beforehand
+    fn pay_out(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> HashMap<usize, u64> {
+        let mut total_winnings: HashMap<usize, u64> = HashMap::new();
+        for sidepot in &self.pots {
+            if sidepot.amount == 0 {
+                continue;
+            }
+
+            // An objective nobody in the side pot qualifies for (e.g. no low) rolls its share into the others
+            let objectives: Vec<&HashMap<usize, Rank>> = hand_rankings.iter()
+                .filter(|ranks| sidepot.elegible_players.iter().any(|pos| ranks.contains_key(pos)))
+                .collect();
+
+            let distribution = objectives.len();
+            let amt_per_subsidepot = sidepot.amount / (distribution as u64);
+            let mut sub_sidepots = vec![amt_per_subsidepot; distribution];
+
+            let mut sidepot_left = sidepot.amount - amt_per_subsidepot * (distribution as u64);
+
+            // Odd chip of overall side pot is given to sub sidepot priority
+            if odd_chip == OddChipPriority::RankPriority {
+                for sp in &mut sub_sidepots {
+                    if sidepot_left == 0 {
+                        break
+                    }
+                    *sp += 1;
+                    sidepot_left -= 1;
+                }
+            }
+
+            let mut winners: BTreeSet<usize> = BTreeSet::new();
+            for (ranks, sub_sidepot) in objectives.into_iter().zip(sub_sidepots) {
+                let mut sidepot_winners: BTreeMap<usize, PotDistribution> = BTreeMap::new();
+                let mut highest_rank_hand: Option<Rank> = None;
+                let mut winner_set: BTreeSet<usize> = BTreeSet::new();
+                for pos in &sidepot.elegible_players {
+                    if let Some(rank) = ranks.get(pos) {
+                        if let Some(highest_rank) = highest_rank_hand {
+                            if *rank > highest_rank {
+                                highest_rank_hand = Some(*rank);
+                                winner_set.clear();
+                                winner_set.insert(*pos);
+                            } else if *rank == highest_rank {
+                                winner_set.insert(*pos);
+                            }
+                        } else {
+                            highest_rank_hand = Some(*rank);
+                            winner_set.insert(*pos);
+                        }
+                    }
+                }
+
+                let mut sidepot_left = sub_sidepot;
+                let chips_awarded = sub_sidepot / (winner_set.len() as u64);
+
+                for w in winner_set {
+                    if let Some(d) = sidepot_winners.get_mut(&w) {
+                        d.chip_dist += chips_awarded;
+                    } else {
+                        sidepot_winners.insert(w, PotDistribution {
+                            chip_dist: chips_awarded,
+                        });
+                    }
+                    sidepot_left -= chips_awarded;
+                    winners.insert(w);
+                }
+
+                // // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
+                // for d in distribution.iter_mut() {
+                //     if sidepot_left == 0 {
+                //         break;
+                //     }
+                //     *d += 1;
+                //     sidepot_left -= 1;
+                // }
+
+                let left_of_btn = sidepot_winners.range((Excluded(btn_idx), Unbounded));
+                let right_of_btn = sidepot_winners.range((Unbounded, Included(btn_idx)));
+
+                let sb_to_btn = left_of_btn.chain(right_of_btn);
+                // distribute sidepot to winners
+                for (pos, winnings) in sb_to_btn {
+                    if let Some(player) = players.get_mut(pos) {
+                        let mut chips_awarded = winnings.chip_dist;
+                        // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
+                        if sidepot_left > 0 {
+                            chips_awarded += 1;
+                            sidepot_left -= 1;
+                        }
+                        player.stack += chips_awarded;
+                        let mut total = *total_winnings.get(pos).unwrap_or(&0);
+                        total += chips_awarded;
+                        total_winnings.insert(*pos, total);
+                    }
+                }
+            }
+
+            // Odd chip of overall sidepot is distributed in OOP order
+            if odd_chip == OddChipPriority::OOP {
+                let left_of_btn = winners.range((Excluded(btn_idx), Unbounded));
+                let right_of_btn = winners.range((Unbounded, Included(btn_idx)));
+
+                let sb_to_btn = left_of_btn.chain(right_of_btn);
+                // distribute sidepot to winners
+                for pos in sb_to_btn {
+                    if let Some(player) = players.get_mut(pos) {
+                        // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
+                        if sidepot_left == 0 {
+                            break;
+                        }
+
+                        player.stack += 1;
+                        let mut total = *total_winnings.get(pos).unwrap_or(&0);
+                        total += 1;
+                        total_winnings.insert(*pos, total);
+                        sidepot_left -= 1;
+                    }
+                }
+            }
+
+            // // Should never be 0, but this is a safety check
+            // if sidepot_winners.len() > 0 {
+            //     // distribute sidepot to winners
+            //     let mut pot_amt = sidepot.amount;
+            //     let winner_count = sidepot_winners.len() as u64;
+            //     let pot_per_winner = pot_amt / winner_count;
+
+            //     // most OOP to IP
+            //     let mut distribution = vec![pot_per_winner; winner_count as usize];
+
+            //     pot_amt -= pot_per_winner * winner_count;
+
+            //     // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
+            //     for d in distribution.iter_mut() {
+            //         if pot_amt == 0 {
+            //             break;
+            //         }
+            //         *d += 1;
+            //         pot_amt -= 1;
+            //     }
+
+            //     let left_of_btn = sidepot_winners.range((Excluded(btn_idx), Unbounded));
+            //     let right_of_btn = sidepot_winners.range((Unbounded, Included(btn_idx)));
+
+            //     let sb_to_btn = left_of_btn.chain(right_of_btn);
+            //     for (pos, winnings) in sb_to_btn.zip(distribution) {
+            //         if let Some(player) = players.get_mut(pos) {
+            //             player.stack += winnings;
+            //             let mut total = *total_winnings.get(pos).unwrap_or(&0);
+            //             total += winnings;
+            //             total_winnings.insert(*pos, total);
+            //         }
+            //     }
+            // }
+        }
+
+        total_winnings
+    }
 }
 
 impl Pot for NoLimitPot {
 
+    fn clone_box(& self) -> Box<dyn Pot + Send> {
+        Box::new(self.clone())
+    }
+
     fn get_all_player_stacks_bets(& self) -> &BTreeMap<usize, (u64, u64)> {
         &self.player_stacks_bets
     }
@@ -147,9 +362,14 @@ impl Pot for NoLimitPot {
         self.bet_diff = bb;
         self.largest_bet = 0;
         self.largest_bet_idxes = None;
+        self.rake = 0;
+
+        // clear out everything left over from the previous hand
+        self.pots.clear();
+        self.player_stacks_bets.clear();
 
         for (_, p) in players {
-            if !p.is_away {
+            if p.is_in_hand && !p.is_away {
                 let entry = (p.stack, 0);
                 self.player_stacks_bets.insert(p.table_position, entry);
             }
@@ -217,6 +437,22 @@ impl Pot for NoLimitPot {
         self.largest_bet_idxes
     }
 
+    fn get_pots(& self) -> &Vec<PartialPot> {
+        &self.pots
+    }
+
+    fn get_total_pot(& self) -> u64 {
+        self.pots.iter().map(|sidepot| sidepot.amount).sum()
+    }
+
+    fn get_rake(& self) -> u64 {
+        self.rake
+    }
+
+    fn set_rake_settings(&mut self, settings: RakeSettings) {
+        self.rake_settings = settings;
+    }
+
     fn is_bomb_pot(& self) -> bool {
         self.is_bomb_pot
     }
@@ -235,11 +471,126 @@ impl Pot for NoLimitPot {
             pos_arr.extend(Vec::from_iter(left_pos_arr.iter()));
             // pos_arr now contains the person in the bb in the first idx and the sb in the last index
 
-            self.post_blind_amt(pos_arr[0], pos_arr[pos_arr.len() - 1]).unwrap();
+            self.post_blind_amt(pos_arr[0], Some(pos_arr[pos_arr.len() - 1])).unwrap();
+        }
+        Ok(())
+    }
+
+    fn post_blinds_before_deal(&mut self, sb_idx: Option<&usize>, bb_idx: &usize) -> Result<(), &str> {
+        // the big blind does not post in a bomb pot, so they might not even be in the hand
+        if !self.is_bomb_pot && !self.player_stacks_bets.contains_key(bb_idx) {
+            return Err("Big blind position is not a player in the hand");
+        }
+
+        // pay ante first
+        self.pay_and_collect_ante();
+
+        if !self.is_bomb_pot {
+            // a small blind that is not dealt in is treated as a dead small blind
+            let sb_idx = sb_idx.filter(|sb_idx| self.player_stacks_bets.contains_key(sb_idx) && *sb_idx != bb_idx);
+            self.post_blind_amt(bb_idx, sb_idx)?;
+        }
+        Ok(())
+    }
+
+    fn post_missed_blinds(&mut self, pos: &usize, post_bb: bool, post_dead_sb: bool) -> Result<(), &str> {
+        if !self.player_stacks_bets.contains_key(pos) {
+            return Err("Position is not a player in the hand");
+        }
+
+        if self.is_bomb_pot {
+            return Err("Missed blinds cannot be posted in a bomb pot");
+        }
+
+        if post_bb {
+            let bb_amt = self.bb_amt;
+            let stack_bet = self.player_stacks_bets.get_mut(pos).unwrap();
+            let bet_size = std::cmp::min(stack_bet.0, std::cmp::max(stack_bet.1, bb_amt));
+            stack_bet.1 = bet_size;
+
+            self.bet_sizes.insert(bet_size);
+        }
+
+        if post_dead_sb {
+            self.post_dead(pos, self.sb_amt);
+        }
+
+        Ok(())
+    }
+
+    fn post_straddles(&mut self, straddle_idxes: &Vec<usize>) -> Result<(), &str> {
+        if straddle_idxes.iter().any(|pos| !self.player_stacks_bets.contains_key(pos)) {
+            return Err("Straddle position is not a player in the hand");
+        }
+
+        if self.is_bomb_pot && !straddle_idxes.is_empty() {
+            return Err("Straddles cannot be posted in a bomb pot");
+        }
+
+        let mut amount = self.bb_amt;
+        for pos in straddle_idxes {
+            amount *= 2;
+            let stack_bet = self.player_stacks_bets.get_mut(pos).unwrap();
+            let bet_size = std::cmp::min(stack_bet.0, amount);
+            stack_bet.1 = bet_size;
+
+            self.bet_sizes.insert(bet_size);
+        }
+
+        if !straddle_idxes.is_empty() {
+            // the last straddle acts as the big blind, so raises have to be at least the size of it
+            self.largest_bet = amount;
+            self.bet_diff = amount;
+        }
+
+        Ok(())
+    }
+
+    fn post_antes_before_deal(&mut self) -> Result<(), &str> {
+        if self.is_bomb_pot {
+            return Err("Bomb pots are posted with the blinds");
+        }
+
+        self.pay_and_collect_ante();
+        Ok(())
+    }
+
+    fn post_button_ante(&mut self, btn_idx: &usize) -> Result<(), &str> {
+        if !self.player_stacks_bets.contains_key(btn_idx) {
+            return Err("Button is not a player in the hand");
+        }
+
+        if self.is_bomb_pot {
+            return Err("Bomb pots are posted with the blinds");
         }
+
+        self.post_dead(btn_idx, self.ante_amt);
         Ok(())
     }
 
+    fn post_bring_in(&mut self, pos: &usize) -> Result<u64, &str> {
+        if self.is_bomb_pot {
+            return Err("Bring-ins cannot be posted in a bomb pot");
+        }
+
+        let sb_amt = self.sb_amt;
+        let stack_bet = match self.player_stacks_bets.get_mut(pos) {
+            Some(stack_bet) => stack_bet,
+            None => return Err("Position is not a player in the hand"),
+        };
+        let bet_size = std::cmp::min(stack_bet.0, sb_amt);
+        stack_bet.1 = bet_size;
+        self.bet_sizes.insert(bet_size);
+
+        // completing the bring-in up to the big blind counts as a raise
+        self.largest_bet = bet_size;
+        if bet_size < self.bb_amt {
+            self.bet_diff = self.bb_amt - bet_size;
+        }
+
+        Ok(bet_size)
+    }
+
     // Function to indicate player in position pos is betting/raising/shoving an amount of bet
     fn bet_or_shove(&mut self, pos: &usize, bet: u64) -> Result<u64, std::string::String> {
         if !self.can_pos_raise(pos) {
@@ -255,7 +606,8 @@ impl Pot for NoLimitPot {
                 let mut lb_llb = self.largest_bet_idxes.unwrap_or((*pos, *pos));
 
                 if bet >= min_bet {
-                    self.bet_diff = bet_size - self.largest_bet;
+                    // completing a bring-in raises by less than the big blind, which is still the smallest raise after it
+                    self.bet_diff = std::cmp::max(bet_size - self.largest_bet, self.bb_amt);
                     lb_llb.1 = *pos;
                 }
 
@@ -303,163 +655,49 @@ impl Pot for NoLimitPot {
         Ok(())
     }
 
-    // Returns back a map of who won and how much they won
-    // TODO: I can prpobably add rayon to this since each side pot relies on independent pieces of data
-    fn distribute_pot(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<HashMap<usize, u64>, &str> {
-        if hand_rankings.len() == 0 {
-            return Err("hand_rankings was of length 0");
+    fn fold_out_of_turn(&mut self, pos: &usize) -> Result<(), std::string::String> {
+        for p in &mut self.pots {
+            p.elegible_players.remove(pos);
         }
+        Ok(())
+    }
 
-        let mut total_winnings: HashMap<usize, u64> = HashMap::new();
-        for sidepot in &self.pots {
-            if sidepot.amount == 0 {
-                continue;
-            }
+    fn distribute_pot_runouts(&mut self, players: &mut HashMap<usize, Player>, runout_rankings: &Vec<Vec<HashMap<usize, Rank>>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<Vec<HashMap<usize, u64>>, &str> {
+        if runout_rankings.len() == 0 || runout_rankings.iter().any(|rankings| rankings.len() == 0) {
+            return Err("runout_rankings was of length 0");
+        }
+        // nothing gets paid out unless every runout can be paid out
+        for hand_rankings in runout_rankings {
+            self.check_rankings(hand_rankings)?;
+        }
+        self.take_rake();
 
-            let distribution = hand_rankings.len();
-            let amt_per_subsidepot = sidepot.amount / (distribution as u64);
-            let mut sub_sidepots = vec![amt_per_subsidepot; distribution];
+        let runouts = runout_rankings.len() as u64;
+        let pots = std::mem::take(&mut self.pots);
+        let mut runout_winnings = Vec::new();
+        for (runout_idx, hand_rankings) in runout_rankings.iter().enumerate() {
+            // odd chips of a pot go to the earlier runouts
+            self.pots = pots.iter().map(|sidepot| {
+                let extra_chip = if (runout_idx as u64) < sidepot.amount % runouts { 1 } else { 0 };
+                PartialPot {
+                    amount: sidepot.amount / runouts + extra_chip,
+                    elegible_players: sidepot.elegible_players.clone(),
+                }
+            }).collect();
 
-            let mut sidepot_left = sidepot.amount - amt_per_subsidepot * (distribution as u64);
-
-            // Odd chip of overall side pot is given to sub sidepot priority
-            if odd_chip == OddChipPriority::RankPriority {
-                for sp in &mut sub_sidepots {
-                    if sidepot_left == 0 {
-                        break
-                    }
-                    *sp += 1;
-                    sidepot_left -= 1;
-                }
-            }
-
-            let mut winners: BTreeSet<usize> = BTreeSet::new();
-            for (ranks, sub_sidepot) in hand_rankings.iter().zip(sub_sidepots) {
-                let mut sidepot_winners: BTreeMap<usize, PotDistribution> = BTreeMap::new();
-                let mut highest_rank_hand: Option<Rank> = None;
-                let mut winner_set: BTreeSet<usize> = BTreeSet::new();
-                for pos in &sidepot.elegible_players {
-                    if let Some(rank) = ranks.get(pos) {
-                        if let Some(highest_rank) = highest_rank_hand {
-                            if *rank > highest_rank {
-                                highest_rank_hand = Some(*rank);
-                                winner_set.clear();
-                                winner_set.insert(*pos);
-                            } else if *rank == highest_rank {
-                                winner_set.insert(*pos);
-                            }
-                        } else {
-                            highest_rank_hand = Some(*rank);
-                            winner_set.insert(*pos);
-                        }
-                    }
-                }
-
-                let mut sidepot_left = sub_sidepot;
-                let chips_awarded = sub_sidepot / (winner_set.len() as u64);
-
-                for w in winner_set {
-                    if let Some(d) = sidepot_winners.get_mut(&w) {
-                        d.chip_dist += chips_awarded;
-                    } else {
-                        sidepot_winners.insert(w, PotDistribution {
-                            chip_dist: chips_awarded,
-                        });
-                    }
-                    sidepot_left -= chips_awarded;
-                    winners.insert(w);
-                }
-
-                // // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
-                // for d in distribution.iter_mut() {
-                //     if sidepot_left == 0 {
-                //         break;
-                //     }
-                //     *d += 1;
-                //     sidepot_left -= 1;
-                // }
-
-                let left_of_btn = sidepot_winners.range((Excluded(btn_idx), Unbounded));
-                let right_of_btn = sidepot_winners.range((Unbounded, Included(btn_idx)));
-
-                let sb_to_btn = left_of_btn.chain(right_of_btn);
-                // distribute sidepot to winners
-                for (pos, winnings) in sb_to_btn {
-                    if let Some(player) = players.get_mut(pos) {
-                        let mut chips_awarded = winnings.chip_dist;
-                        // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
-                        if sidepot_left > 0 {
-                            chips_awarded += 1;
-                            sidepot_left -= 1;
-                        }
-                        player.stack += chips_awarded;
-                        let mut total = *total_winnings.get(pos).unwrap_or(&0);
-                        total += chips_awarded;
-                        total_winnings.insert(*pos, total);
-                    }
-                }
-            }
-
-            // Odd chip of overall sidepot is distributed in OOP order
-            if odd_chip == OddChipPriority::OOP {
-                let left_of_btn = winners.range((Excluded(btn_idx), Unbounded));
-                let right_of_btn = winners.range((Unbounded, Included(btn_idx)));
-
-                let sb_to_btn = left_of_btn.chain(right_of_btn);
-                // distribute sidepot to winners
-                for pos in sb_to_btn {
-                    if let Some(player) = players.get_mut(pos) {
-                        // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
-                        if sidepot_left == 0 {
-                            break;
-                        }
-
-                        player.stack += 1;
-                        let mut total = *total_winnings.get(pos).unwrap_or(&0);
-                        total += 1;
-                        total_winnings.insert(*pos, total);
-                        sidepot_left -= 1;
-                    }
-                }
-            }
-
-            // // Should never be 0, but this is a safety check
-            // if sidepot_winners.len() > 0 {
-            //     // distribute sidepot to winners
-            //     let mut pot_amt = sidepot.amount;
-            //     let winner_count = sidepot_winners.len() as u64;
-            //     let pot_per_winner = pot_amt / winner_count;
-
-            //     // most OOP to IP
-            //     let mut distribution = vec![pot_per_winner; winner_count as usize];
-
-            //     pot_amt -= pot_per_winner * winner_count;
-
-            //     // Give one chip per person until we run out, bc pot_amt is just less than sidepot_winners.len()
-            //     for d in distribution.iter_mut() {
-            //         if pot_amt == 0 {
-            //             break;
-            //         }
-            //         *d += 1;
-            //         pot_amt -= 1;
-            //     }
-
-            //     let left_of_btn = sidepot_winners.range((Excluded(btn_idx), Unbounded));
-            //     let right_of_btn = sidepot_winners.range((Unbounded, Included(btn_idx)));
-
-            //     let sb_to_btn = left_of_btn.chain(right_of_btn);
-            //     for (pos, winnings) in sb_to_btn.zip(distribution) {
-            //         if let Some(player) = players.get_mut(pos) {
-            //             player.stack += winnings;
-            //             let mut total = *total_winnings.get(pos).unwrap_or(&0);
-            //             total += winnings;
-            //             total_winnings.insert(*pos, total);
-            //         }
-            //     }
-            // }
+            runout_winnings.push(self.pay_out(players, hand_rankings, btn_idx, odd_chip));
         }
+        self.pots = pots;
 
-        Ok(total_winnings)
+        Ok(runout_winnings)
+    }
+
+    // Returns back a map of who won and how much they won
+    // TODO: I can prpobably add rayon to this since each side pot relies on independent pieces of data
+    fn distribute_pot(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<HashMap<usize, u64>, &str> {
+        self.check_rankings(hand_rankings)?;
+        self.take_rake();
+        Ok(self.pay_out(players, hand_rankings, btn_idx, odd_chip))
     }
 
 }
@@ -511,11 +749,42 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
     }
 
+    #[test]
+    fn reset_clears_previous_hand() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [500, 400, 750, 220];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(pot.post_before_deal(&2), Ok(()));
+        assert_eq!(pot.check_call(&3), Ok(2));
+        pot.collect_bets();
+
+        // player 3 leaves the table and player 1 is sitting out
+        players.remove(&3);
+        players.get_mut(&1).unwrap().is_away = true;
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        assert_eq!(Vec::<PartialPot>::new(), pot.pots);
+        assert_eq!(BTreeMap::from([
+            (0, (500, 0)),
+            (2, (750, 0)),
+        ]), pot.player_stacks_bets);
+    }
+
     #[test]
     fn basic_post_blinds() {
         let mut players = HashMap::<usize, Player>::new();
@@ -559,6 +828,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -603,71 +874,428 @@ mod tests {
             largest_bet: bb,
             bet_diff: bb,
 
-            sb_amt: sb,
-            bb_amt: bb,
-            ante_amt: ante,
-            is_bomb_pot: is_bomb,
-        };
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn post_bomb_pot_ante() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [2000, 2000, 2000, 2000];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 10;
+        let bb = 20;
+        let ante = 100;
+        let is_bomb = true;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        let bb_idx = 2;
+        assert_eq!(pot.post_before_deal(&bb_idx), Ok(()));
+
+        let expected_pot = NoLimitPot {
+            pots: vec![
+                PartialPot {
+                    amount: 400,
+                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                },
+            ],
+            player_stacks_bets: BTreeMap::from([
+                (0, (1900, 0)),
+                (1, (1900, 0)),
+                (2, (1900, 0)),
+                (3, (1900, 0)),
+            ]),
+            bet_sizes: BTreeSet::new(),
+            largest_bet_idxes: None,
+            largest_bet: 0,
+            bet_diff: bb,
+
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn advanced_post_blinds() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 200];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        let bb_idx = 0;
+        assert_eq!(pot.post_before_deal(&bb_idx), Ok(()));
+
+        let expected_pot = NoLimitPot {
+            pots: vec![
+                PartialPot {
+                    amount: 0,
+                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                },
+            ],
+            player_stacks_bets: BTreeMap::from([
+                (0, (200, 2)),
+                (1, (200, 0)),
+                (2, (200, 0)),
+                (3, (200, 1)),
+            ]),
+            bet_sizes: BTreeSet::from([1, 2]),
+            largest_bet_idxes: None,
+            largest_bet: bb,
+            bet_diff: bb,
+
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn post_blinds_dead_small_blind() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 200];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        assert_eq!(pot.post_blinds_before_deal(None, &2), Ok(()));
+
+        let expected_pot = NoLimitPot {
+            pots: vec![
+                PartialPot {
+                    amount: 0,
+                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                },
+            ],
+            player_stacks_bets: BTreeMap::from([
+                (0, (200, 0)),
+                (1, (200, 0)),
+                (2, (200, 2)),
+                (3, (200, 0)),
+            ]),
+            bet_sizes: BTreeSet::from([2]),
+            largest_bet_idxes: None,
+            largest_bet: bb,
+            bet_diff: bb,
+
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn post_blinds_heads_up() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200];
+
+        for id in 0..2 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        // button posts the small blind
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &0), Ok(()));
+
+        assert_eq!(BTreeMap::from([
+            (0, (200, 2)),
+            (1, (200, 1)),
+        ]), pot.player_stacks_bets);
+        assert_eq!(BTreeSet::from([1, 2]), pot.bet_sizes);
+    }
+
+    #[test]
+    fn post_blinds_invalid_big_blind() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+        players.get_mut(&2).unwrap().is_away = true;
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert!(pot.post_blinds_before_deal(Some(&1), &2).is_err());
+    }
+
+    #[test]
+    fn post_missed_big_and_small_blind() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 200];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_missed_blinds(&0, true, true), Ok(()));
+
+        let expected_pot = NoLimitPot {
+            pots: vec![
+                PartialPot {
+                    amount: 1,
+                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                },
+            ],
+            player_stacks_bets: BTreeMap::from([
+                (0, (199, 2)),
+                (1, (200, 1)),
+                (2, (200, 2)),
+                (3, (200, 0)),
+            ]),
+            bet_sizes: BTreeSet::from([1, 2]),
+            largest_bet_idxes: None,
+            largest_bet: bb,
+            bet_diff: bb,
+
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn post_missed_small_blind_only() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 200];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
+
+        assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
+
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_missed_blinds(&3, false, true), Ok(()));
+
+        let expected_pot = NoLimitPot {
+            pots: vec![
+                PartialPot {
+                    amount: 1,
+                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                },
+            ],
+            player_stacks_bets: BTreeMap::from([
+                (0, (200, 0)),
+                (1, (200, 1)),
+                (2, (200, 2)),
+                (3, (199, 0)),
+            ]),
+            bet_sizes: BTreeSet::from([1, 2]),
+            largest_bet_idxes: None,
+            largest_bet: bb,
+            bet_diff: bb,
+
+            sb_amt: sb,
+            bb_amt: bb,
+            ante_amt: ante,
+            is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
+        };
+
+        assert_eq!(expected_pot, pot);
+    }
+
+    #[test]
+    fn post_missed_blinds_short_stack() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 3];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 2, 4, 0, false), Ok(()));
+
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_missed_blinds(&3, true, true), Ok(()));
+
+        // live big blind gets posted first, whatever is left goes in dead
+        assert_eq!(&(3, 3), pot.get_player_stack_bet(&3).unwrap());
+        assert_eq!(0, pot.pots[0].amount);
+        assert_eq!(BTreeSet::from([2, 3, 4]), pot.bet_sizes);
+    }
+
+    #[test]
+    fn post_missed_blinds_invalid_position() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+        players.get_mut(&0).unwrap().is_away = true;
 
-        assert_eq!(expected_pot, pot);
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert!(pot.post_missed_blinds(&0, true, true).is_err());
     }
 
     #[test]
-    fn post_bomb_pot_ante() {
+    fn post_button_ante_without_blinds() {
         let mut players = HashMap::<usize, Player>::new();
         let mut pot = NoLimitPot::new();
 
-        let starting_stacks = [2000, 2000, 2000, 2000];
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 3, false), Ok(()));
+        assert_eq!(pot.post_button_ante(&0), Ok(()));
+        assert_eq!(3, pot.get_total_pot());
+        assert_eq!(Ok(&(197, 0)), pot.get_player_stack_bet(&0));
+        assert_eq!(Ok(&(200, 0)), pot.get_player_stack_bet(&1));
+
+        // nobody has bet yet, so the smallest bet is the big blind
+        assert!(pot.bet_or_shove(&1, 1).is_err());
+        assert_eq!(pot.bet_or_shove(&1, 2), Ok(2));
+        assert_eq!(pot.check_call(&2), Ok(2));
+        assert_eq!(pot.check_call(&0), Ok(2));
+        pot.collect_bets();
+        assert_eq!(9, pot.get_total_pot());
+        assert!(pot.post_button_ante(&3).is_err());
+    }
+
+    #[test]
+    fn post_single_straddle() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [200, 200, 200, 200];
 
         for id in 0..4 {
             players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
         }
 
-        let sb = 10;
-        let bb = 20;
-        let ante = 100;
-        let is_bomb = true;
+        let sb = 1;
+        let bb = 2;
+        let ante = 0;
+        let is_bomb = false;
 
         assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
 
-        let bb_idx = 2;
-        assert_eq!(pot.post_before_deal(&bb_idx), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_straddles(&vec![3]), Ok(()));
 
         let expected_pot = NoLimitPot {
             pots: vec![
                 PartialPot {
-                    amount: 400,
+                    amount: 0,
                     elegible_players: HashSet::from([0, 1, 2, 3]),
                 },
             ],
             player_stacks_bets: BTreeMap::from([
-                (0, (1900, 0)),
-                (1, (1900, 0)),
-                (2, (1900, 0)),
-                (3, (1900, 0)),
+                (0, (200, 0)),
+                (1, (200, 1)),
+                (2, (200, 2)),
+                (3, (200, 4)),
             ]),
-            bet_sizes: BTreeSet::new(),
+            bet_sizes: BTreeSet::from([1, 2, 4]),
             largest_bet_idxes: None,
-            largest_bet: 0,
-            bet_diff: bb,
+            largest_bet: 4,
+            bet_diff: 4,
 
             sb_amt: sb,
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
     }
 
     #[test]
-    fn advanced_post_blinds() {
+    fn post_re_straddles() {
         let mut players = HashMap::<usize, Player>::new();
         let mut pot = NoLimitPot::new();
 
-        let starting_stacks = [200, 200, 200, 200];
+        let starting_stacks = [200, 200, 200, 200, 200, 200];
 
-        for id in 0..4 {
+        for id in 0..6 {
             players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
         }
 
@@ -678,36 +1306,94 @@ mod tests {
 
         assert_eq!(pot.reset_pot(&players, sb, bb, ante, is_bomb), Ok(()));
 
-        let bb_idx = 0;
-        assert_eq!(pot.post_before_deal(&bb_idx), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_straddles(&vec![3, 4, 5]), Ok(()));
 
         let expected_pot = NoLimitPot {
             pots: vec![
                 PartialPot {
                     amount: 0,
-                    elegible_players: HashSet::from([0, 1, 2, 3]),
+                    elegible_players: HashSet::from([0, 1, 2, 3, 4, 5]),
                 },
             ],
             player_stacks_bets: BTreeMap::from([
-                (0, (200, 2)),
-                (1, (200, 0)),
-                (2, (200, 0)),
-                (3, (200, 1)),
+                (0, (200, 0)),
+                (1, (200, 1)),
+                (2, (200, 2)),
+                (3, (200, 4)),
+                (4, (200, 8)),
+                (5, (200, 16)),
             ]),
-            bet_sizes: BTreeSet::from([1, 2]),
+            bet_sizes: BTreeSet::from([1, 2, 4, 8, 16]),
             largest_bet_idxes: None,
-            largest_bet: bb,
-            bet_diff: bb,
+            largest_bet: 16,
+            bet_diff: 16,
 
             sb_amt: sb,
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
     }
 
+    #[test]
+    fn raise_over_straddle() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert_eq!(pot.post_straddles(&vec![3]), Ok(()));
+
+        // min raise is to twice the straddle
+        assert!(pot.bet_or_shove(&0, 6).is_err());
+        assert_eq!(Ok(8), pot.bet_or_shove(&0, 8));
+        assert_eq!(Some((0, 0)), pot.largest_bet_idxes);
+    }
+
+    #[test]
+    fn fold_out_of_turn_keeps_action_open() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        pot.collect_bets();
+
+        assert_eq!(Ok(()), pot.fold_out_of_turn(&2));
+        assert_eq!(None, pot.largest_bet_idxes);
+        assert!(!pot.pots[0].elegible_players.contains(&2));
+
+        assert_eq!(Ok(()), pot.fold(&1));
+        assert_eq!(Some((1, 1)), pot.largest_bet_idxes);
+    }
+
+    #[test]
+    fn post_straddle_invalid_position() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 200));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(pot.post_blinds_before_deal(Some(&1), &2), Ok(()));
+        assert!(pot.post_straddles(&vec![5]).is_err());
+    }
+
     #[test]
     fn basic_call_preflop() {
         let mut players = HashMap::<usize, Player>::new();
@@ -758,6 +1444,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -814,6 +1502,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -872,6 +1562,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -922,6 +1614,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -972,6 +1666,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -1023,6 +1719,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -1074,6 +1772,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -1127,6 +1827,8 @@ mod tests {
             bb_amt: bb,
             ante_amt: ante,
             is_bomb_pot: is_bomb,
+            rake_settings: RakeSettings::new(),
+            rake: 0,
         };
 
         assert_eq!(expected_pot, pot);
@@ -1480,6 +2182,51 @@ mod tests {
         check_flipped(&pot);
     }
 
+    #[test]
+    fn rake_only_taken_from_contested_pots() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+        pot.set_rake_settings(RakeSettings { percent: 10.0, cap: None });
+
+        for id in 0..2 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 400));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        let btn_idx = 0;
+
+        // the second pot is an uncalled bet
+        pot.pots.extend(vec![
+            PartialPot {
+                amount: 125,
+                elegible_players: HashSet::from([0, 1]),
+            },
+            PartialPot {
+                amount: 70,
+                elegible_players: HashSet::from([0]),
+            },
+        ]);
+
+        let rankings: Vec<HashMap<usize, Rank>> = Vec::from([
+            HashMap::from([
+                (0, Rank::High(HighRank::new(300))),
+                (1, Rank::High(HighRank::new(2000))),
+            ]),
+        ]);
+
+        let expected_output: HashMap<usize, u64> = HashMap::from([
+            (0, 70),
+            (1, 113),
+        ]);
+        assert_eq!(expected_output, pot.distribute_pot(&mut players, &rankings, &btn_idx, OddChipPriority::OOP).expect("Error distributing pot"));
+        assert_eq!(12, pot.get_rake());
+
+        // the next hand starts without any rake
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+        assert_eq!(0, pot.get_rake());
+    }
+
     #[test]
     fn basic_distribute_pot() {
         let mut players = HashMap::<usize, Player>::new();
@@ -1964,6 +2711,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn double_objective_pot_no_qualifying_low() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [50, 150, 400, 75];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        let btn_idx = 0;
+
+        pot.pots.push(PartialPot {
+            amount: 233,
+            elegible_players: HashSet::from([0, 3]),
+        });
+
+        // nobody made a low, so the high hand scoops
+        let rankings: Vec<HashMap<usize, Rank>> = Vec::from([
+            HashMap::from([
+                (0, Rank::High(HighRank::new(1700))),
+                (3, Rank::High(HighRank::new(500))),
+            ]),
+            HashMap::new(),
+        ]);
+
+        let expected_output: HashMap<usize, u64> = HashMap::from([
+            (0, 233),
+        ]);
+        assert_eq!(expected_output, pot.distribute_pot(&mut players, &rankings, &btn_idx, OddChipPriority::OOP).expect("Error distributing pot"));
+
+        let expected_stacks = vec![283, 150, 400, 75];
+
+        for (pos, player) in &players {
+            assert_eq!(expected_stacks[*pos], player.stack);
+        }
+    }
+
+    #[test]
+    fn double_objective_side_pot_without_low() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [50, 150, 400, 75];
+
+        for id in 0..4 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        let btn_idx = 0;
+
+        let pots = vec![
+            PartialPot {
+                amount: 120,
+                elegible_players: HashSet::from([0, 1, 3]),
+            },
+            PartialPot {
+                amount: 100,
+                elegible_players: HashSet::from([0, 3]),
+            },
+        ];
+        pot.pots.extend(pots);
+
+        // only the short stack made a low, so the side pot they are not in goes to the high hand
+        let rankings: Vec<HashMap<usize, Rank>> = Vec::from([
+            HashMap::from([
+                (0, Rank::High(HighRank::new(1700))),
+                (1, Rank::High(HighRank::new(300))),
+                (3, Rank::High(HighRank::new(500))),
+            ]),
+            HashMap::from([
+                (1, Rank::High(HighRank::new(1200))),
+            ]),
+        ]);
+
+        let expected_output: HashMap<usize, u64> = HashMap::from([
+            (0, 160),
+            (1, 60),
+        ]);
+        assert_eq!(expected_output, pot.distribute_pot(&mut players, &rankings, &btn_idx, OddChipPriority::OOP).expect("Error distributing pot"));
+
+        let expected_stacks = vec![210, 210, 400, 75];
+
+        for (pos, player) in &players {
+            assert_eq!(expected_stacks[*pos], player.stack);
+        }
+    }
+
     #[test]
     fn check_contested_pot() {
         let mut players = HashMap::<usize, Player>::new();
@@ -2030,4 +2870,92 @@ mod tests {
         assert_eq!(false, pot.is_pot_contested());
     }
 
+    #[test]
+    fn distribute_pot_runouts_split() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let starting_stacks = [0, 0, 100];
+
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), starting_stacks[id]));
+        }
+
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        let btn_idx = 2;
+
+        let pots = vec![
+            PartialPot {
+                amount: 301,
+                elegible_players: HashSet::from([0, 1]),
+            },
+            PartialPot {
+                amount: 100,
+                elegible_players: HashSet::from([1]),
+            },
+        ];
+        pot.pots.extend(pots);
+
+        let runout_rankings = vec![
+            vec![HashMap::from([
+                (0, Rank::High(HighRank::new(2000))),
+                (1, Rank::High(HighRank::new(300))),
+            ])],
+            vec![HashMap::from([
+                (0, Rank::High(HighRank::new(100))),
+                (1, Rank::High(HighRank::new(300))),
+            ])],
+        ];
+
+        let expected_output = vec![
+            HashMap::from([(0, 151), (1, 50)]),
+            HashMap::from([(1, 200)]),
+        ];
+        assert_eq!(expected_output, pot.distribute_pot_runouts(&mut players, &runout_rankings, &btn_idx, OddChipPriority::OOP).expect("Error distributing pot"));
+
+        let expected_stacks = vec![151, 250, 100];
+
+        for (pos, player) in &players {
+            assert_eq!(expected_stacks[*pos], player.stack);
+        }
+    }
+
+    #[test]
+    fn distribute_nothing_when_side_pot_unranked() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        for id in 0..3 {
+            players.insert(id, Player::new(id, format!("Player {}", id), 100));
+        }
+        assert_eq!(pot.reset_pot(&players, 1, 2, 0, false), Ok(()));
+
+        pot.pots.extend(vec![
+            PartialPot {
+                amount: 300,
+                elegible_players: HashSet::from([0, 1, 2]),
+            },
+            PartialPot {
+                amount: 100,
+                elegible_players: HashSet::from([1, 2]),
+            },
+        ]);
+        // only the main pot has a ranked player
+        let rankings: Vec<HashMap<usize, Rank>> = vec![HashMap::from([(0, Rank::High(HighRank::new(2000)))])];
+
+        assert!(pot.distribute_pot(&mut players, &rankings, &0, OddChipPriority::OOP).is_err());
+        assert!(pot.distribute_pot_runouts(&mut players, &vec![rankings.clone(), rankings], &0, OddChipPriority::OOP).is_err());
+        assert!(players.values().all(|player| player.stack == 100));
+        assert_eq!(400, pot.get_total_pot());
+    }
+
+    #[test]
+    fn distribute_pot_runouts_no_rankings() {
+        let mut players = HashMap::<usize, Player>::new();
+        let mut pot = NoLimitPot::new();
+
+        let runout_rankings = vec![];
+        assert!(pot.distribute_pot_runouts(&mut players, &runout_rankings, &0, OddChipPriority::OOP).is_err());
+    }
 }
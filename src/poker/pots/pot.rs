@@ -3,15 +3,29 @@ use playing_cards::poker::Rank;
 
 use crate::poker::Player;
 
-use super::OddChipPriority;
+use super::{OddChipPriority, RakeSettings};
+
+pub trait Pot: std::fmt::Debug {
+    // Copy of the pot, used to take back actions
+    fn clone_box(& self) -> Box<dyn Pot + Send>;
 
-pub trait Pot {
     fn get_all_player_stacks_bets(& self) -> &BTreeMap<usize, (u64, u64)>;
 
     fn get_player_stack_bet(& self, pos: &usize) -> Result<&(u64, u64), std::string::String>;
 
     fn get_largest_bet_idxes(& self) -> Option<(usize, usize)>;
 
+    // Main pot followed by the side pots
+    fn get_pots(& self) -> &Vec<PartialPot>;
+
+    // Chips collected into the main pot and all side pots
+    fn get_total_pot(& self) -> u64;
+
+    // Chips taken by the house from the pot once the hand is settled
+    fn get_rake(& self) -> u64;
+
+    fn set_rake_settings(&mut self, settings: RakeSettings);
+
     fn are_all_bets_good(& self, action_idx: usize) -> bool {
         if let Some((lb, _)) = self.get_largest_bet_idxes() {
             lb == action_idx
@@ -26,21 +40,53 @@ pub trait Pot {
 
     fn post_before_deal(&mut self, bb_idx: &usize) -> Result<(), &str>;
 
+    // Same as post_before_deal, but with both blind positions given. A small blind of None means the small blind is dead
+    fn post_blinds_before_deal(&mut self, sb_idx: Option<&usize>, bb_idx: &usize) -> Result<(), &str>;
+
+    // Posts the blinds a player owes from sitting out (or from sitting down mid game). Must be called after the blinds are posted.
+    // The big blind is live and counts towards the player's bet, while the small blind is dead and goes straight into the pot
+    fn post_missed_blinds(&mut self, pos: &usize, post_bb: bool, post_dead_sb: bool) -> Result<(), &str>;
+
+    // Posts live straddles in the order given, each one double the previous blind. Must be called after the blinds are posted
+    fn post_straddles(&mut self, straddle_idxes: &Vec<usize>) -> Result<(), &str>;
+
+    // Only pays the antes, for games that are played with a bring-in instead of blinds
+    fn post_antes_before_deal(&mut self) -> Result<(), &str>;
+
+    // The button pays the ante for the whole table, as dead money, when it is played instead of blinds
+    fn post_button_ante(&mut self, btn_idx: &usize) -> Result<(), &str>;
+
+    // Posts the bring-in (the small blind amount) once the cards are dealt. Must be called after the antes are posted.
+    // Returns what the player brought in for
+    fn post_bring_in(&mut self, pos: &usize) -> Result<u64, &str>;
+
+    // Called at the start of every betting street. Fixed limit pots bet the big bet instead of the small bet on the later
+    // streets, every other pot ignores it
+    fn set_big_bet(&mut self, _is_big_bet: bool) {}
+
     fn bet_or_shove(&mut self, pos: &usize, bet: u64) -> Result<u64, std::string::String>;
 
     fn check_call(&mut self, pos: &usize) -> Result<u64, std::string::String>;
 
     fn fold(&mut self, pos: &usize) -> Result<(), std::string::String>;
 
+    // Folds a player that is not the one to act, such as a player leaving the table. They did not take a turn, so the
+    // action does not close on them
+    fn fold_out_of_turn(&mut self, pos: &usize) -> Result<(), std::string::String>;
+
     fn reset_pot(&mut self, players: &HashMap<usize, Player>, sb: u64, bb: u64, ante: u64, is_bomb: bool) -> Result<(), &str>;
     
     fn collect_bets(&mut self);
 
     fn distribute_pot(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<HashMap<usize, u64>, &str>;
 
+    // Splits every pot evenly across the runouts and distributes each share with the rankings of its runout.
+    // Returns the winnings of each runout
+    fn distribute_pot_runouts(&mut self, players: &mut HashMap<usize, Player>, runout_rankings: &Vec<Vec<HashMap<usize, Rank>>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<Vec<HashMap<usize, u64>>, &str>;
+
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct PartialPot {
     pub amount: u64,
     pub elegible_players: HashSet<usize>, // ids of all the players that are eligible to win the pot
@@ -1,46 +1,195 @@
-use super::Pot;
-use crate::poker::Player;
+use std::collections::{BTreeMap, HashMap};
 
-pub struct PotLimitPot<'a> {
-    pots: Vec<u64>,
-    elegible_players: Vec<Vec<&'a Player>>,
+use playing_cards::poker::Rank;
 
-    largest_bet_idx: usize, // should be set when a bet larger than largest_bet is made
-    largest_bet: u64,       // should be set when a bet larger than largest_bet is made
-    total_pot_size: u64,
+use super::{NoLimitPot, Pot, PartialPot, OddChipPriority, RakeSettings};
+use crate::poker::Player;
 
-    bet_diff: u64,          // used to determine the minimum amount to raise by
+// Same as no limit, except nobody can bet more than the size of the pot
+#[derive(Debug, PartialEq, Clone)]
+pub struct PotLimitPot {
+    pot: NoLimitPot,
 }
 
-impl<'a> PotLimitPot<'a> {
-    pub fn new() -> PotLimitPot<'a> {
+impl PotLimitPot {
+    pub fn new() -> PotLimitPot {
         PotLimitPot {
-            pots: Vec::new(),
-            elegible_players: Vec::new(),
-            largest_bet_idx: 0,
-            largest_bet: 0,
-            total_pot_size: 0,
-            bet_diff: 0,
+            pot: NoLimitPot::new(),
         }
     }
+
+    // Largest bet the player at pos can make: calling the largest bet and then raising by everything in the middle
+    pub fn get_max_bet(& self, pos: &usize) -> u64 {
+        let stacks_bets = self.pot.get_all_player_stacks_bets();
+        let largest_bet = stacks_bets.values().map(|(_, bet)| *bet).max().unwrap_or(0);
+        let bets: u64 = stacks_bets.values().map(|(_, bet)| *bet).sum();
+        let player_bet = stacks_bets.get(pos).map_or(0, |(_, bet)| *bet);
+
+        let pot_after_call = self.pot.get_total_pot() + bets + (largest_bet - player_bet);
+        largest_bet + pot_after_call
+    }
 }
 
-impl Pot for PotLimitPot<'_> {
+impl Pot for PotLimitPot {
+    fn clone_box(& self) -> Box<dyn Pot + Send> {
+        Box::new(self.clone())
+    }
+
+    fn get_all_player_stacks_bets(& self) -> &BTreeMap<usize, (u64, u64)> {
+        self.pot.get_all_player_stacks_bets()
+    }
+
+    fn get_player_stack_bet(& self, pos: &usize) -> Result<&(u64, u64), std::string::String> {
+        self.pot.get_player_stack_bet(pos)
+    }
+
+    fn get_largest_bet_idxes(& self) -> Option<(usize, usize)> {
+        self.pot.get_largest_bet_idxes()
+    }
+
+    fn get_pots(& self) -> &Vec<PartialPot> {
+        self.pot.get_pots()
+    }
+
+    fn get_total_pot(& self) -> u64 {
+        self.pot.get_total_pot()
+    }
+
+    fn get_rake(& self) -> u64 {
+        self.pot.get_rake()
+    }
+
+    fn set_rake_settings(&mut self, settings: RakeSettings) {
+        self.pot.set_rake_settings(settings)
+    }
+
+    fn is_bomb_pot(& self) -> bool {
+        self.pot.is_bomb_pot()
+    }
+
+    fn is_pot_contested(& self) -> bool {
+        self.pot.is_pot_contested()
+    }
+
+    fn post_before_deal(&mut self, bb_idx: &usize) -> Result<(), &str> {
+        self.pot.post_before_deal(bb_idx)
+    }
+
+    fn post_blinds_before_deal(&mut self, sb_idx: Option<&usize>, bb_idx: &usize) -> Result<(), &str> {
+        self.pot.post_blinds_before_deal(sb_idx, bb_idx)
+    }
+
+    fn post_missed_blinds(&mut self, pos: &usize, post_bb: bool, post_dead_sb: bool) -> Result<(), &str> {
+        self.pot.post_missed_blinds(pos, post_bb, post_dead_sb)
+    }
+
+    fn post_straddles(&mut self, straddle_idxes: &Vec<usize>) -> Result<(), &str> {
+        self.pot.post_straddles(straddle_idxes)
+    }
+
+    fn post_antes_before_deal(&mut self) -> Result<(), &str> {
+        self.pot.post_antes_before_deal()
+    }
+
+    fn post_button_ante(&mut self, btn_idx: &usize) -> Result<(), &str> {
+        self.pot.post_button_ante(btn_idx)
+    }
 
-    fn get_largest_bet_idx(& self) -> usize {
-        self.largest_bet_idx
+    fn post_bring_in(&mut self, pos: &usize) -> Result<u64, &str> {
+        self.pot.post_bring_in(pos)
     }
 
-    // This is the function that will work for No Limit
-    // TODO: Need to polymorphisize this. Turn struct Pot into a trait that is used in NoLimitPot, LimitPot, and PotLimitPot.
-    fn set_highest_bet(&mut self, action_idx: usize, player_stack: u64, new_bet: u64) -> Result<(), &str> {
-        if new_bet != player_stack && self.bet_diff + self.largest_bet > new_bet && new_bet <= self.total_pot_size + 2 * self.largest_bet {
-            return Err("Bet is not within valid bounds");
+    fn bet_or_shove(&mut self, pos: &usize, bet: u64) -> Result<u64, std::string::String> {
+        let max_bet = self.get_max_bet(pos);
+        if bet > max_bet {
+            // a player can only go all in if their stack is within the limit
+            let stack = self.pot.get_player_stack_bet(pos)?.0;
+            if stack > max_bet {
+                return Err(format!("Bet of {} is over the pot limit of {}", bet, max_bet));
+            }
         }
-        self.bet_diff = new_bet - self.largest_bet;
-        self.largest_bet = new_bet;
-        self.largest_bet_idx = action_idx;
-        self.total_pot_size += new_bet;
-        Ok(())
+        self.pot.bet_or_shove(pos, bet)
+    }
+
+    fn check_call(&mut self, pos: &usize) -> Result<u64, std::string::String> {
+        self.pot.check_call(pos)
+    }
+
+    fn fold(&mut self, pos: &usize) -> Result<(), std::string::String> {
+        self.pot.fold(pos)
+    }
+
+    fn fold_out_of_turn(&mut self, pos: &usize) -> Result<(), std::string::String> {
+        self.pot.fold_out_of_turn(pos)
+    }
+
+    fn reset_pot(&mut self, players: &HashMap<usize, Player>, sb: u64, bb: u64, ante: u64, is_bomb: bool) -> Result<(), &str> {
+        self.pot.reset_pot(players, sb, bb, ante, is_bomb)
+    }
+
+    fn collect_bets(&mut self) {
+        self.pot.collect_bets()
+    }
+
+    fn distribute_pot(&mut self, players: &mut HashMap<usize, Player>, hand_rankings: &Vec<HashMap<usize, Rank>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<HashMap<usize, u64>, &str> {
+        self.pot.distribute_pot(players, hand_rankings, btn_idx, odd_chip)
+    }
+
+    fn distribute_pot_runouts(&mut self, players: &mut HashMap<usize, Player>, runout_rankings: &Vec<Vec<HashMap<usize, Rank>>>, btn_idx: &usize, odd_chip: OddChipPriority) -> Result<Vec<HashMap<usize, u64>>, &str> {
+        self.pot.distribute_pot_runouts(players, runout_rankings, btn_idx, odd_chip)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_pot(stacks: &[u64]) -> PotLimitPot {
+        let players: HashMap<usize, Player> = stacks.iter()
+            .enumerate()
+            .map(|(pos, stack)| (pos, Player::new(pos, format!("Player {}", pos), *stack)))
+            .collect();
+
+        let mut pot = PotLimitPot::new();
+        assert_eq!(Ok(()), pot.reset_pot(&players, 1, 2, 0, false));
+        assert_eq!(Ok(()), pot.post_blinds_before_deal(Some(&1), &2));
+        pot
+    }
+
+    #[test]
+    fn max_raise_preflop() {
+        let mut pot = get_pot(&[200, 200, 200]);
+
+        // call 2, then raise the 5 in the middle
+        assert_eq!(7, pot.get_max_bet(&0));
+        assert!(pot.bet_or_shove(&0, 8).is_err());
+        assert_eq!(Ok(7), pot.bet_or_shove(&0, 7));
+
+        // small blind calls 6 more, making the pot 16, so can raise to 23
+        assert_eq!(23, pot.get_max_bet(&1));
+    }
+
+    #[test]
+    fn max_bet_after_collecting() {
+        let mut pot = get_pot(&[200, 200, 200]);
+        assert!(pot.check_call(&0).is_ok());
+        assert!(pot.check_call(&1).is_ok());
+        assert!(pot.check_call(&2).is_ok());
+        pot.collect_bets();
+
+        assert_eq!(6, pot.get_max_bet(&1));
+        assert!(pot.bet_or_shove(&1, 7).is_err());
+        assert_eq!(Ok(6), pot.bet_or_shove(&1, 6));
+        // call 6 and raise the 18 in the middle
+        assert_eq!(24, pot.get_max_bet(&2));
+    }
+
+    #[test]
+    fn short_stack_can_shove() {
+        let mut pot = get_pot(&[5, 200, 200]);
+        assert_eq!(Ok(5), pot.bet_or_shove(&0, 5));
+
+        let mut pot = get_pot(&[50, 200, 200]);
+        assert!(pot.bet_or_shove(&0, 50).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RakeSettings {
+    pub percent: f64,       // of every contested pot, rounded down
+    pub cap: Option<u64>,   // most the house takes from a single hand, None for no cap
+}
+
+impl RakeSettings {
+    pub fn new() -> RakeSettings {
+        RakeSettings {
+            percent: 0.0,
+            cap: None,
+        }
+    }
+
+    // Rake owed on the contested chips of a hand
+    pub fn get_rake(& self, contested: u64) -> u64 {
+        let rake = (contested as f64 * self.percent / 100.0).floor() as u64;
+        self.cap.map_or(rake, |cap| std::cmp::min(rake, cap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_rake_by_default() {
+        assert_eq!(0, RakeSettings::new().get_rake(1000));
+    }
+
+    #[test]
+    fn rake_rounds_down_and_is_capped() {
+        let settings = RakeSettings { percent: 5.0, cap: None };
+        assert_eq!(4, settings.get_rake(99));
+        assert_eq!(50, settings.get_rake(1000));
+
+        let settings = RakeSettings { percent: 5.0, cap: Some(30) };
+        assert_eq!(30, settings.get_rake(1000));
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct BlindPositions {
+    pub btn_idx: usize,         // can be an empty seat or a player that is not dealt in (dead button)
+    pub sb_idx: usize,          // seat of the small blind, only posted if is_sb_dead is false
+    pub bb_idx: usize,
+    pub is_sb_dead: bool,
+}
+
+// first active seat strictly left of pos
+pub fn next_active(active: &BTreeSet<usize>, pos: usize) -> usize {
+    *active.range(pos + 1..).chain(active.range(..=pos)).next().unwrap()
+}
+
+// first active seat strictly right of pos
+pub fn prev_active(active: &BTreeSet<usize>, pos: usize) -> usize {
+    *active.range(..pos).rev().chain(active.range(pos..).rev()).next().unwrap()
+}
+
+// Moves the button and blinds for the next hand using the dead button rule:
+//  - the big blind always moves to the next active player, so nobody can skip (or post twice) the big blind
+//  - the small blind goes to whoever had the big blind last hand. If they are gone, the small blind is dead
+//  - the button goes to whoever had the small blind last hand, even if that seat is now empty
+// Heads up, the button posts the small blind. active contains all seats that are dealt into the next hand.
+pub fn rotate_blinds(prev: Option<&BlindPositions>, active: &BTreeSet<usize>) -> Option<BlindPositions> {
+    if active.len() < 2 {
+        return None;
+    }
+
+    let prev = match prev {
+        Some(prev) => prev,
+        None => {
+            // first hand of the table, button starts at the first active seat
+            let btn_idx = *active.iter().next().unwrap();
+            let sb_idx = if active.len() == 2 { btn_idx } else { next_active(active, btn_idx) };
+            return Some(BlindPositions {
+                btn_idx: btn_idx,
+                sb_idx: sb_idx,
+                bb_idx: next_active(active, sb_idx),
+                is_sb_dead: false,
+            });
+        }
+    };
+
+    let bb_idx = next_active(active, prev.bb_idx);
+
+    if active.len() == 2 {
+        let btn_idx = next_active(active, bb_idx);
+        return Some(BlindPositions {
+            btn_idx: btn_idx,
+            sb_idx: btn_idx,
+            bb_idx: bb_idx,
+            is_sb_dead: false,
+        });
+    }
+
+    let sb_idx = prev.bb_idx;
+    let is_sb_dead = !active.contains(&sb_idx);
+
+    let was_heads_up = prev.btn_idx == prev.sb_idx;
+    let mut btn_idx = if was_heads_up { prev_active(active, sb_idx) } else { prev.sb_idx };
+    if btn_idx == bb_idx || btn_idx == sb_idx {
+        btn_idx = prev_active(active, sb_idx);
+    }
+
+    Some(BlindPositions {
+        btn_idx: btn_idx,
+        sb_idx: sb_idx,
+        bb_idx: bb_idx,
+        is_sb_dead: is_sb_dead,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    fn positions(btn_idx: usize, sb_idx: usize, bb_idx: usize, is_sb_dead: bool) -> BlindPositions {
+        BlindPositions {
+            btn_idx: btn_idx,
+            sb_idx: sb_idx,
+            bb_idx: bb_idx,
+            is_sb_dead: is_sb_dead,
+        }
+    }
+
+    #[test]
+    fn first_hand() {
+        let active = BTreeSet::from([1, 3, 4, 6]);
+        assert_eq!(Some(positions(1, 3, 4, false)), rotate_blinds(None, &active));
+    }
+
+    #[test]
+    fn first_hand_heads_up() {
+        let active = BTreeSet::from([2, 5]);
+        assert_eq!(Some(positions(2, 2, 5, false)), rotate_blinds(None, &active));
+    }
+
+    #[test]
+    fn not_enough_players() {
+        let active = BTreeSet::from([2]);
+        assert_eq!(None, rotate_blinds(None, &active));
+        assert_eq!(None, rotate_blinds(Some(&positions(0, 1, 2, false)), &active));
+    }
+
+    #[test]
+    fn full_orbit() {
+        let active = BTreeSet::from([0, 1, 2, 3]);
+        let mut blinds = rotate_blinds(None, &active).unwrap();
+        assert_eq!(positions(0, 1, 2, false), blinds);
+
+        let expected = [
+            positions(1, 2, 3, false),
+            positions(2, 3, 0, false),
+            positions(3, 0, 1, false),
+            positions(0, 1, 2, false),
+        ];
+        for e in expected {
+            blinds = rotate_blinds(Some(&blinds), &active).unwrap();
+            assert_eq!(e, blinds);
+        }
+    }
+
+    #[test]
+    fn dead_small_blind_when_big_blind_leaves() {
+        // player in seat 2 posted the big blind and left
+        let active = BTreeSet::from([0, 1, 3, 4]);
+        let blinds = rotate_blinds(Some(&positions(0, 1, 2, false)), &active).unwrap();
+
+        assert_eq!(positions(1, 2, 3, true), blinds);
+    }
+
+    #[test]
+    fn dead_button_when_small_blind_leaves() {
+        // player in seat 1 posted the small blind and left
+        let active = BTreeSet::from([0, 2, 3, 4]);
+        let blinds = rotate_blinds(Some(&positions(0, 1, 2, false)), &active).unwrap();
+
+        assert_eq!(positions(1, 2, 3, false), blinds);
+
+        // button then moves on to the player that posted the small blind
+        let blinds = rotate_blinds(Some(&blinds), &active).unwrap();
+        assert_eq!(positions(2, 3, 4, false), blinds);
+    }
+
+    #[test]
+    fn skip_away_players_for_big_blind() {
+        // seat 3 is sitting out, so the big blind jumps to seat 4
+        let active = BTreeSet::from([0, 1, 2, 4]);
+        let blinds = rotate_blinds(Some(&positions(0, 1, 2, false)), &active).unwrap();
+
+        assert_eq!(positions(1, 2, 4, false), blinds);
+    }
+
+    #[test]
+    fn heads_up_button_posts_small_blind() {
+        let active = BTreeSet::from([1, 4]);
+        let mut blinds = rotate_blinds(None, &active).unwrap();
+
+        for _ in 0..5 {
+            let next = rotate_blinds(Some(&blinds), &active).unwrap();
+            assert_eq!(next.btn_idx, next.sb_idx);
+            assert_eq!(blinds.btn_idx, next.bb_idx);
+            assert!(!next.is_sb_dead);
+            blinds = next;
+        }
+    }
+
+    #[test]
+    fn three_handed_to_heads_up() {
+        // small blind busts, big blind has to move onto the button
+        let active = BTreeSet::from([0, 2]);
+        let blinds = rotate_blinds(Some(&positions(0, 1, 2, false)), &active).unwrap();
+
+        assert_eq!(positions(2, 2, 0, false), blinds);
+    }
+
+    #[test]
+    fn heads_up_to_three_handed() {
+        let active = BTreeSet::from([0, 1, 2]);
+        let blinds = rotate_blinds(Some(&positions(0, 0, 2, false)), &active).unwrap();
+
+        assert_eq!(positions(1, 2, 0, false), blinds);
+    }
+
+    #[test]
+    fn seat_churn_over_many_hands() {
+        // seeded so a failure can be reproduced
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut active: BTreeSet<usize> = BTreeSet::from([0, 1, 2, 3, 4, 5]);
+        let mut prev: Option<BlindPositions> = None;
+
+        for _ in 0..1000 {
+            // players randomly leave, sit out, come back or sit down
+            for seat in 0..9 {
+                if rng.gen_range(0..10) == 0 {
+                    if active.contains(&seat) {
+                        active.remove(&seat);
+                    } else {
+                        active.insert(seat);
+                    }
+                }
+            }
+
+            let blinds = rotate_blinds(prev.as_ref(), &active);
+            if active.len() < 2 {
+                assert_eq!(None, blinds);
+                continue;
+            }
+            let blinds = blinds.unwrap();
+
+            assert!(active.contains(&blinds.bb_idx));
+            assert_ne!(blinds.btn_idx, blinds.bb_idx);
+            if !blinds.is_sb_dead {
+                assert!(active.contains(&blinds.sb_idx));
+                assert_ne!(blinds.sb_idx, blinds.bb_idx);
+            }
+
+            if active.len() == 2 {
+                assert_eq!(blinds.btn_idx, blinds.sb_idx);
+                assert!(!blinds.is_sb_dead);
+            } else if let Some(prev) = &prev {
+                // the big blind moves forward exactly one active seat
+                assert_eq!(next_active(&active, prev.bb_idx), blinds.bb_idx);
+                assert_eq!(prev.bb_idx, blinds.sb_idx);
+                assert_ne!(prev.bb_idx, blinds.bb_idx);
+            }
+
+            prev = Some(blinds);
+        }
+    }
+}
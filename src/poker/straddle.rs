@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::poker::{BlindPositions, next_active};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum StraddleType {
+    None,
+    UnderTheGun,    // first straddle is left of the big blind
+    Button,         // Mississippi straddle, action preflop starts with the small blind
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StraddleSettings {
+    pub straddle_type: StraddleType,
+    pub max_straddles: usize,   // cap on the straddle plus all re-straddles
+}
+
+impl StraddleSettings {
+    pub fn new() -> StraddleSettings {
+        StraddleSettings {
+            straddle_type: StraddleType::None,
+            max_straddles: 1,
+        }
+    }
+}
+
+// Seats that post a live straddle for the hand, in posting order. Each straddle is double the previous blind.
+// Re-straddles move clockwise from the first straddler and stop at the first player that does not want to straddle,
+// cannot cover the straddle, or is in one of the blinds. will_straddle contains the seats that opted in
+pub fn get_straddle_positions(settings: &StraddleSettings, blinds: &BlindPositions, active: &BTreeSet<usize>,
+        will_straddle: &BTreeSet<usize>, can_cover: impl Fn(&usize, u64) -> bool, bb_amt: u64) -> Vec<usize> {
+    let mut straddles = Vec::new();
+
+    // nobody can straddle heads up, the button is already in a blind
+    if active.len() < 3 {
+        return straddles;
+    }
+
+    let first = match settings.straddle_type {
+        StraddleType::None => {
+            return straddles;
+        },
+        StraddleType::UnderTheGun => next_active(active, blinds.bb_idx),
+        StraddleType::Button => blinds.btn_idx,
+    };
+
+    let mut pos = first;
+    let mut amount = bb_amt;
+    while straddles.len() < settings.max_straddles {
+        let is_blind = pos == blinds.bb_idx || (pos == blinds.sb_idx && !blinds.is_sb_dead);
+        amount *= 2;
+        if !active.contains(&pos) || is_blind || !will_straddle.contains(&pos) || !can_cover(&pos, amount) {
+            break;
+        }
+        straddles.push(pos);
+
+        pos = next_active(active, pos);
+        if pos == first {
+            break;
+        }
+    }
+
+    straddles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blinds(btn_idx: usize, sb_idx: usize, bb_idx: usize) -> BlindPositions {
+        BlindPositions {
+            btn_idx: btn_idx,
+            sb_idx: sb_idx,
+            bb_idx: bb_idx,
+            is_sb_dead: false,
+        }
+    }
+
+    fn settings(straddle_type: StraddleType, max_straddles: usize) -> StraddleSettings {
+        StraddleSettings {
+            straddle_type: straddle_type,
+            max_straddles: max_straddles,
+        }
+    }
+
+    #[test]
+    fn no_straddles() {
+        let active = BTreeSet::from([0, 1, 2, 3, 4]);
+        let all = active.clone();
+        assert!(get_straddle_positions(&settings(StraddleType::None, 3), &blinds(0, 1, 2), &active, &all, |_, _| true, 2).is_empty());
+    }
+
+    #[test]
+    fn under_the_gun_straddle() {
+        let active = BTreeSet::from([0, 1, 2, 3, 4]);
+        let all = active.clone();
+        assert_eq!(vec![3], get_straddle_positions(&settings(StraddleType::UnderTheGun, 1), &blinds(0, 1, 2), &active, &all, |_, _| true, 2));
+    }
+
+    #[test]
+    fn re_straddles_up_to_cap() {
+        let active = BTreeSet::from([0, 1, 2, 3, 4, 5]);
+        let all = active.clone();
+        assert_eq!(vec![3, 4, 5], get_straddle_positions(&settings(StraddleType::UnderTheGun, 3), &blinds(0, 1, 2), &active, &all, |_, _| true, 2));
+
+        // re-straddles never wrap into the blinds
+        assert_eq!(vec![3, 4, 5, 0], get_straddle_positions(&settings(StraddleType::UnderTheGun, 9), &blinds(0, 1, 2), &active, &all, |_, _| true, 2));
+    }
+
+    #[test]
+    fn re_straddle_chain_breaks() {
+        let active = BTreeSet::from([0, 1, 2, 3, 4, 5]);
+        let will_straddle = BTreeSet::from([3, 5]);
+        assert_eq!(vec![3], get_straddle_positions(&settings(StraddleType::UnderTheGun, 3), &blinds(0, 1, 2), &active, &will_straddle, |_, _| true, 2));
+
+        // seat 4 cannot cover the re-straddle of 8
+        let all = active.clone();
+        assert_eq!(vec![3], get_straddle_positions(&settings(StraddleType::UnderTheGun, 3), &blinds(0, 1, 2), &active, &all, |pos, amt| *pos != 4 || amt < 8, 2));
+    }
+
+    #[test]
+    fn button_straddle() {
+        let active = BTreeSet::from([0, 1, 2, 3]);
+        let all = active.clone();
+        // small blind cannot re-straddle the button
+        assert_eq!(vec![0], get_straddle_positions(&settings(StraddleType::Button, 3), &blinds(0, 1, 2), &active, &all, |_, _| true, 2));
+
+        // dead button cannot straddle
+        let active = BTreeSet::from([1, 2, 3]);
+        assert!(get_straddle_positions(&settings(StraddleType::Button, 1), &blinds(0, 1, 2), &active, &all, |_, _| true, 2).is_empty());
+    }
+
+    #[test]
+    fn heads_up_cannot_straddle() {
+        let active = BTreeSet::from([0, 1]);
+        let all = active.clone();
+        assert!(get_straddle_positions(&settings(StraddleType::UnderTheGun, 1), &blinds(0, 0, 1), &active, &all, |_, _| true, 2).is_empty());
+    }
+}
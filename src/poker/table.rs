@@ -1,166 +1,3843 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use uuid::Uuid;
+
+use playing_cards::core::Card;
+use playing_cards::poker::{HighRank, Rank};
 
 use crate::poker::Player;
-use crate::poker::GameVariation;
-use crate::poker::games::DefaultGame;
+use crate::poker::{BlindPositions, rotate_blinds};
+use crate::poker::{ActionClock, ClockSettings};
+use crate::poker::{StraddleSettings, get_straddle_positions};
+use crate::poker::{BombPotSettings, get_bomb_pot_votes_needed};
+use crate::poker::TableConfig;
+use crate::poker::{Ledger, LedgerEntryType};
+use crate::poker::{ChipAuditor, AuditReport, LoggedAction};
+use crate::poker::{HandHistory, SeatHistory, DrawHistory, SidePotHistory};
+use crate::poker::{GameVariation, Street};
 
-use crate::poker::pots::{Pot, NoLimitPot};
+use crate::poker::pots::Pot;
 
 use crate::poker::{GameActionPayload, GameActionResponse};
-use crate::poker::game_actions::{GameAction, PotAction, requests::{BetAction, DrawAction}};
+use crate::poker::game_actions::{GameAction, PotAction, GameResponse, Broadcast, Multicast, SingleResponse};
+use crate::poker::game_actions::requests::{BetAction, DrawAction, SitDownAction, ReserveSeatAction, WaitingListAction, SitInAction, PlayerSettingsAction, BombPotVoteAction, RunItAction, ShowCardsAction, ShowdownAction, AddChipsAction};
+use crate::poker::game_actions::responses::{PersonalFoldResponse, PublicFoldResponse,
+                    PersonalCheckCallResponse, PublicCheckCallResponse,
+                    PersonalBetRaiseResponse, PublicBetRaiseResponse,
+                    PersonalDrawResponse, PublicDrawResponse,
+                    PublicSitDownResponse, PersonalSitDownResponse,
+                    PublicStandUpResponse, PersonalStandUpResponse,
+                    PublicReserveSeatResponse, PersonalReserveSeatResponse,
+                    PersonalWaitingListResponse,
+                    PublicAwayResponse, PersonalAwayResponse, ActionClockResponse,
+                    PersonalSettingsResponse, PublicBombPotVoteResponse, PersonalBombPotVoteResponse,
+                    RunItOfferResponse, PublicRunItResponse, PersonalRunItResponse,
+                    RunItDecisionResponse, RunoutResultResponse,
+                    PublicShowHandResponse, PublicMuckHandResponse, PersonalShowHandResponse,
+                    PersonalTableConfigResponse, PersonalAddChipsResponse, LedgerResponse,
+                    PersonalUndoResponse,
+                    StartingHandResponse, GameState, HandResultResponse, StatusCode};
 
 use crate::poker::ActionType;
 
 use serde_json::Value;
 
+// Everything a player action can change, taken right before the action so it can be undone
+struct UndoEntry {
+    pot: Box<dyn Pot + Send>,
+    action_idx: usize,
+    in_hand: BTreeSet<usize>,
+    away: BTreeSet<usize>,          // timing out marks the player as away, so that is taken back too
+    last_aggressor: Option<usize>,
+    run_it_votes: Option<HashMap<usize, usize>>,
+    action_log_len: usize,
+}
+
+// Everything starting a hand changes before the cards are dealt, taken so a hand that fails to start can be taken back
+struct HandStartEntry {
+    players: HashMap<usize, Player>,
+    blind_positions: Option<BlindPositions>,
+    straddle_positions: Vec<usize>,
+    big_blind_idx: usize,
+    btn_idx: usize,
+    pot: Box<dyn Pot + Send>,
+    auditor: ChipAuditor,
+    action_log: Vec<LoggedAction>,
+}
+
+// A settled hand waiting on players to decide if they show or muck their hand
+struct Showdown {
+    order: VecDeque<usize>,         // players that still have to show or muck, in showdown order
+    must_show: HashSet<usize>,      // players that can not muck their hand
+    winnings: HashMap<usize, u64>,  // what everyone won, sent out once the showdown is over
+}
+
+const WAIT_FOR_NEXT_HAND: Duration = Duration::from_secs(1);
+const RESERVE_SEAT_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub struct Table {
     players: HashMap<usize, Player>,        // list of all players corresponding to their table position
+    seats: HashMap<Uuid, usize>,            // websocket id of a player to their table position
+    reserved_seats: HashMap<usize, (Uuid, Instant)>,    // seats held for a player until the reservation expires
+    waiting_list: VecDeque<(Uuid, String, u64)>,        // players waiting for a seat (id, name, buy-in)
+    pending_stand_ups: HashSet<usize>,                  // players that leave the table once the current hand is over
     game: Box<dyn GameVariation + Send>,    // what game the table is playing
-    
+
+    is_hand_in_progress: bool,
+    street_idx: usize,                      // index into the streets of the game variation
     action_idx: usize,                      // action_idx will always point to a Player that is in the hand
+    last_to_draw_idx: usize,                // last player to act during a draw street
+    run_it_votes: Option<HashMap<usize, usize>>,    // times each player agreed to run it, Some while waiting on players to decide
+    run_it_times: Option<usize>,                    // number of runouts once everyone decided
+    last_aggressor: Option<usize>,          // last player to bet or raise during the current betting street
+    fold_winner: Option<usize>,             // player that won the last hand uncontested, can choose to show their cards
+    showdown: Option<Showdown>,             // Some while waiting on players to show or muck after the hand is settled
+    shown_hands: HashMap<usize, Vec<Card>>, // cards revealed to the table during the last hand
+
+    clock: ActionClock,                     // times the player in action
+    clock_settings: ClockSettings,
+    hand_number: u64,                       // number of hands dealt so far, used for time bank refills
 
+    blind_positions: Option<BlindPositions>,   // button and blinds of the last hand dealt
+    straddle_positions: Vec<usize>,             // straddles of the last hand dealt, in posting order
     big_blind_idx: usize,
     btn_idx: usize,
     pot: Box<dyn Pot + Send>,               // handles all bets from players, checks for when all bets are good, and distributes pot based upon rankings
 
+    sb_amt: u64,
+    bb_amt: u64,
+    ante_amt: u64,
+    is_button_ante: bool,
+    straddle_settings: StraddleSettings,
+
     start_next_hand: bool,                  // is the table running (start/stop next hand)
     is_paused: bool,                        // is the current hand paused
     is_next_hand_bomb: bool,
+    bomb_pot_settings: BombPotSettings,
+    bomb_pot_votes: HashSet<usize>,         // positions of the players that voted for a bomb pot
+    hands_since_bomb_pot: u64,              // normal hands dealt since the last bomb pot
+
+    host_id: Option<Uuid>,                  // player running the table, the first one to start the game
+    config: TableConfig,
+    pending_config: Option<TableConfig>,    // config the host set during a hand, applied before the next one is dealt
+    ledger: Ledger,                         // every buy-in, rebuy, top-up and cash out
+    auditor: ChipAuditor,                   // checks that no chips get created or destroyed during a hand
+    action_log: Vec<LoggedAction>,          // everything that happened in the current hand
+    is_halted: bool,                        // chips were not conserved, the table can not continue
+    undo_log: Vec<UndoEntry>,               // state before every action of the current street, oldest first
+    hand_history: Option<HandHistory>,      // history of the current (or last) hand
+    hand_history_dir: Option<PathBuf>,      // where hand histories are written to, None to not write them
 }
 
 impl Table {
 
     pub fn new() -> Table {
+        let config = TableConfig::new();
         Table {
             players: HashMap::new(),
-            game: Box::new(DefaultGame::new()),
+            seats: HashMap::new(),
+            reserved_seats: HashMap::new(),
+            waiting_list: VecDeque::new(),
+            pending_stand_ups: HashSet::new(),
+            game: config.create_game(),
+            is_hand_in_progress: false,
+            street_idx: 0,
             action_idx: 0,
+            last_to_draw_idx: 0,
+            run_it_votes: None,
+            run_it_times: None,
+            last_aggressor: None,
+            fold_winner: None,
+            showdown: None,
+            shown_hands: HashMap::new(),
+            clock: ActionClock::new(),
+            clock_settings: ClockSettings::new(),
+            hand_number: 0,
+            blind_positions: None,
+            straddle_positions: Vec::new(),
             big_blind_idx: 0,
             btn_idx: 0,
-            pot: Box::new(NoLimitPot::new()),
+            pot: config.create_pot(),
+            sb_amt: 1,
+            bb_amt: 2,
+            ante_amt: 0,
+            is_button_ante: false,
+            straddle_settings: StraddleSettings::new(),
             start_next_hand: false,
             is_paused: false,
             is_next_hand_bomb: false,
+            bomb_pot_settings: BombPotSettings::new(),
+            bomb_pot_votes: HashSet::new(),
+            hands_since_bomb_pot: 0,
+
+            host_id: None,
+            config: config,
+            pending_config: None,
+            ledger: Ledger::new(),
+            auditor: ChipAuditor::new(),
+            action_log: Vec::new(),
+            is_halted: false,
+            undo_log: Vec::new(),
+            hand_history: None,
+            hand_history_dir: None,
+        }
+    }
+
+    pub fn with_config(config: TableConfig) -> Result<Table, std::string::String> {
+        config.validate()?;
+        let mut table = Table::new();
+        table.apply_config(config);
+        Ok(table)
+    }
+
+    pub fn set_hand_history_dir(&mut self, dir: PathBuf) {
+        self.hand_history_dir = Some(dir);
+    }
+
+    fn apply_config(&mut self, config: TableConfig) {
+        self.game = config.create_game();
+        self.pot = config.create_pot();
+        self.sb_amt = config.sb_amt;
+        self.bb_amt = config.bb_amt;
+        self.ante_amt = config.ante_amt;
+        self.is_button_ante = config.is_button_ante;
+        self.set_clock_settings(config.clock_settings.clone());
+        self.set_straddle_settings(config.straddle_settings.clone());
+        self.set_bomb_pot_settings(config.bomb_pot_settings.clone());
+        self.config = config;
+    }
+
+    // The first player to start the game runs the table. Returns whether the player is the host
+    fn claim_host(&mut self, id: Uuid) -> bool {
+        *self.host_id.get_or_insert(id) == id
+    }
+
+    fn is_host(& self, id: &Uuid) -> bool {
+        self.host_id.as_ref() == Some(id)
+    }
+
+    fn update_config(&mut self, id: Uuid, config: TableConfig) -> Vec<GameResponse> {
+        if !self.is_host(&id) {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::TableConfigResponse(PersonalTableConfigResponse::new(id, StatusCode::NotHost, None)))];
+        }
+        let validation = config.validate().and_then(|_| {
+            match self.players.keys().find(|pos| **pos >= config.seat_count) {
+                Some(pos) => Err(format!("Seat {} is taken and would be removed", pos)),
+                None => Ok(()),
+            }
+        });
+        if let Err(err) = validation {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::TableConfigResponse(PersonalTableConfigResponse::new(id, StatusCode::InvalidTableConfig, Some(err))))];
+        }
+
+        let responses = vec![
+            GameResponse::SingleResponse(id, SingleResponse::TableConfigResponse(PersonalTableConfigResponse::new(id, StatusCode::OK, None))),
+            GameResponse::Broadcast(Broadcast::TableConfig(config.clone())),
+        ];
+        if self.is_hand_in_progress || self.showdown.is_some() {
+            self.pending_config = Some(config);
+        } else {
+            self.pending_config = None;
+            self.apply_config(config);
         }
+        responses
     }
-    
+
+    fn get_position(& self, id: &Uuid) -> Option<usize> {
+        self.seats.get(id).cloned()
+    }
+
+    fn get_player_id(& self, pos: &usize) -> Option<Uuid> {
+        self.seats.iter().find(|(_, p)| *p == pos).map(|(id, _)| *id)
+    }
+
+    fn get_street(& self) -> Option<Street> {
+        self.game.get_streets().get(self.street_idx).cloned()
+    }
+
+    // All seated positions in clockwise order starting from the seat left of pos
+    fn get_positions_after(& self, pos: usize) -> Vec<usize> {
+        let positions: BTreeMap<usize, ()> = self.players.keys().map(|p| (*p, ())).collect();
+        positions.range(pos + 1..).chain(positions.range(..=pos)).map(|(p, _)| *p).collect()
+    }
+
+    // A player can act if they are still in the hand and have chips behind
+    fn can_player_act(& self, pos: &usize) -> bool {
+        self.players.get(pos).map_or(false, |p| p.is_in_hand) &&
+            self.pot.get_player_stack_bet(pos).map_or(false, |(stack, bet)| stack > bet)
+    }
+
+    fn next_to_act(& self, pos: usize) -> Option<usize> {
+        self.get_positions_after(pos).into_iter().find(|p| self.can_player_act(p))
+    }
+
+    fn next_in_hand(& self, pos: usize) -> Option<usize> {
+        self.get_positions_after(pos).into_iter().find(|p| self.players.get(p).map_or(false, |p| p.is_in_hand))
+    }
+
     // TODO: How to manage check/call bet/raise and fold?
     //  Thru Player? Table will handle incoming data and actions
     fn check_all_bets_good(& self) -> bool {
         self.pot.are_all_bets_good(self.action_idx)
     }
 
-    fn have_all_players_folded(& self) -> bool {
-        self.players.iter().map(|x| {
-            x.1.is_away || !x.1.is_in_hand
-        }).reduce(|x, y| { x && y }).unwrap()
+    // The betting round is closed once action reaches (or skips over) the player that made the largest bet
+    fn has_betting_closed(& self, from: usize, to: usize) -> bool {
+        for pos in self.get_positions_after(from) {
+            if self.pot.are_all_bets_good(pos) {
+                return true;
+            }
+            if pos == to {
+                break;
+            }
+        }
+        false
     }
 
-    // TODO: Probably should figure out what types are going to be sent thru the channels here
-    pub async fn run_loop(table: Arc<Mutex<Table>>, rx: &mut UnboundedReceiver<GameActionPayload>, res_tx: UnboundedSender<GameActionResponse>) {
-        let mut game_loop_tx: Option<UnboundedSender<GameAction>> = None;
-        while let Some(msg) = rx.recv().await {
-            if let Some(tx) = game_loop_tx.clone() {
-                match msg.action_type {
-                    ActionType::CheckCall => {
-                        // talk to game loop, forward message
+    fn get_largest_bet(& self) -> u64 {
+        self.pot.get_all_player_stacks_bets().values().map(|(_, bet)| *bet).max().unwrap_or(0)
+    }
 
-                        tx.send(GameAction::Pot(msg.id, PotAction::CheckCall));
-                        continue;
-                    },
-                    ActionType::BetRaise => {
-                        // talk to game loop, forward message
-                        // turn Value into a GameAction then forward
-                        let action: Result<BetAction, _> = serde_json::from_value(Value::Object(msg.data));
-                        if let Ok(action) = action {
-                            tx.send(GameAction::Pot(msg.id, PotAction::BetRaise(action)));
-                        } else {
-                            todo!();
-                        }
-                        continue;
-                    },
-                    ActionType::Fold => {
-                        // talk to game loop, forward message
+    // Checking is legal if the player has already matched the largest bet
+    fn can_player_check(& self, pos: &usize) -> bool {
+        self.pot.get_player_stack_bet(pos).map_or(false, |(_, bet)| *bet >= self.get_largest_bet())
+    }
 
-                        tx.send(GameAction::Pot(msg.id, PotAction::Fold));
+    // Betting can be skipped if nobody (or only a single player that has nothing to call) has chips behind
+    fn is_betting_needed(& self) -> bool {
+        let stacks_bets = self.pot.get_all_player_stacks_bets();
+        let largest_bet = self.get_largest_bet();
+        let can_act: Vec<&usize> = stacks_bets.keys().filter(|pos| self.can_player_act(pos)).collect();
 
-                        continue;
-                    },
-                    ActionType::Draw => {
-                        // talk to game loop, forward message
-                        // turn Value into a GameAction then forward
+        can_act.len() > 1 || can_act.iter().any(|pos| stacks_bets[pos].1 < largest_bet)
+    }
 
-                        let action: Result<DrawAction, _> = serde_json::from_value(Value::Object(msg.data));
-                        if let Ok(action) = action {
-                            tx.send(GameAction::Draw(msg.id, action));
-                        } else {
-                            todo!();
-                        }
-                        continue;
-                    },
-                    _ => {}
-                }
+    fn get_players_in_hand(& self) -> Vec<usize> {
+        let mut in_hand: Vec<usize> = self.players.iter()
+            .filter(|(_, p)| p.is_in_hand)
+            .map(|(pos, _)| *pos)
+            .collect();
+        in_hand.sort();
+        in_hand
+    }
+
+    fn have_all_other_players_folded(& self) -> bool {
+        self.players.iter().filter(|x| {
+            x.1.is_in_hand
+        }).count() <= 1
+    }
+
+    // Face up cards of everyone still in the hand, in stud games
+    fn get_up_cards(& self) -> BTreeMap<usize, Vec<Card>> {
+        self.get_players_in_hand().into_iter()
+            .filter(|pos| !self.players[pos].get_up_cards().is_empty())
+            .map(|pos| (pos, self.players[&pos].get_up_cards().clone()))
+            .collect()
+    }
+
+    fn get_game_state(& self) -> GameState {
+        GameState::new(
+            self.is_hand_in_progress,
+            self.is_hand_in_progress && self.pot.is_bomb_pot(),
+            if self.is_hand_in_progress { self.get_street() } else { None },
+            self.action_idx,
+            self.blind_positions.clone(),
+            self.straddle_positions.clone(),
+            self.pot.get_all_player_stacks_bets().clone(),
+            self.get_players_in_hand(),
+            self.game.get_board(),
+            self.game.get_second_board(),
+            self.get_up_cards(),
+        )
+    }
+
+    pub fn set_clock_settings(&mut self, settings: ClockSettings) {
+        self.clock_settings = settings;
+    }
+
+    fn get_turn(& self) -> Option<(usize, usize)> {
+        if self.showdown.is_some() {
+            // showing down comes after the last street
+            Some((self.game.get_streets().len(), self.action_idx))
+        } else if self.is_hand_in_progress {
+            Some((self.street_idx, self.action_idx))
+        } else {
+            None
+        }
+    }
+
+    // (action time, time bank) of the player at pos. Players that are away get no time to act
+    fn get_time_limits(& self, pos: &usize) -> (Duration, Duration) {
+        // deciding how many times to run it is not a turn, so there is no time bank for it
+        if self.run_it_votes.is_some() {
+            return (self.clock_settings.get_action_time(), Duration::ZERO);
+        }
+
+        // neither is showing or mucking, but away players are not waited on for it
+        let time_bank = |player: &Player| if self.showdown.is_some() { Duration::ZERO } else { player.time_bank };
+        match self.players.get(pos) {
+            Some(player) if !player.is_away => (self.clock_settings.get_action_time(), time_bank(player)),
+            _ => (Duration::ZERO, Duration::ZERO),
+        }
+    }
+
+    fn get_action_clock_response(& self, now: Instant) -> GameResponse {
+        let (action_time, time_bank) = self.get_time_limits(&self.action_idx);
+        let (action_time_left, time_bank_left) = self.clock.get_time_left(now, action_time, time_bank);
+        GameResponse::Broadcast(Broadcast::ActionClock(ActionClockResponse::new(
+            self.action_idx,
+            action_time_left.as_millis() as u64,
+            time_bank_left.as_millis() as u64,
+            self.clock.is_paused(),
+        )))
+    }
+
+    // Restarts the clock whenever action moves on. Any time the last player took past their action time comes out of their time bank
+    fn update_action_clock(&mut self, prev_turn: Option<(usize, usize)>, now: Instant) -> Vec<GameResponse> {
+        let turn = self.get_turn();
+        if turn == prev_turn && (turn.is_none() || self.clock.is_running()) {
+            return Vec::new();
+        }
+
+        let elapsed = self.clock.stop(now);
+        if let Some((_, prev_pos)) = prev_turn {
+            let used = elapsed.saturating_sub(self.clock_settings.get_action_time());
+            if let Some(player) = self.players.get_mut(&prev_pos) {
+                player.time_bank = player.time_bank.saturating_sub(used);
             }
-            match msg.action_type {
-                ActionType::StartGame => {
-                    // TODO: check authorization jwt by looking up uuid
-                    if game_loop_tx.clone().map_or_else(|| true, |tx| tx.is_closed()) {
-                        let t = table.clone();
-                        let mut table = table.lock().unwrap();
-                        table.start_next_hand = true;
+        }
 
-                        let (tx, rx) = unbounded_channel::<GameAction>();
-                        game_loop_tx = Some(tx);
+        if turn.is_none() {
+            return Vec::new();
+        }
+        self.clock.start(now);
+        vec![self.get_action_clock_response(now)]
+    }
 
-                        let mes_res_tx = res_tx.clone();
-                        tokio::spawn(async move {
-                            Table::game_loop(t, rx, mes_res_tx).await;
-                        });
-                    }
+    // Time until the clock of the player in action needs to be checked again. None if the clock is stopped or frozen
+    fn get_clock_timeout(& self, now: Instant) -> Option<Duration> {
+        if !self.clock.is_running() || self.clock.is_paused() {
+            return None;
+        }
 
-                    // let res = GameActionResponse{
+        let (action_time, time_bank) = self.get_time_limits(&self.action_idx);
+        let elapsed = self.clock.elapsed(now);
+        if elapsed < action_time {
+            Some(action_time - elapsed)
+        } else {
+            Some((action_time + time_bank).saturating_sub(elapsed))
+        }
+    }
 
-                    // }
-                    // res_tx.send(message);
-                },
-                ActionType::PauseGame => {
-                    // check authorization jwt by looking up uuid
+    // Lets everyone know once the player in action starts using their time bank, and acts for them once it runs out
+    fn check_action_clock(&mut self, now: Instant) -> Vec<GameResponse> {
+        let is_deciding = self.is_hand_in_progress || self.showdown.is_some();
+        if !is_deciding || !self.clock.is_running() || self.clock.is_paused() {
+            return Vec::new();
+        }
 
-                    let mut table = table.lock().unwrap();
-                    table.is_paused = true;
-                },
-                ActionType::ResumeGame => {
-                    // check authorization jwt by looking up uuid
+        let pos = self.action_idx;
+        let (action_time, time_bank) = self.get_time_limits(&pos);
+        let elapsed = self.clock.elapsed(now);
+        if elapsed < action_time {
+            return Vec::new();
+        }
+        if elapsed < action_time + time_bank {
+            if self.clock.is_using_time_bank() {
+                return Vec::new();
+            }
+            self.clock.use_time_bank();
+            return vec![self.get_action_clock_response(now)];
+        }
 
-                    let mut table = table.lock().unwrap();
-                    table.is_paused = false;
-                },
-                ActionType::StopGame => {
-                    // check authorization jwt by looking up uuid
+        if self.run_it_votes.is_some() {
+            let prev_turn = self.get_turn();
+            let mut responses = self.decide_run_it();
+            responses.extend(self.update_action_clock(prev_turn, now));
+            responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+            return responses;
+        }
 
-                    let mut table = table.lock().unwrap();
-                    table.start_next_hand = false;
-                },
-                _ => {
-                    // This means we got a GameAction and no game loop is running
-                    // TODO: send some response idk
+        if self.showdown.is_some() {
+            // players that did not decide fall back on their auto muck setting
+            let prev_turn = self.get_turn();
+            let show = self.players.get(&pos).map_or(false, |player| !player.auto_muck);
+            let mut responses = self.reveal_hand(show);
+            responses.extend(self.continue_showdown());
+            responses.extend(self.update_action_clock(prev_turn, now));
+            responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+            return responses;
+        }
+
+        self.time_out_player(pos, now)
+    }
+
+    // Checks for the player if they can, otherwise folds them (or stands pat on a draw). The player is then marked as away
+    fn time_out_player(&mut self, pos: usize, now: Instant) -> Vec<GameResponse> {
+        let id = match self.get_player_id(&pos) {
+            Some(id) => id,
+            None => {
+                return Vec::new();
+            },
+        };
+        let prev_turn = self.get_turn();
+        // taken before the player is marked as away, so undoing the time out brings them back
+        let undo_entry = self.get_undo_entry();
+        let undo_len = self.undo_log.len();
+
+        let mut responses = Vec::new();
+        let player = self.players.get_mut(&pos).unwrap();
+        if !player.is_away {
+            player.is_away = true;
+            responses.push(GameResponse::SingleResponse(id, SingleResponse::AwayResponse(PersonalAwayResponse::new(id, StatusCode::OK, true))));
+            responses.push(GameResponse::Multicast(id, Multicast::AwayResponse(PublicAwayResponse::new(pos, true))));
+        }
+
+        responses.extend(match self.get_street() {
+            Some(Street::Draw) => self.handle_draw_action(id, DrawAction { cards_to_discard: Vec::new() }),
+            _ => {
+                let action = if self.can_player_check(&pos) { PotAction::CheckCall } else { PotAction::Fold };
+                self.handle_pot_action(id, action)
+            },
+        });
+        if self.undo_log.len() > undo_len {
+            *self.undo_log.last_mut().unwrap() = undo_entry;
+        }
+        responses.extend(self.update_action_clock(prev_turn, now));
+        responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+        responses
+    }
+
+    fn pause(&mut self, now: Instant) -> Vec<GameResponse> {
+        self.is_paused = true;
+        self.clock.pause(now);
+        if self.clock.is_running() {
+            vec![self.get_action_clock_response(now)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn resume(&mut self, now: Instant) -> Vec<GameResponse> {
+        if self.is_halted {
+            return Vec::new();
+        }
+        self.is_paused = false;
+        self.clock.resume(now);
+        if self.clock.is_running() {
+            vec![self.get_action_clock_response(now)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub fn set_straddle_settings(&mut self, settings: StraddleSettings) {
+        self.straddle_settings = settings;
+    }
+
+    fn update_settings(&mut self, id: Uuid, action: PlayerSettingsAction) -> Vec<GameResponse> {
+        let pos = match self.get_position(&id) {
+            Some(pos) => pos,
+            None => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::SettingsResponse(PersonalSettingsResponse::new(id, StatusCode::NotSeated)))];
+            },
+        };
+
+        let player = self.players.get_mut(&pos).unwrap();
+        if let Some(will_straddle) = action.will_straddle {
+            player.will_straddle = will_straddle;
+        }
+        if let Some(play_in_bomb_pots) = action.play_in_bomb_pots {
+            player.play_in_bomb_pots = play_in_bomb_pots;
+        }
+        if let Some(auto_muck) = action.auto_muck {
+            player.auto_muck = auto_muck;
+        }
+        if let Some(auto_rebuy) = action.auto_rebuy {
+            player.auto_rebuy = auto_rebuy;
+        }
+
+        vec![GameResponse::SingleResponse(id, SingleResponse::SettingsResponse(PersonalSettingsResponse::new(id, StatusCode::OK)))]
+    }
+
+    fn sit_in(&mut self, id: Uuid, action: SitInAction) -> Vec<GameResponse> {
+        if let Some(pos) = self.get_position(&id) {
+            self.players.get_mut(&pos).unwrap().wait_for_bb = action.wait_for_bb;
+        }
+        self.set_away(id, false)
+    }
+
+    // Away players are not dealt into the next hand
+    fn set_away(&mut self, id: Uuid, is_away: bool) -> Vec<GameResponse> {
+        let pos = match self.get_position(&id) {
+            Some(pos) => pos,
+            None => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::AwayResponse(PersonalAwayResponse::new(id, StatusCode::NotSeated, is_away)))];
+            },
+        };
+        self.players.get_mut(&pos).unwrap().is_away = is_away;
+
+        vec![
+            GameResponse::SingleResponse(id, SingleResponse::AwayResponse(PersonalAwayResponse::new(id, StatusCode::OK, is_away))),
+            GameResponse::Multicast(id, Multicast::AwayResponse(PublicAwayResponse::new(pos, is_away))),
+        ]
+    }
+
+    // Status sent back to a player that tried to act when they could not
+    fn get_rejected_status(& self) -> StatusCode {
+        if self.is_paused {
+            StatusCode::GamePaused
+        } else {
+            StatusCode::NotYourTurn
+        }
+    }
+
+    // Seats that get dealt into the next hand. Players waiting for the big blind only get dealt in once it reaches them
+    fn get_active_positions(&mut self) -> BTreeSet<usize> {
+        let has_blinds = self.has_blinds();
+        for (_, p) in &mut self.players {
+            p.is_in_hand = !p.is_away && p.stack > 0;
+            // nothing is owed in games without blinds, so everyone gets dealt in right away
+            if !has_blinds {
+                p.missed_sb = false;
+                p.missed_bb = false;
+            }
+        }
+
+        let next_bb = self.blind_positions.as_ref()
+            .and_then(|prev| self.get_positions_after(prev.bb_idx).into_iter().find(|pos| self.players[pos].is_in_hand));
+
+        for (pos, p) in &mut self.players {
+            if p.is_in_hand && p.owes_blinds() && p.wait_for_bb && Some(*pos) != next_bb {
+                p.is_in_hand = false;
+            }
+        }
+
+        self.get_players_in_hand().into_iter().collect()
+    }
+
+    // Blinds are posted unless the game has a bring-in or the table plays with a button ante
+    fn has_blinds(& self) -> bool {
+        self.game.has_blinds() && !self.is_button_ante
+    }
+
+    // Players that are skipped by the big blind owe both blinds, and players sitting in a dead small blind owe the small blind
+    fn mark_missed_blinds(&mut self, prev: Option<&BlindPositions>, blinds: &BlindPositions, is_bomb: bool) {
+        if let Some(prev) = prev {
+            for pos in self.get_positions_after(prev.bb_idx) {
+                if pos == blinds.bb_idx {
+                    break;
+                }
+                let player = self.players.get_mut(&pos).unwrap();
+                if !player.is_in_hand {
+                    player.missed_sb = true;
+                    player.missed_bb = true;
                 }
             }
         }
+
+        if blinds.is_sb_dead {
+            if let Some(player) = self.players.get_mut(&blinds.sb_idx) {
+                player.missed_sb = true;
+            }
+        }
+
+        // posting the big blind pays off everything that is owed. Nobody posts a big blind in a bomb pot though
+        if is_bomb {
+            return;
+        }
+        if let Some(player) = self.players.get_mut(&blinds.bb_idx) {
+            player.missed_sb = false;
+            player.missed_bb = false;
+        }
     }
 
-    pub async fn game_loop(table: Arc<Mutex<Table>>, main_loop_rx: UnboundedReceiver<GameAction>, res_tx: UnboundedSender<GameActionResponse>) {
-        loop {
-            {
-                let table = table.lock().unwrap();
+    // A bomb pot is dealt if one was voted for or is scheduled, as long as at least two players want to play in it
+    fn is_bomb_pot_next(& self, active: &BTreeSet<usize>) -> bool {
+        let is_bomb = self.is_next_hand_bomb || self.bomb_pot_settings.is_scheduled(self.hands_since_bomb_pot, active.len());
+        is_bomb && self.has_blinds() && active.iter().filter(|pos| self.players[pos].play_in_bomb_pots).count() >= 2
+    }
 
-                if !table.start_next_hand {
-                    break
+    pub fn set_bomb_pot_settings(&mut self, settings: BombPotSettings) {
+        self.bomb_pot_settings = settings;
+    }
+
+    fn get_bomb_pot_vote_response(& self) -> GameResponse {
+        let votes_needed = get_bomb_pot_votes_needed(self.players.values().filter(|p| !p.is_away).count());
+        GameResponse::Broadcast(Broadcast::BombPotVote(PublicBombPotVoteResponse::new(self.bomb_pot_votes.len(), votes_needed, self.is_next_hand_bomb)))
+    }
+
+    fn vote_bomb_pot(&mut self, id: Uuid, action: BombPotVoteAction) -> Vec<GameResponse> {
+        let status = match self.get_position(&id) {
+            None => StatusCode::NotSeated,
+            Some(_) if !self.bomb_pot_settings.allow_votes => StatusCode::VotingDisabled,
+            Some(_) => StatusCode::OK,
+        };
+
+        if status != StatusCode::OK {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::BombPotVoteResponse(PersonalBombPotVoteResponse::new(id, status)))];
+        }
+
+        let pos = self.get_position(&id).unwrap();
+        if action.vote {
+            self.bomb_pot_votes.insert(pos);
+        } else {
+            self.bomb_pot_votes.remove(&pos);
+        }
+
+        let votes_needed = get_bomb_pot_votes_needed(self.players.values().filter(|p| !p.is_away).count());
+        if self.bomb_pot_votes.len() >= votes_needed {
+            self.is_next_hand_bomb = true;
+        }
+
+        vec![
+            GameResponse::SingleResponse(id, SingleResponse::BombPotVoteResponse(PersonalBombPotVoteResponse::new(id, StatusCode::OK))),
+            self.get_bomb_pot_vote_response(),
+        ]
+    }
+
+    fn post_straddles(&mut self, active: &BTreeSet<usize>) -> Result<(), std::string::String> {
+        self.straddle_positions = Vec::new();
+        if self.pot.is_bomb_pot() {
+            return Ok(());
+        }
+
+        let blinds = self.blind_positions.as_ref().unwrap();
+        let will_straddle = self.players.iter()
+            .filter(|(_, p)| p.will_straddle && !p.owes_blinds())
+            .map(|(pos, _)| *pos)
+            .collect();
+        let can_cover = |pos: &usize, amount: u64| self.players.get(pos).map_or(false, |p| p.stack > amount);
+        let straddles = get_straddle_positions(&self.straddle_settings, blinds, active, &will_straddle, can_cover, self.bb_amt);
+
+        self.pot.post_straddles(&straddles)
+            .map_err(|e| e.to_string())?;
+        self.straddle_positions = straddles;
+        Ok(())
+    }
+
+    fn post_missed_blinds(&mut self) -> Result<(), std::string::String> {
+        if self.pot.is_bomb_pot() {
+            return Ok(());
+        }
+
+        for pos in self.get_players_in_hand() {
+            let player = self.players.get_mut(&pos).unwrap();
+            if player.owes_blinds() {
+                self.pot.post_missed_blinds(&pos, player.missed_bb, player.missed_sb)
+                    .map_err(|e| e.to_string())?;
+                player.missed_sb = false;
+                player.missed_bb = false;
+            }
+        }
+        Ok(())
+    }
+
+    // Starts the next hand. A hand that can not be started leaves the table as it was, so trying again later does not move the
+    // button or charge anyone for missing the blinds again
+    fn start_hand(&mut self) -> Result<Vec<GameResponse>, std::string::String> {
+        if self.showdown.is_some() {
+            return Err(format!("Players are still deciding to show or muck their hand"));
+        }
+        self.apply_pending_chips();
+        let entry = HandStartEntry {
+            players: self.players.clone(),
+            blind_positions: self.blind_positions.clone(),
+            straddle_positions: self.straddle_positions.clone(),
+            big_blind_idx: self.big_blind_idx,
+            btn_idx: self.btn_idx,
+            pot: self.pot.clone_box(),
+            auditor: self.auditor.clone(),
+            action_log: self.action_log.clone(),
+        };
+
+        let result = self.deal_hand();
+        if result.is_err() {
+            self.players = entry.players;
+            self.blind_positions = entry.blind_positions;
+            self.straddle_positions = entry.straddle_positions;
+            self.big_blind_idx = entry.big_blind_idx;
+            self.btn_idx = entry.btn_idx;
+            self.pot = entry.pot;
+            self.auditor = entry.auditor;
+            self.action_log = entry.action_log;
+        }
+        result
+    }
+
+    // Resets the pot, posts blinds and deals out the starting hands
+    fn deal_hand(&mut self) -> Result<Vec<GameResponse>, std::string::String> {
+        let active = self.get_active_positions();
+        let blinds = match rotate_blinds(self.blind_positions.as_ref(), &active) {
+            Some(blinds) => blinds,
+            None => {
+                return Err(format!("Not enough players to start a hand"));
+            }
+        };
+        let is_bomb = self.is_bomb_pot_next(&active);
+        let has_blinds = self.has_blinds();
+        if has_blinds {
+            self.mark_missed_blinds(self.blind_positions.clone().as_ref(), &blinds, is_bomb);
+        }
+
+        if is_bomb {
+            for (_, p) in &mut self.players {
+                if !p.play_in_bomb_pots {
+                    p.is_in_hand = false;
                 }
             }
         }
+
+        self.btn_idx = blinds.btn_idx;
+        self.big_blind_idx = blinds.bb_idx;
+        let sb_idx = if blinds.is_sb_dead { None } else { Some(blinds.sb_idx) };
+        self.blind_positions = Some(blinds);
+        // the first player after a dead button antes for them
+        let button_ante_idx = if self.is_button_ante {
+            std::iter::once(self.btn_idx).chain(self.get_positions_after(self.btn_idx)).find(|pos| active.contains(pos))
+        } else {
+            None
+        };
+
+        let ante_amt = if is_bomb { self.bomb_pot_settings.ante_amt } else { self.ante_amt };
+        self.pot.reset_pot(&self.players, self.sb_amt, self.bb_amt, ante_amt, is_bomb)
+            .map_err(|e| e.to_string())?;
+
+        let stacks = self.get_hand_stacks(false);
+        self.auditor.start_hand(self.hand_number + 1, &stacks);
+        self.action_log.clear();
+        self.undo_log.clear();
+        self.action_log.push(LoggedAction::HandStarted { hand_number: self.hand_number + 1, btn_idx: self.btn_idx, stacks: stacks.clone() });
+
+        if has_blinds {
+            self.pot.post_blinds_before_deal(sb_idx.as_ref(), &self.big_blind_idx)
+                .map_err(|e| e.to_string())?;
+            self.post_straddles(&active)?;
+            self.post_missed_blinds()?;
+        } else if let Some(pos) = button_ante_idx {
+            self.pot.post_button_ante(&pos)
+                .map_err(|e| e.to_string())?;
+            self.straddle_positions = Vec::new();
+        } else {
+            self.pot.post_antes_before_deal()
+                .map_err(|e| e.to_string())?;
+            self.straddle_positions = Vec::new();
+        }
+        for (pos, (stack, bet)) in self.pot.get_all_player_stacks_bets() {
+            let ante = if button_ante_idx.map_or(true, |idx| idx == *pos) { std::cmp::min(stacks[pos], ante_amt) } else { 0 };
+            let dead = stacks[pos] - stack - ante;
+            if ante + bet + dead > 0 {
+                self.action_log.push(LoggedAction::Posted { position: *pos, ante: ante, live: *bet, dead: dead });
+            }
+        }
+
+        self.game.start_normal(&mut self.players, self.btn_idx)
+            .map_err(|e| e.to_string())?;
+
+        // the bring-in depends on the cards dealt, so it can only be posted after the deal
+        let bring_in_idx = self.game.get_bring_in_idx(&self.players);
+        if let Some(pos) = bring_in_idx {
+            let amount = self.pot.post_bring_in(&pos)
+                .map_err(|e| e.to_string())?;
+            self.action_log.push(LoggedAction::BringIn { position: pos, amount: amount });
+        }
+
+        self.is_hand_in_progress = true;
+        self.street_idx = 0;
+
+        self.is_next_hand_bomb = false;
+        self.run_it_votes = None;
+        self.run_it_times = None;
+        self.fold_winner = None;
+        self.shown_hands.clear();
+        if is_bomb {
+            self.hands_since_bomb_pot = 0;
+            self.bomb_pot_votes.clear();
+        } else {
+            self.hands_since_bomb_pot += 1;
+        }
+
+        self.hand_number += 1;
+        for (_, p) in &mut self.players {
+            p.time_bank = self.clock_settings.refill_time_bank(p.time_bank, self.hand_number);
+        }
+        self.hand_history = Some(self.start_hand_history(&stacks, ante_amt, is_bomb));
+
+        let mut responses = self.get_hand_responses(&self.get_players_in_hand());
+
+        // the last straddle acts like the big blind, so action starts left of them and they get the option.
+        // In stud, the bring-in takes the place of the big blind, and without any blinds action starts left of the button
+        let mut start_pos = bring_in_idx
+            .or(self.straddle_positions.last().cloned())
+            .unwrap_or(if has_blinds { self.big_blind_idx } else { self.btn_idx });
+        if is_bomb && self.get_street() == Some(Street::Betting) {
+            // everyone is already in for the bomb, so preflop betting is skipped
+            self.street_idx += 1;
+            start_pos = self.btn_idx;
+        }
+        responses.extend(self.start_street(start_pos));
+        responses.extend(self.update_action_clock(None, Instant::now()));
+        responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+
+        Ok(responses)
+    }
+
+    // Private response with the whole hand for each of the given players
+    fn get_hand_responses(& self, positions: &Vec<usize>) -> Vec<GameResponse> {
+        positions.iter()
+            .filter_map(|pos| {
+                let id = self.get_player_id(pos)?;
+                let hand = self.players[pos].get_hand().clone();
+                Some(GameResponse::SingleResponse(id, SingleResponse::StartingHandResponse(StartingHandResponse::new(hand))))
+            })
+            .collect()
+    }
+
+    // Deals the current street. Players that were dealt cards are sent their new hand
+    fn deal_street(&mut self) -> Result<Vec<GameResponse>, std::string::String> {
+        let hand_sizes: HashMap<usize, usize> = self.players.iter().map(|(pos, p)| (*pos, p.get_hand().len())).collect();
+        self.game.deal_street(&mut self.players, self.street_idx)
+            .map_err(|e| format!("Could not deal street {}: {}", self.street_idx, e))?;
+
+        let dealt_to: Vec<usize> = self.get_players_in_hand().into_iter()
+            .filter(|pos| self.players[pos].get_hand().len() != hand_sizes[pos])
+            .collect();
+        if let Some(history) = &mut self.hand_history {
+            for pos in &dealt_to {
+                history.hole_cards.insert(*pos, self.players[pos].get_hand().clone());
+            }
+        }
+        Ok(self.get_hand_responses(&dealt_to))
+    }
+
+    // Sets up action for the current street, skipping over any streets where no action can take place.
+    // Action starts left of start_pos, unless the game decides who acts first by the cards showing
+    fn start_street(&mut self, start_pos: usize) -> Vec<GameResponse> {
+        let mut responses = Vec::new();
+        loop {
+            match self.get_street() {
+                Some(Street::Betting) => {
+                    self.last_aggressor = None;
+                    self.pot.set_big_bet(self.street_idx >= self.game.get_big_bet_street_idx());
+                    // the offer comes before the street is dealt, so every runout gets its own cards for it
+                    if !self.is_betting_needed() && self.should_offer_run_it() {
+                        responses.extend(self.offer_run_it());
+                        return responses;
+                    }
+                    match self.deal_street() {
+                        Ok(dealt) => responses.extend(dealt),
+                        Err(err) => {
+                            responses.extend(self.halt(Broadcast::HandFailure(err)));
+                            return responses;
+                        },
+                    }
+                    if self.is_betting_needed() {
+                        let next = match self.game.get_first_to_act(&self.players, self.street_idx) {
+                            Some(first) => std::iter::once(first)
+                                .chain(self.get_positions_after(first))
+                                .find(|pos| self.can_player_act(pos)),
+                            None => self.next_to_act(start_pos),
+                        };
+                        if let Some(pos) = next {
+                            self.action_idx = pos;
+                            return responses;
+                        }
+                    }
+                },
+                Some(Street::Draw) => {
+                    match self.deal_street() {
+                        Ok(dealt) => responses.extend(dealt),
+                        Err(err) => {
+                            responses.extend(self.halt(Broadcast::HandFailure(err)));
+                            return responses;
+                        },
+                    }
+                    if let Some(pos) = self.next_in_hand(start_pos) {
+                        self.action_idx = pos;
+                        self.last_to_draw_idx = *self.get_positions_after(start_pos).iter()
+                            .filter(|p| self.players[p].is_in_hand)
+                            .last()
+                            .unwrap();
+                        return responses;
+                    }
+                },
+                None => {
+                    responses.extend(self.end_hand());
+                    return responses;
+                },
+            }
+            self.pot.collect_bets();
+            self.street_idx += 1;
+        }
+    }
+
+    // Players can choose to run it multiple times once nobody can bet anymore and there is more to deal
+    fn should_offer_run_it(& self) -> bool {
+        self.run_it_times.is_none() &&
+            self.run_it_votes.is_none() &&
+            self.game.get_max_runouts() > 1 &&
+            self.get_players_in_hand().len() > 1
+    }
+
+    fn offer_run_it(&mut self) -> Vec<GameResponse> {
+        self.run_it_votes = Some(HashMap::new());
+        let offer = RunItOfferResponse::new(self.get_players_in_hand(), self.game.get_max_runouts(), self.clock_settings.action_secs);
+        vec![GameResponse::Broadcast(Broadcast::RunItOffer(offer))]
+    }
+
+    fn handle_run_it(&mut self, id: Uuid, action: RunItAction) -> Vec<GameResponse> {
+        let pos = self.get_position(&id);
+        let can_vote = self.run_it_votes.is_some() &&
+            !self.is_paused &&
+            pos.map_or(false, |pos| self.players[&pos].is_in_hand);
+
+        let status = if !can_vote {
+            self.get_rejected_status()
+        } else if action.times == 0 || action.times > self.game.get_max_runouts() {
+            StatusCode::InvalidRunItTimes
+        } else {
+            StatusCode::OK
+        };
+
+        if status != StatusCode::OK {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::RunItResponse(PersonalRunItResponse::new(id, status)))];
+        }
+
+        let pos = pos.unwrap();
+        let votes = self.run_it_votes.as_mut().unwrap();
+        votes.insert(pos, action.times);
+        let has_everyone_voted = self.get_players_in_hand().iter().all(|pos| self.run_it_votes.as_ref().unwrap().contains_key(pos));
+
+        let mut responses = vec![
+            GameResponse::SingleResponse(id, SingleResponse::RunItResponse(PersonalRunItResponse::new(id, StatusCode::OK))),
+            GameResponse::Multicast(id, Multicast::RunItResponse(PublicRunItResponse::new(pos, action.times))),
+        ];
+        if has_everyone_voted {
+            responses.extend(self.decide_run_it());
+        }
+        responses
+    }
+
+    // Runs it the fewest times anyone agreed to. Players that did not decide run it once
+    fn decide_run_it(&mut self) -> Vec<GameResponse> {
+        let votes = self.run_it_votes.take().unwrap_or_default();
+        let times = self.get_players_in_hand().iter()
+            .map(|pos| votes.get(pos).cloned().unwrap_or(1))
+            .min()
+            .unwrap_or(1);
+        self.run_it_times = Some(times);
+
+        let mut responses = vec![GameResponse::Broadcast(Broadcast::RunItDecision(RunItDecisionResponse::new(times)))];
+        if times > 1 {
+            responses.extend(self.end_hand());
+        } else {
+            responses.extend(self.start_street(self.btn_idx));
+        }
+        responses
+    }
+
+    // Stacks of everyone dealt into the hand. Until the hand is settled, the pot keeps track of the stacks
+    fn get_hand_stacks(& self, is_settled: bool) -> BTreeMap<usize, u64> {
+        self.pot.get_all_player_stacks_bets().iter()
+            .map(|(pos, (stack, _))| {
+                let stack = if is_settled { self.players.get(pos).map_or(0, |p| p.stack) } else { *stack };
+                (*pos, stack)
+            })
+            .collect()
+    }
+
+    fn audit_chips(& self, checkpoint: &str, is_settled: bool) -> Result<(), AuditReport> {
+        // the pot is not emptied out when it gets paid out
+        let pot_total = if is_settled { 0 } else { self.pot.get_total_pot() };
+        self.auditor.verify(checkpoint, self.get_hand_stacks(is_settled), pot_total, self.pot.get_rake(), &self.action_log)
+    }
+
+    // Stops the table for good so the host can look into what went wrong
+    fn halt(&mut self, reason: Broadcast) -> Vec<GameResponse> {
+        self.is_halted = true;
+        self.start_next_hand = false;
+        let mut responses = self.pause(Instant::now());
+        responses.push(GameResponse::Broadcast(reason));
+        responses
+    }
+
+    fn get_undo_entry(& self) -> UndoEntry {
+        UndoEntry {
+            pot: self.pot.clone_box(),
+            action_idx: self.action_idx,
+            in_hand: self.get_players_in_hand().into_iter().collect(),
+            away: self.players.iter().filter(|(_, p)| p.is_away).map(|(pos, _)| *pos).collect(),
+            last_aggressor: self.last_aggressor,
+            run_it_votes: self.run_it_votes.clone(),
+            action_log_len: self.action_log.len(),
+        }
+    }
+
+    // Takes back the last player action, or every action of the current street. Only betting actions on the current street can be undone,
+    // and only by the host
+    fn undo(&mut self, id: Uuid, to_street_start: bool) -> Vec<GameResponse> {
+        if !self.is_host(&id) {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::UndoResponse(PersonalUndoResponse::new(id, StatusCode::NotHost, 0)))];
+        }
+        if !self.is_hand_in_progress || self.is_halted || self.undo_log.is_empty() {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::UndoResponse(PersonalUndoResponse::new(id, StatusCode::NothingToUndo, 0)))];
+        }
+
+        let count = if to_street_start { self.undo_log.len() } else { 1 };
+        let entry = self.undo_log.drain(self.undo_log.len() - count..).next().unwrap();
+
+        let prev_turn = self.get_turn();
+        self.pot = entry.pot;
+        self.action_idx = entry.action_idx;
+        let mut away_changed = Vec::new();
+        for (pos, p) in &mut self.players {
+            p.is_in_hand = entry.in_hand.contains(pos);
+            let is_away = entry.away.contains(pos);
+            if p.is_away != is_away {
+                p.is_away = is_away;
+                away_changed.push((*pos, is_away));
+            }
+        }
+        self.last_aggressor = entry.last_aggressor;
+        self.run_it_votes = entry.run_it_votes;
+        self.action_log.truncate(entry.action_log_len);
+
+        let mut responses = vec![GameResponse::SingleResponse(id, SingleResponse::UndoResponse(PersonalUndoResponse::new(id, StatusCode::OK, count)))];
+        for (pos, is_away) in away_changed {
+            let player_id = self.get_player_id(&pos).unwrap_or_else(Uuid::nil);
+            responses.push(GameResponse::Multicast(player_id, Multicast::AwayResponse(PublicAwayResponse::new(pos, is_away))));
+        }
+        responses.extend(self.update_action_clock(prev_turn, Instant::now()));
+        responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+        responses
+    }
+
+    fn start_hand_history(& self, stacks: &BTreeMap<usize, u64>, ante_amt: u64, is_bomb: bool) -> HandHistory {
+        HandHistory {
+            hand_number: self.hand_number,
+            started_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs()),
+            config: self.config.clone(),
+            seats: stacks.iter()
+                .map(|(pos, stack)| SeatHistory { position: *pos, name: self.players[pos].name.clone(), stack: *stack })
+                .collect(),
+            blinds: self.blind_positions.clone(),
+            straddles: self.straddle_positions.clone(),
+            ante_amt: ante_amt,
+            is_bomb_pot: is_bomb,
+            hole_cards: stacks.keys().map(|pos| (*pos, self.players[pos].get_hand().clone())).collect(),
+            up_cards: BTreeMap::new(),
+            actions: Vec::new(),
+            draws: Vec::new(),
+            board: Vec::new(),
+            second_board: Vec::new(),
+            runouts: Vec::new(),
+            side_pots: Vec::new(),
+            rake: 0,
+            winnings: Vec::new(),
+            shown_hands: BTreeMap::new(),
+            final_stacks: BTreeMap::new(),
+        }
+    }
+
+    // Fills in how the hand ended and writes it out
+    fn finish_hand_history(&mut self) -> Result<(), std::string::String> {
+        let final_stacks = self.get_hand_stacks(true);
+        let history = match &mut self.hand_history {
+            Some(history) => history,
+            None => return Ok(()),
+        };
+
+        history.actions = self.action_log.clone();
+        history.board = self.game.get_board();
+        history.second_board = self.game.get_second_board();
+        history.up_cards = history.hole_cards.keys()
+            .map(|pos| (*pos, self.players.get(pos).map_or(Vec::new(), |p| p.get_up_cards().clone())))
+            .filter(|(_, cards)| !cards.is_empty())
+            .collect();
+        history.side_pots = SidePotHistory::from_pots(self.pot.get_pots());
+        history.rake = self.pot.get_rake();
+        history.shown_hands = self.shown_hands.iter().map(|(pos, cards)| (*pos, cards.clone())).collect();
+        history.final_stacks = final_stacks;
+
+        if let Some(dir) = &self.hand_history_dir {
+            history.write_to(dir)
+                .map_err(|e| format!("Could not write hand history for hand {}: {}", history.hand_number, e))?;
+        }
+        Ok(())
+    }
+
+    fn end_street(&mut self) -> Vec<GameResponse> {
+        self.pot.collect_bets();
+        self.undo_log.clear();
+        self.action_log.push(LoggedAction::StreetEnded { street_idx: self.street_idx });
+        if let Err(report) = self.audit_chips(&format!("End of street {}", self.street_idx), false) {
+            return self.halt(Broadcast::AuditFailure(report));
+        }
+        self.street_idx += 1;
+        self.start_street(self.btn_idx)
+    }
+
+    // Moves action to the next player, ending the street or the hand if needed
+    fn advance_action(&mut self) -> Vec<GameResponse> {
+        if self.have_all_other_players_folded() {
+            return self.end_hand();
+        }
+
+        match self.get_street() {
+            Some(Street::Betting) => {
+                let next = self.next_to_act(self.action_idx);
+                let is_street_over = next.map_or(true, |next| self.has_betting_closed(self.action_idx, next));
+
+                if is_street_over {
+                    self.end_street()
+                } else {
+                    self.action_idx = next.unwrap();
+                    Vec::new()
+                }
+            },
+            Some(Street::Draw) => {
+                if self.action_idx == self.last_to_draw_idx {
+                    self.end_street()
+                } else {
+                    self.action_idx = self.next_in_hand(self.action_idx).unwrap();
+                    Vec::new()
+                }
+            },
+            None => self.end_hand(),
+        }
+    }
+
+    // Collects all remaining bets and pays out the pot to the winners
+    fn end_hand(&mut self) -> Vec<GameResponse> {
+        self.pot.collect_bets();
+
+        // the pot keeps track of stacks during the hand, so sync them back up before the pot gets paid out
+        for (pos, (stack, _)) in self.pot.get_all_player_stacks_bets() {
+            if let Some(player) = self.players.get_mut(pos) {
+                player.stack = *stack;
+            }
+        }
+
+        let in_hand = self.get_players_in_hand();
+        let all_in: HashSet<usize> = in_hand.iter().filter(|pos| self.players[pos].stack == 0).cloned().collect();
+        let run_it_times = self.run_it_times.unwrap_or(1);
+        let runouts = if in_hand.len() > 1 && run_it_times > 1 {
+            self.game.deal_runouts(run_it_times).ok()
+        } else {
+            None
+        };
+
+        let odd_chip = self.game.get_odd_chip_priority();
+        let mut responses = Vec::new();
+        let winnings = match runouts {
+            Some(runouts) => {
+                let rankings = runouts.iter().map(|runout| self.game.evaluate_runout(&self.players, runout)).collect();
+                let runout_winnings = match self.pot.distribute_pot_runouts(&mut self.players, &rankings, &self.btn_idx, odd_chip) {
+                    Ok(runout_winnings) => runout_winnings,
+                    Err(err) => {
+                        let err = format!("Could not settle the pot: {}", err);
+                        return self.halt(Broadcast::HandFailure(err));
+                    },
+                };
+
+                let mut winnings = HashMap::new();
+                for (runout_idx, (runout, runout_winnings)) in runouts.into_iter().zip(runout_winnings).enumerate() {
+                    for (pos, amt) in &runout_winnings {
+                        *winnings.entry(*pos).or_insert(0) += amt;
+                    }
+                    if let Some(history) = &mut self.hand_history {
+                        history.runouts.push(runout.clone());
+                        history.winnings.push(runout_winnings.clone());
+                    }
+                    responses.push(GameResponse::Broadcast(Broadcast::RunoutResult(RunoutResultResponse::new(runout_idx, runout, runout_winnings))));
+                }
+                winnings
+            },
+            None => {
+                let rankings = if in_hand.len() == 1 {
+                    vec![HashMap::from([(in_hand[0], Rank::High(HighRank::new(0)))])]
+                } else {
+                    self.game.evaluate_all_hands(&self.players)
+                };
+
+                let winnings = match self.pot.distribute_pot(&mut self.players, &rankings, &self.btn_idx, odd_chip) {
+                    Ok(winnings) => winnings,
+                    Err(err) => {
+                        let err = format!("Could not settle the pot: {}", err);
+                        return self.halt(Broadcast::HandFailure(err));
+                    },
+                };
+                if let Some(history) = &mut self.hand_history {
+                    history.winnings.push(winnings.clone());
+                }
+                winnings
+            },
+        };
+
+        self.is_hand_in_progress = false;
+
+        if in_hand.len() > 1 {
+            responses.extend(self.start_showdown(&all_in, winnings));
+        } else {
+            self.fold_winner = in_hand.first().cloned();
+            responses.extend(self.finish_hand(winnings));
+        }
+        responses
+    }
+
+    // Wraps up a settled hand once every hand that is going to be shown has been
+    fn finish_hand(&mut self, winnings: HashMap<usize, u64>) -> Vec<GameResponse> {
+        let mut responses = Vec::new();
+        self.action_log.push(LoggedAction::HandEnded { winnings: winnings.clone() });
+        if let Err(err) = self.finish_hand_history() {
+            if let Some(host) = self.host_id {
+                responses.push(GameResponse::SingleResponse(host, SingleResponse::HandHistoryError(err)));
+            }
+        }
+        responses.push(GameResponse::Broadcast(Broadcast::HandResult(HandResultResponse::new(winnings))));
+        if let Err(report) = self.audit_chips("Settlement", true) {
+            responses.extend(self.halt(Broadcast::AuditFailure(report)));
+            return responses;
+        }
+
+        responses.extend(self.remove_pending_players());
+        if let Some(config) = self.pending_config.take() {
+            self.apply_config(config);
+        }
+        responses
+    }
+
+    // The last aggressor of the final betting street shows first, then everyone else clockwise from the button
+    fn get_showdown_order(& self) -> Vec<usize> {
+        let mut order: Vec<usize> = self.get_positions_after(self.btn_idx).into_iter()
+            .filter(|pos| self.players[pos].is_in_hand)
+            .collect();
+        if let Some(idx) = self.last_aggressor.and_then(|aggressor| order.iter().position(|pos| *pos == aggressor)) {
+            let aggressor = order.remove(idx);
+            order.insert(0, aggressor);
+        }
+        order
+    }
+
+    // The first hand, all-in hands and hands that won chips are always shown. Everyone else decides if they show or muck their hand
+    fn start_showdown(&mut self, all_in: &HashSet<usize>, winnings: HashMap<usize, u64>) -> Vec<GameResponse> {
+        let order: VecDeque<usize> = self.get_showdown_order().into_iter().collect();
+        let must_show = order.iter().enumerate()
+            .filter(|(idx, pos)| *idx == 0 || all_in.contains(pos) || winnings.get(pos).map_or(false, |amt| *amt > 0))
+            .map(|(_, pos)| *pos)
+            .collect();
+        self.showdown = Some(Showdown {
+            order: order,
+            must_show: must_show,
+            winnings: winnings,
+        });
+        self.continue_showdown()
+    }
+
+    // Shows hands in order until a player has to decide, and finishes the hand once nobody is left to show
+    fn continue_showdown(&mut self) -> Vec<GameResponse> {
+        let mut responses = Vec::new();
+        loop {
+            let next = self.showdown.as_ref()
+                .and_then(|showdown| showdown.order.front().map(|pos| (*pos, showdown.must_show.contains(pos))));
+            match next {
+                Some((_, true)) => responses.extend(self.reveal_hand(true)),
+                Some((pos, false)) => {
+                    self.action_idx = pos;
+                    return responses;
+                },
+                None => break,
+            }
+        }
+
+        let winnings = self.showdown.take().map_or_else(HashMap::new, |showdown| showdown.winnings);
+        responses.extend(self.finish_hand(winnings));
+        responses
+    }
+
+    // Shows or mucks the hand of the next player at showdown
+    fn reveal_hand(&mut self, show: bool) -> Vec<GameResponse> {
+        let pos = match self.showdown.as_mut().and_then(|showdown| showdown.order.pop_front()) {
+            Some(pos) => pos,
+            None => return Vec::new(),
+        };
+        let id = self.get_player_id(&pos).unwrap_or_else(Uuid::nil);
+
+        if show {
+            let hand = self.players[&pos].get_hand().clone();
+            self.shown_hands.insert(pos, hand.clone());
+            vec![GameResponse::Multicast(id, Multicast::ShowHandResponse(PublicShowHandResponse::new(pos, hand)))]
+        } else {
+            vec![GameResponse::Multicast(id, Multicast::MuckHandResponse(PublicMuckHandResponse::new(pos)))]
+        }
+    }
+
+    fn handle_showdown(&mut self, id: Uuid, action: ShowdownAction) -> Vec<GameResponse> {
+        let is_players_turn = self.showdown.is_some() &&
+            !self.is_paused &&
+            self.get_position(&id) == Some(self.action_idx);
+        if !is_players_turn {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(id, self.get_rejected_status())))];
+        }
+
+        let mut responses = vec![GameResponse::SingleResponse(id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(id, StatusCode::OK)))];
+        responses.extend(self.reveal_hand(action.show));
+        responses.extend(self.continue_showdown());
+        responses
+    }
+
+    // The winner of an uncontested pot can show any of their cards until the next hand is dealt
+    fn show_cards(&mut self, id: Uuid, action: ShowCardsAction) -> Vec<GameResponse> {
+        let pos = match self.get_position(&id) {
+            Some(pos) => pos,
+            None => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(id, StatusCode::NotSeated)))];
+            },
+        };
+
+        let hand = self.players[&pos].get_hand();
+        let can_show = !self.is_hand_in_progress &&
+            self.fold_winner == Some(pos) &&
+            !action.cards.is_empty() &&
+            action.cards.iter().all(|card| hand.contains(card));
+        if !can_show {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(id, StatusCode::InvalidShow)))];
+        }
+
+        let shown = self.shown_hands.entry(pos).or_insert_with(Vec::new);
+        for card in &action.cards {
+            if !shown.contains(card) {
+                shown.push(*card);
+            }
+        }
+
+        vec![
+            GameResponse::SingleResponse(id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(id, StatusCode::OK))),
+            GameResponse::Multicast(id, Multicast::ShowHandResponse(PublicShowHandResponse::new(pos, action.cards))),
+        ]
+    }
+
+    fn handle_pot_action(&mut self, id: Uuid, action: PotAction) -> Vec<GameResponse> {
+        let pos = self.get_position(&id);
+        let is_players_turn = self.is_hand_in_progress &&
+            !self.is_paused &&
+            self.run_it_votes.is_none() &&
+            self.get_street() == Some(Street::Betting) &&
+            pos == Some(self.action_idx);
+        let rejected = self.get_rejected_status();
+        let undo_entry = self.get_undo_entry();
+
+        let mut responses = Vec::new();
+        match action {
+            PotAction::Fold => {
+                if !is_players_turn {
+                    return vec![GameResponse::SingleResponse(id, SingleResponse::FoldResponse(PersonalFoldResponse::new(id, rejected)))];
+                }
+                let pos = pos.unwrap();
+                if self.pot.fold(&pos).is_err() {
+                    return vec![GameResponse::SingleResponse(id, SingleResponse::FoldResponse(PersonalFoldResponse::new(id, StatusCode::ActionFailed)))];
+                }
+                self.players.get_mut(&pos).unwrap().is_in_hand = false;
+                self.action_log.push(LoggedAction::Fold { position: pos });
+
+                responses.push(GameResponse::SingleResponse(id, SingleResponse::FoldResponse(PersonalFoldResponse::new(id, StatusCode::OK))));
+                responses.push(GameResponse::Multicast(id, Multicast::FoldResponse(PublicFoldResponse::new(pos))));
+            },
+            PotAction::CheckCall => {
+                if !is_players_turn {
+                    return vec![GameResponse::SingleResponse(id, SingleResponse::CheckCallResponse(PersonalCheckCallResponse::new(id, rejected)))];
+                }
+                let pos = pos.unwrap();
+                if self.pot.check_call(&pos).is_err() {
+                    return vec![GameResponse::SingleResponse(id, SingleResponse::CheckCallResponse(PersonalCheckCallResponse::new(id, StatusCode::ActionFailed)))];
+                }
+                self.action_log.push(LoggedAction::CheckCall { position: pos });
+
+                responses.push(GameResponse::SingleResponse(id, SingleResponse::CheckCallResponse(PersonalCheckCallResponse::new(id, StatusCode::OK))));
+                responses.push(GameResponse::Multicast(id, Multicast::CheckCallResponse(PublicCheckCallResponse::new(pos))));
+            },
+            PotAction::BetRaise(bet) => {
+                if !is_players_turn {
+                    return vec![GameResponse::SingleResponse(id, SingleResponse::BetRaiseResponse(PersonalBetRaiseResponse::new(id, rejected, 0)))];
+                }
+                let pos = pos.unwrap();
+                let largest_bet = self.get_largest_bet();
+                match self.pot.bet_or_shove(&pos, bet.amount) {
+                    Ok(bet_size) => {
+                        if self.get_largest_bet() > largest_bet {
+                            self.last_aggressor = Some(pos);
+                        }
+                        self.action_log.push(LoggedAction::BetRaise { position: pos, amount: bet_size });
+                        responses.push(GameResponse::SingleResponse(id, SingleResponse::BetRaiseResponse(PersonalBetRaiseResponse::new(id, StatusCode::OK, bet_size))));
+                        responses.push(GameResponse::Multicast(id, Multicast::BetRaiseResponse(PublicBetRaiseResponse::new(pos, bet_size))));
+                    },
+                    Err(_) => {
+                        return vec![GameResponse::SingleResponse(id, SingleResponse::BetRaiseResponse(PersonalBetRaiseResponse::new(id, StatusCode::InvalidBet, bet.amount)))];
+                    }
+                }
+            },
+        }
+
+        self.undo_log.push(undo_entry);
+        responses.extend(self.advance_action());
+        responses
+    }
+
+    fn handle_draw_action(&mut self, id: Uuid, action: DrawAction) -> Vec<GameResponse> {
+        let pos = self.get_position(&id);
+        let is_players_turn = self.is_hand_in_progress &&
+            !self.is_paused &&
+            self.run_it_votes.is_none() &&
+            self.get_street() == Some(Street::Draw) &&
+            pos == Some(self.action_idx);
+
+        if !is_players_turn {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::DrawResponse(PersonalDrawResponse::new(id, self.get_rejected_status(), Vec::new())))];
+        }
+        let pos = pos.unwrap();
+
+        let player = self.players.get_mut(&pos).unwrap();
+        let new_cards = match self.game.draw_cards(player, &action.cards_to_discard) {
+            Ok(cards) => cards,
+            Err(_) => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::DrawResponse(PersonalDrawResponse::new(id, StatusCode::InvalidDraw, Vec::new())))];
+            },
+        };
+
+        self.action_log.push(LoggedAction::Draw { position: pos, discard_count: action.cards_to_discard.len() });
+        // the cards are already dealt, so nothing before a draw can be undone
+        self.undo_log.clear();
+        if let Some(history) = &mut self.hand_history {
+            history.draws.push(DrawHistory { position: pos, discarded: action.cards_to_discard.clone(), drawn: new_cards.clone() });
+        }
+
+        let mut responses = vec![
+            GameResponse::Multicast(id, Multicast::DrawResponse(PublicDrawResponse::new(pos, action.cards_to_discard.len(), new_cards.len()))),
+            GameResponse::SingleResponse(id, SingleResponse::DrawResponse(PersonalDrawResponse::new(id, StatusCode::OK, new_cards))),
+        ];
+
+        responses.extend(self.advance_action());
+        responses
+    }
+
+    fn handle_game_action(&mut self, action: GameAction) -> Vec<GameResponse> {
+        let prev_turn = self.get_turn();
+        let mut responses = match action {
+            GameAction::Pot(id, action) => self.handle_pot_action(id, action),
+            GameAction::Draw(id, action) => self.handle_draw_action(id, action),
+            GameAction::RunIt(id, action) => self.handle_run_it(id, action),
+            GameAction::Showdown(id, action) => self.handle_showdown(id, action),
+        };
+        responses.extend(self.update_action_clock(prev_turn, Instant::now()));
+
+        responses.push(GameResponse::Broadcast(Broadcast::State(self.get_game_state())));
+        responses
+    }
+
+    fn is_seat_reserved_for_other(& self, pos: &usize, id: &Uuid) -> bool {
+        self.reserved_seats.get(pos).map_or(false, |(reserved_id, reserved_at)| {
+            reserved_id != id && reserved_at.elapsed() < RESERVE_SEAT_TIMEOUT
+        })
+    }
+
+    // Number of seats that are either taken or held by a reservation
+    fn get_occupied_seat_count(& self) -> usize {
+        let reserved = self.reserved_seats.iter()
+            .filter(|(pos, (_, reserved_at))| !self.players.contains_key(pos) && reserved_at.elapsed() < RESERVE_SEAT_TIMEOUT)
+            .count();
+        self.players.len() + reserved
+    }
+
+    fn seat_player(&mut self, id: Uuid, pos: usize, name: String, buy_in: u64, wait_for_bb: bool) -> Vec<GameResponse> {
+        let mut player = Player::new(pos, name.clone(), buy_in);
+        // players that sit down during a hand are dealt in on the next one
+        player.is_in_hand = false;
+        // once the game is going, new players have to post a big blind to be dealt in
+        player.missed_bb = self.blind_positions.is_some();
+        player.wait_for_bb = wait_for_bb;
+        player.time_bank = self.clock_settings.get_starting_time_bank();
+
+        self.players.insert(pos, player);
+        self.seats.insert(id, pos);
+        self.ledger.record(pos, name.clone(), LedgerEntryType::BuyIn, buy_in, self.hand_number);
+        self.reserved_seats.remove(&pos);
+        self.waiting_list.retain(|(waiting_id, _, _)| *waiting_id != id);
+
+        vec![
+            GameResponse::SingleResponse(id, SingleResponse::SitDownResponse(PersonalSitDownResponse::new(id, StatusCode::OK, Some(pos)))),
+            GameResponse::Multicast(id, Multicast::SitDownResponse(PublicSitDownResponse::new(pos, name, buy_in))),
+        ]
+    }
+
+    fn sit_down(&mut self, id: Uuid, action: SitDownAction) -> Vec<GameResponse> {
+        let status = if self.seats.contains_key(&id) {
+            StatusCode::AlreadySeated
+        } else if action.position >= self.config.seat_count {
+            StatusCode::InvalidSeat
+        } else if self.players.contains_key(&action.position) {
+            StatusCode::SeatTaken
+        } else if self.is_seat_reserved_for_other(&action.position, &id) {
+            StatusCode::SeatReserved
+        } else if action.buy_in < self.config.min_buy_in || action.buy_in > self.config.max_buy_in {
+            StatusCode::InvalidBuyIn
+        } else {
+            StatusCode::OK
+        };
+
+        if status != StatusCode::OK {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::SitDownResponse(PersonalSitDownResponse::new(id, status, None)))];
+        }
+
+        self.seat_player(id, action.position, action.name, action.buy_in, action.wait_for_bb)
+    }
+
+    fn reserve_seat(&mut self, id: Uuid, action: ReserveSeatAction) -> Vec<GameResponse> {
+        let status = if self.seats.contains_key(&id) {
+            StatusCode::AlreadySeated
+        } else if action.position >= self.config.seat_count {
+            StatusCode::InvalidSeat
+        } else if self.players.contains_key(&action.position) {
+            StatusCode::SeatTaken
+        } else if self.is_seat_reserved_for_other(&action.position, &id) {
+            StatusCode::SeatReserved
+        } else {
+            StatusCode::OK
+        };
+
+        if status != StatusCode::OK {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::ReserveSeatResponse(PersonalReserveSeatResponse::new(id, status)))];
+        }
+
+        // a player can only hold one seat at a time
+        self.reserved_seats.retain(|_, (reserved_id, _)| *reserved_id != id);
+        self.reserved_seats.insert(action.position, (id, Instant::now()));
+
+        vec![
+            GameResponse::SingleResponse(id, SingleResponse::ReserveSeatResponse(PersonalReserveSeatResponse::new(id, StatusCode::OK))),
+            GameResponse::Multicast(id, Multicast::ReserveSeatResponse(PublicReserveSeatResponse::new(action.position, RESERVE_SEAT_TIMEOUT.as_secs()))),
+        ]
+    }
+
+    fn stand_up(&mut self, id: Uuid) -> Vec<GameResponse> {
+        let pos = match self.get_position(&id) {
+            Some(pos) => pos,
+            None => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::StandUpResponse(PersonalStandUpResponse::new(id, StatusCode::NotSeated)))];
+            },
+        };
+
+        // players leaving in the middle of a hand forfeit it, so they can only leave once their hand is folded
+        let is_in_hand = self.is_hand_in_progress && self.players[&pos].is_in_hand;
+        if is_in_hand {
+            // leaving out of turn must not close the action on the leaver, everyone after them still gets to act
+            let fold = if self.action_idx == pos {
+                self.pot.fold(&pos)
+            } else {
+                self.pot.fold_out_of_turn(&pos)
+            };
+            if fold.is_err() {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::StandUpResponse(PersonalStandUpResponse::new(id, StatusCode::ActionFailed)))];
+            }
+        }
+
+        let mut responses = vec![GameResponse::SingleResponse(id, SingleResponse::StandUpResponse(PersonalStandUpResponse::new(id, StatusCode::OK)))];
+
+        if self.is_hand_in_progress {
+            let prev_turn = self.get_turn();
+            // they stay seated until the hand is over
+            self.pending_stand_ups.insert(pos);
+            if is_in_hand {
+                self.players.get_mut(&pos).unwrap().is_in_hand = false;
+                self.action_log.push(LoggedAction::Fold { position: pos });
+                // the player is gone, so they can not be brought back into the hand
+                self.undo_log.clear();
+                responses.push(GameResponse::Multicast(id, Multicast::FoldResponse(PublicFoldResponse::new(pos))));
+
+                if self.action_idx == pos || self.have_all_other_players_folded() {
+                    responses.extend(self.advance_action());
+                }
+            }
+            responses.extend(self.update_action_clock(prev_turn, Instant::now()));
+        } else if self.showdown.as_ref().map_or(false, |showdown| showdown.order.contains(&pos)) {
+            // their hand still gets shown or mucked, so they leave once the showdown is over
+            self.pending_stand_ups.insert(pos);
+        } else {
+            responses.extend(self.remove_player(pos));
+        }
+
+        responses
+    }
+
+    // Removes the player from the table and gives the seat to the next player on the waiting list
+    fn remove_player(&mut self, pos: usize) -> Vec<GameResponse> {
+        let mut responses = Vec::new();
+        if let Some(id) = self.get_player_id(&pos) {
+            self.seats.remove(&id);
+            responses.push(GameResponse::Multicast(id, Multicast::StandUpResponse(PublicStandUpResponse::new(pos))));
+        }
+        if let Some(player) = self.players.remove(&pos) {
+            self.ledger.record(pos, player.name, LedgerEntryType::CashOut, player.stack, self.hand_number);
+        }
+        self.pending_stand_ups.remove(&pos);
+        self.bomb_pot_votes.remove(&pos);
+
+        // the buy-in limits might have changed since the player joined the waiting list, anyone outside of them loses their spot
+        while let Some((id, name, buy_in)) = self.waiting_list.pop_front() {
+            if buy_in < self.config.min_buy_in || buy_in > self.config.max_buy_in {
+                responses.push(GameResponse::SingleResponse(id, SingleResponse::WaitingListResponse(PersonalWaitingListResponse::new(id, StatusCode::InvalidBuyIn, None))));
+                continue;
+            }
+            // the player might not be around when the seat opens up, so they do not get charged a blind until it is their turn
+            responses.extend(self.seat_player(id, pos, name, buy_in, true));
+            break;
+        }
+        responses
+    }
+
+    // Chips the player has behind. During a hand this does not include what is already in the pot
+    fn get_current_stack(& self, pos: &usize) -> u64 {
+        if self.is_hand_in_progress {
+            if let Ok((stack, bet)) = self.pot.get_player_stack_bet(pos) {
+                return stack - bet;
+            }
+        }
+        self.players.get(pos).map_or(0, |p| p.stack)
+    }
+
+    // A rebuy has to bring the player back up to at least the min buy-in, and nobody can go over the max buy-in
+    fn add_chips(&mut self, id: Uuid, action: AddChipsAction) -> Vec<GameResponse> {
+        let pos = match self.get_position(&id) {
+            Some(pos) => pos,
+            None => {
+                return vec![GameResponse::SingleResponse(id, SingleResponse::AddChipsResponse(PersonalAddChipsResponse::new(id, StatusCode::NotSeated, false)))];
+            },
+        };
+
+        let total = self.get_current_stack(&pos) + self.players[&pos].pending_chips + action.amount;
+        if action.amount == 0 || total < self.config.min_buy_in || total > self.config.max_buy_in {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::AddChipsResponse(PersonalAddChipsResponse::new(id, StatusCode::InvalidBuyIn, false)))];
+        }
+
+        self.players.get_mut(&pos).unwrap().pending_chips += action.amount;
+        if !self.is_hand_in_progress {
+            self.apply_pending_chips();
+        }
+
+        vec![GameResponse::SingleResponse(id, SingleResponse::AddChipsResponse(PersonalAddChipsResponse::new(id, StatusCode::OK, self.is_hand_in_progress)))]
+    }
+
+    // Adds queued rebuys and top-ups, and rebuys busted players that asked to. Stacks are capped at the max buy-in
+    fn apply_pending_chips(&mut self) {
+        let mut positions: Vec<usize> = self.players.keys().cloned().collect();
+        positions.sort();
+        for pos in positions {
+            let player = self.players.get_mut(&pos).unwrap();
+            let room = self.config.max_buy_in.saturating_sub(player.stack);
+            let (amount, entry_type) = if player.pending_chips > 0 {
+                let entry_type = if player.stack == 0 { LedgerEntryType::Rebuy } else { LedgerEntryType::TopUp };
+                (std::cmp::min(player.pending_chips, room), entry_type)
+            } else if player.auto_rebuy && player.stack == 0 {
+                (room, LedgerEntryType::AutoRebuy)
+            } else {
+                continue;
+            };
+
+            player.pending_chips = 0;
+            if amount > 0 {
+                player.stack += amount;
+                self.ledger.record(pos, player.name.clone(), entry_type, amount, self.hand_number);
+            }
+        }
+    }
+
+    // Only the host gets to see what everyone bought in and cashed out for
+    fn get_ledger(& self, id: Uuid) -> Vec<GameResponse> {
+        let res = if self.is_host(&id) {
+            LedgerResponse::new(id, StatusCode::OK, self.ledger.get_entries().clone())
+        } else {
+            LedgerResponse::new(id, StatusCode::NotHost, Vec::new())
+        };
+        vec![GameResponse::SingleResponse(id, SingleResponse::LedgerResponse(res))]
+    }
+
+    fn remove_pending_players(&mut self) -> Vec<GameResponse> {
+        let mut pending: Vec<usize> = self.pending_stand_ups.iter().cloned().collect();
+        pending.sort();
+        pending.into_iter().flat_map(|pos| self.remove_player(pos)).collect()
+    }
+
+    fn join_waiting_list(&mut self, id: Uuid, action: WaitingListAction) -> Vec<GameResponse> {
+        let status = if self.seats.contains_key(&id) || self.waiting_list.iter().any(|(waiting_id, _, _)| *waiting_id == id) {
+            StatusCode::AlreadySeated
+        } else if self.get_occupied_seat_count() < self.config.seat_count {
+            StatusCode::TableNotFull
+        } else if action.buy_in < self.config.min_buy_in || action.buy_in > self.config.max_buy_in {
+            StatusCode::InvalidBuyIn
+        } else {
+            StatusCode::OK
+        };
+
+        if status != StatusCode::OK {
+            return vec![GameResponse::SingleResponse(id, SingleResponse::WaitingListResponse(PersonalWaitingListResponse::new(id, status, None)))];
+        }
+
+        self.waiting_list.push_back((id, action.name, action.buy_in));
+        vec![GameResponse::SingleResponse(id, SingleResponse::WaitingListResponse(PersonalWaitingListResponse::new(id, StatusCode::OK, Some(self.waiting_list.len() - 1))))]
+    }
+
+    fn leave_waiting_list(&mut self, id: Uuid) -> Vec<GameResponse> {
+        let len = self.waiting_list.len();
+        self.waiting_list.retain(|(waiting_id, _, _)| *waiting_id != id);
+
+        let status = if len == self.waiting_list.len() { StatusCode::NotSeated } else { StatusCode::OK };
+        vec![GameResponse::SingleResponse(id, SingleResponse::WaitingListResponse(PersonalWaitingListResponse::new(id, status, None)))]
+    }
+
+    // TODO: Probably should figure out what types are going to be sent thru the channels here
+    pub async fn run_loop(table: Arc<Mutex<Table>>, rx: &mut UnboundedReceiver<GameActionPayload>, res_tx: UnboundedSender<GameActionResponse>) {
+        let mut game_loop_tx: Option<UnboundedSender<GameAction>> = None;
+        while let Some(msg) = rx.recv().await {
+            if let Some(tx) = game_loop_tx.clone() {
+                match msg.action_type {
+                    ActionType::CheckCall => {
+                        // talk to game loop, forward message
+
+                        let _ = tx.send(GameAction::Pot(msg.id, PotAction::CheckCall));
+                        continue;
+                    },
+                    ActionType::BetRaise => {
+                        // talk to game loop, forward message
+                        // turn Value into a GameAction then forward
+                        let action: Result<BetAction, _> = serde_json::from_value(Value::Object(msg.data));
+                        if let Ok(action) = action {
+                            let _ = tx.send(GameAction::Pot(msg.id, PotAction::BetRaise(action)));
+                        } else {
+                            let res = PersonalBetRaiseResponse::new(msg.id, StatusCode::MalformedRequest, 0);
+                            let _ = res_tx.send(GameResponse::SingleResponse(msg.id, SingleResponse::BetRaiseResponse(res)));
+                        }
+                        continue;
+                    },
+                    ActionType::Fold => {
+                        // talk to game loop, forward message
+
+                        let _ = tx.send(GameAction::Pot(msg.id, PotAction::Fold));
+
+                        continue;
+                    },
+                    ActionType::Draw => {
+                        // talk to game loop, forward message
+                        // turn Value into a GameAction then forward
+
+                        let action: Result<DrawAction, _> = serde_json::from_value(Value::Object(msg.data));
+                        if let Ok(action) = action {
+                            let _ = tx.send(GameAction::Draw(msg.id, action));
+                        } else {
+                            let res = PersonalDrawResponse::new(msg.id, StatusCode::MalformedRequest, Vec::new());
+                            let _ = res_tx.send(GameResponse::SingleResponse(msg.id, SingleResponse::DrawResponse(res)));
+                        }
+                        continue;
+                    },
+                    ActionType::RunIt => {
+                        let action: Result<RunItAction, _> = serde_json::from_value(Value::Object(msg.data));
+                        if let Ok(action) = action {
+                            let _ = tx.send(GameAction::RunIt(msg.id, action));
+                        } else {
+                            let res = PersonalRunItResponse::new(msg.id, StatusCode::MalformedRequest);
+                            let _ = res_tx.send(GameResponse::SingleResponse(msg.id, SingleResponse::RunItResponse(res)));
+                        }
+                        continue;
+                    },
+                    ActionType::Showdown => {
+                        let action: Result<ShowdownAction, _> = serde_json::from_value(Value::Object(msg.data));
+                        if let Ok(action) = action {
+                            let _ = tx.send(GameAction::Showdown(msg.id, action));
+                        } else {
+                            let res = PersonalShowHandResponse::new(msg.id, StatusCode::MalformedRequest);
+                            let _ = res_tx.send(GameResponse::SingleResponse(msg.id, SingleResponse::ShowHandResponse(res)));
+                        }
+                        continue;
+                    },
+                    _ => {}
+                }
+            }
+            match msg.action_type {
+                ActionType::StartGame => {
+                    let is_host = table.lock().unwrap().claim_host(msg.id);
+                    if is_host && game_loop_tx.clone().map_or_else(|| true, |tx| tx.is_closed()) {
+                        let t = table.clone();
+                        let mut table = table.lock().unwrap();
+                        table.start_next_hand = true;
+
+                        let (tx, rx) = unbounded_channel::<GameAction>();
+                        game_loop_tx = Some(tx);
+
+                        let mes_res_tx = res_tx.clone();
+                        tokio::spawn(async move {
+                            Table::game_loop(t, rx, mes_res_tx).await;
+                        });
+                    }
+
+                    // let res = GameActionResponse{
+
+                    // }
+                    // res_tx.send(message);
+                },
+                ActionType::UndoAction | ActionType::RewindStreet => {
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.undo(msg.id, matches!(msg.action_type, ActionType::RewindStreet))
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::PauseGame => {
+                    // check authorization jwt by looking up uuid
+
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.pause(Instant::now())
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::ResumeGame => {
+                    // check authorization jwt by looking up uuid
+
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.resume(Instant::now())
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::StopGame => {
+                    // check authorization jwt by looking up uuid
+
+                    let mut table = table.lock().unwrap();
+                    table.start_next_hand = false;
+                },
+                ActionType::SitDown => {
+                    let action: Result<SitDownAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.sit_down(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::SitDownResponse(PersonalSitDownResponse::new(msg.id, StatusCode::MalformedRequest, None)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::StandUp => {
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.stand_up(msg.id)
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::ReserveSeat => {
+                    let action: Result<ReserveSeatAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.reserve_seat(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::ReserveSeatResponse(PersonalReserveSeatResponse::new(msg.id, StatusCode::MalformedRequest)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::JoinWaitingList => {
+                    let action: Result<WaitingListAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.join_waiting_list(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::WaitingListResponse(PersonalWaitingListResponse::new(msg.id, StatusCode::MalformedRequest, None)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::LeaveWaitingList => {
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.leave_waiting_list(msg.id)
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::SitOut => {
+                    let responses = {
+                        let mut table = table.lock().unwrap();
+                        table.set_away(msg.id, true)
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::UpdateSettings => {
+                    let action: Result<PlayerSettingsAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.update_settings(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::SettingsResponse(PersonalSettingsResponse::new(msg.id, StatusCode::MalformedRequest)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::UpdateTableConfig => {
+                    let config: Result<TableConfig, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = match config {
+                        Ok(config) => {
+                            let mut table = table.lock().unwrap();
+                            table.update_config(msg.id, config)
+                        },
+                        Err(err) => {
+                            let res = PersonalTableConfigResponse::new(msg.id, StatusCode::MalformedRequest, Some(err.to_string()));
+                            vec![GameResponse::SingleResponse(msg.id, SingleResponse::TableConfigResponse(res))]
+                        },
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::AddChips => {
+                    let action: Result<AddChipsAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.add_chips(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::AddChipsResponse(PersonalAddChipsResponse::new(msg.id, StatusCode::MalformedRequest, false)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::GetLedger => {
+                    let responses = {
+                        let table = table.lock().unwrap();
+                        table.get_ledger(msg.id)
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::ShowCards => {
+                    let action: Result<ShowCardsAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.show_cards(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::ShowHandResponse(PersonalShowHandResponse::new(msg.id, StatusCode::MalformedRequest)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::VoteBombPot => {
+                    let action: Result<BombPotVoteAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.vote_bomb_pot(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::BombPotVoteResponse(PersonalBombPotVoteResponse::new(msg.id, StatusCode::MalformedRequest)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                ActionType::SitIn => {
+                    let action: Result<SitInAction, _> = serde_json::from_value(Value::Object(msg.data));
+                    let responses = if let Ok(action) = action {
+                        let mut table = table.lock().unwrap();
+                        table.sit_in(msg.id, action)
+                    } else {
+                        vec![GameResponse::SingleResponse(msg.id, SingleResponse::AwayResponse(PersonalAwayResponse::new(msg.id, StatusCode::MalformedRequest, true)))]
+                    };
+                    responses.into_iter().for_each(|res| { let _ = res_tx.send(res); });
+                },
+                _ => {
+                    // This means we got a GameAction and no game loop is running
+                    // TODO: send some response idk
+                }
+            }
+        }
+    }
+
+    pub async fn game_loop(table: Arc<Mutex<Table>>, mut main_loop_rx: UnboundedReceiver<GameAction>, res_tx: UnboundedSender<GameActionResponse>) {
+        loop {
+            let responses = {
+                let mut table = table.lock().unwrap();
+
+                if !table.start_next_hand || table.is_halted {
+                    break
+                }
+
+                if table.is_paused {
+                    Err(format!("Table is paused"))
+                } else {
+                    table.start_hand()
+                }
+            };
+
+            match responses {
+                Ok(responses) => {
+                    for res in responses {
+                        let _ = res_tx.send(res);
+                    }
+                },
+                Err(_) => {
+                    // not able to deal a hand yet, so check again later
+                    tokio::time::sleep(WAIT_FOR_NEXT_HAND).await;
+                    continue;
+                }
+            }
+
+            loop {
+                let timeout = {
+                    let table = table.lock().unwrap();
+                    if !table.is_hand_in_progress && table.showdown.is_none() {
+                        break;
+                    }
+                    // a frozen clock still needs to be looked at again in case the table gets resumed
+                    table.get_clock_timeout(Instant::now()).unwrap_or(WAIT_FOR_NEXT_HAND)
+                };
+
+                let responses = match tokio::time::timeout(timeout, main_loop_rx.recv()).await {
+                    Ok(Some(action)) => {
+                        let mut table = table.lock().unwrap();
+                        table.handle_game_action(action)
+                    },
+                    Ok(None) => return,
+                    Err(_) => {
+                        let mut table = table.lock().unwrap();
+                        table.check_action_clock(Instant::now())
+                    },
+                };
+
+                for res in responses {
+                    let _ = res_tx.send(res);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::poker::{StraddleType, BombPotSchedule, GameType, RakeSettings};
+    use crate::poker::games::{FiveCardDraw, TexasHoldem};
+
+    use super::*;
+
+    // Deals nothing and ranks players by a fixed strength
+    struct MockGame {
+        streets: Vec<Street>,
+        strengths: HashMap<usize, u32>,
+        runout_strengths: Vec<HashMap<usize, u32>>,    // strengths on each runout, runouts are dealt as a single card
+    }
+
+    fn get_runout_cards() -> Vec<Card> {
+        Card::vec_from_str("2s3s4s").unwrap()
+    }
+
+    impl GameVariation for MockGame {
+        fn get_min_player_count(& self) -> usize {
+            2
+        }
+
+        fn get_max_player_count(& self) -> usize {
+            4
+        }
+
+        fn start_normal(&mut self, _players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+            Ok(())
+        }
+
+        fn get_streets(& self) -> Vec<Street> {
+            self.streets.clone()
+        }
+
+        fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+            Ok(Vec::new())
+        }
+
+        fn evaluate_all_hands(& self, players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+            vec![players.iter()
+                .filter(|(_, p)| p.is_in_hand)
+                .map(|(pos, _)| (*pos, Rank::High(HighRank::new(self.strengths[pos]))))
+                .collect()]
+        }
+
+        fn get_max_runouts(& self) -> usize {
+            self.runout_strengths.len().max(1)
+        }
+
+        fn deal_runouts(&mut self, count: usize) -> Result<Vec<Vec<Card>>, &str> {
+            Ok(get_runout_cards().into_iter().take(count).map(|card| vec![card]).collect())
+        }
+
+        fn evaluate_runout(& self, players: &HashMap<usize, Player>, runout: &Vec<Card>) -> Vec<HashMap<usize, Rank>> {
+            let runout_idx = get_runout_cards().iter().position(|card| *card == runout[0]).unwrap();
+            vec![players.iter()
+                .filter(|(_, p)| p.is_in_hand)
+                .map(|(pos, _)| (*pos, Rank::High(HighRank::new(self.runout_strengths[runout_idx][pos]))))
+                .collect()]
+        }
+    }
+
+    fn setup_table(stacks: &[u64], streets: Vec<Street>, strengths: HashMap<usize, u32>) -> (Table, Vec<Uuid>) {
+        let mut table = Table::new();
+        table.game = Box::new(MockGame { streets: streets, strengths: strengths, runout_strengths: Vec::new() });
+        table.config.seat_count = table.game.get_max_player_count();
+
+        let mut ids = Vec::new();
+        for (pos, stack) in stacks.iter().enumerate() {
+            let id = Uuid::new_v4();
+            table.players.insert(pos, Player::new(pos, format!("Player {}", pos), *stack));
+            table.seats.insert(id, pos);
+            ids.push(id);
+        }
+        (table, ids)
+    }
+
+    fn stacks(table: &Table) -> Vec<u64> {
+        (0..table.players.len()).map(|pos| table.players[&pos].stack).collect()
+    }
+
+    #[test]
+    fn fold_to_big_blind() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(3, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[3], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(vec![200, 199, 201, 200], stacks(&table));
+    }
+
+    #[test]
+    fn reject_out_of_turn_action() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+
+        assert!(table.players[&1].is_in_hand);
+        assert_eq!(0, table.action_idx);
+    }
+
+    #[test]
+    fn big_blind_gets_option() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        assert_eq!(0, table.street_idx);
+        assert_eq!(2, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        // postflop action starts left of the button
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn play_hand_to_showdown() {
+        let strengths = HashMap::from([(0, 100), (1, 300), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 6 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        assert_eq!(1, table.street_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::BetRaise(BetAction { amount: 10 })));
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(vec![194, 222, 184], stacks(&table));
+    }
+
+    #[test]
+    fn all_in_runs_out_board() {
+        let strengths = HashMap::from([(0, 100), (1, 300)]);
+        let (mut table, ids) = setup_table(&[50, 200], vec![Street::Betting, Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 50 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(vec![0, 250], stacks(&table));
+    }
+
+    #[test]
+    fn holdem_deals_board_each_street() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        table.game = Box::new(TexasHoldem::new());
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.players.values().all(|p| p.get_hand().len() == 2));
+
+        for board_size in [0, 3, 4, 5] {
+            assert_eq!(board_size, table.game.get_board().len());
+            for _ in 0..ids.len() {
+                table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+            }
+        }
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(600, stacks(&table).iter().sum::<u64>());
+    }
+
+    #[test]
+    fn holdem_board_dealt_after_run_it_decided() {
+        let (mut table, ids) = setup_table(&[100, 100], Vec::new(), HashMap::new());
+        table.game = Box::new(TexasHoldem::new());
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        assert!(table.run_it_votes.is_some());
+        assert!(table.game.get_board().is_empty());
+
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 1 }));
+        table.handle_game_action(GameAction::RunIt(ids[1], RunItAction { times: 1 }));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(5, table.game.get_board().len());
+        assert_eq!(200, stacks(&table).iter().sum::<u64>());
+    }
+
+    #[test]
+    fn omaha_table_is_pot_limit() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        let mut config = TableConfig::new();
+        config.game_type = GameType::Omaha;
+        table.apply_config(config);
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.players.values().all(|p| p.get_hand().len() == 4));
+
+        // the pot is 3 after the blinds, so the most the first player can raise to is 7
+        let first = ids[table.action_idx];
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 8 })));
+        assert_eq!(first, ids[table.action_idx]);
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 7 })));
+        assert_ne!(first, ids[table.action_idx]);
+    }
+
+    #[test]
+    fn draw_street_order() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Draw, Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        assert_eq!(Some(Street::Draw), table.get_street());
+        assert_eq!(1, table.action_idx);
+        assert_eq!(0, table.last_to_draw_idx);
+
+        table.handle_game_action(GameAction::Draw(ids[1], DrawAction { cards_to_discard: Vec::new() }));
+        table.handle_game_action(GameAction::Draw(ids[2], DrawAction { cards_to_discard: Vec::new() }));
+        assert_eq!(Some(Street::Draw), table.get_street());
+
+        table.handle_game_action(GameAction::Draw(ids[0], DrawAction { cards_to_discard: Vec::new() }));
+        assert_eq!(Some(Street::Betting), table.get_street());
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn triple_draw_is_fixed_limit() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        let mut config = TableConfig::new();
+        config.game_type = GameType::TripleDrawDeuceToSeven;
+        table.apply_config(config);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(7, table.game.get_streets().len());
+
+        // raises are one small bet (the big blind) before the second draw
+        let first = ids[table.action_idx];
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 6 })));
+        assert_eq!(first, ids[table.action_idx]);
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 4 })));
+        assert_ne!(first, ids[table.action_idx]);
+        for _ in 0..2 {
+            table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+        }
+
+        for street_idx in [1, 2, 3] {
+            assert_eq!(street_idx, table.street_idx);
+            for _ in 0..3 {
+                let street = table.get_street();
+                let id = ids[table.action_idx];
+                if street == Some(Street::Draw) {
+                    table.handle_game_action(GameAction::Draw(id, DrawAction { cards_to_discard: Vec::new() }));
+                } else {
+                    table.handle_game_action(GameAction::Pot(id, PotAction::CheckCall));
+                }
+            }
+        }
+
+        // after the second draw, bets are the big bet
+        assert_eq!(4, table.street_idx);
+        let first = ids[table.action_idx];
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 2 })));
+        assert_eq!(first, ids[table.action_idx]);
+        table.handle_game_action(GameAction::Pot(first, PotAction::BetRaise(BetAction { amount: 4 })));
+        assert_ne!(first, ids[table.action_idx]);
+    }
+
+    fn get_draw_responses(responses: &Vec<GameResponse>) -> Vec<serde_json::Value> {
+        responses.iter()
+            .filter_map(|response| match response {
+                GameResponse::Multicast(_, Multicast::DrawResponse(draw)) => serde_json::to_value(draw).ok(),
+                GameResponse::SingleResponse(_, SingleResponse::DrawResponse(draw)) => serde_json::to_value(draw).ok(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn five_card_draw_hand() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        table.game = Box::new(FiveCardDraw::new());
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.players.values().all(|p| p.get_hand().len() == 5));
+        for id in &ids {
+            table.handle_game_action(GameAction::Pot(*id, PotAction::CheckCall));
+        }
+        assert_eq!(Some(Street::Draw), table.get_street());
+
+        // a card from someone else's hand is rejected and the player still has to draw
+        let drawing = table.action_idx;
+        let other_card = table.players[&((drawing + 1) % 3)].get_hand()[0];
+        let responses = table.handle_game_action(GameAction::Draw(ids[drawing], DrawAction { cards_to_discard: vec![other_card] }));
+        assert_eq!(vec![json!({"reqId": ids[drawing], "status": 3, "newCards": []})], get_draw_responses(&responses));
+        assert_eq!(drawing, table.action_idx);
+
+        let discards = table.players[&drawing].get_hand()[..2].to_vec();
+        let kept = table.players[&drawing].get_hand()[2..].to_vec();
+        let responses = table.handle_game_action(GameAction::Draw(ids[drawing], DrawAction { cards_to_discard: discards.clone() }));
+        let draw_responses = get_draw_responses(&responses);
+        assert_eq!(json!({"position": drawing, "discardCount": 2, "cardsRecieved": 2}), draw_responses[0]);
+        let new_cards: Vec<Card> = serde_json::from_value(draw_responses[1]["newCards"].clone()).unwrap();
+        assert_eq!(2, new_cards.len());
+        assert!(table.players[&drawing].get_hand().iter().all(|card| kept.contains(card) || new_cards.contains(card)));
+
+        for _ in 0..2 {
+            table.handle_game_action(GameAction::Draw(ids[table.action_idx], DrawAction { cards_to_discard: Vec::new() }));
+        }
+        assert_eq!(Some(Street::Betting), table.get_street());
+        for _ in 0..3 {
+            table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+        }
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(600, stacks(&table).iter().sum::<u64>());
+    }
+
+    #[test]
+    fn stud_hand_brings_in_and_acts_by_cards_showing() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        let mut config = TableConfig::new();
+        config.game_type = GameType::SevenCardStud;
+        config.seat_count = 8;
+        config.ante_amt = 1;
+        table.apply_config(config);
+
+        // nobody posts a blind, everyone antes and the lowest door card brings in
+        assert!(table.start_hand().is_ok());
+        assert!(table.players.values().all(|p| p.get_hand().len() == 3 && p.get_up_cards().len() == 1));
+        let bring_in = table.game.get_bring_in_idx(&table.players).unwrap();
+        assert_eq!(Some(&LoggedAction::BringIn { position: bring_in, amount: 1 }), table.action_log.last());
+        for pos in 0..3 {
+            let bet = if pos == bring_in { 1 } else { 0 };
+            assert_eq!(&(199, bet), table.pot.get_player_stack_bet(&pos).unwrap());
+        }
+        assert_eq!((bring_in + 1) % 3, table.action_idx);
+
+        // completing the bring-in is a raise up to the small bet
+        let first = table.action_idx;
+        table.handle_game_action(GameAction::Pot(ids[first], PotAction::BetRaise(BetAction { amount: 3 })));
+        assert_eq!(first, table.action_idx);
+        table.handle_game_action(GameAction::Pot(ids[first], PotAction::BetRaise(BetAction { amount: 2 })));
+        table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+        let responses = table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+
+        // everyone is sent their hand with the 4th street card, and the best hand showing acts first
+        let hands_sent = responses.iter()
+            .filter(|res| matches!(res, GameResponse::SingleResponse(_, SingleResponse::StartingHandResponse(_))))
+            .count();
+        assert_eq!(3, hands_sent);
+        assert_eq!(1, table.street_idx);
+        assert!(table.players.values().all(|p| p.get_hand().len() == 4 && p.get_up_cards().len() == 2));
+        assert_eq!(table.game.get_first_to_act(&table.players, 1), Some(table.action_idx));
+    }
+
+    #[test]
+    fn button_ante_replaces_blinds() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        let mut config = TableConfig::new();
+        config.game_type = GameType::ShortDeckHoldem;
+        config.ante_amt = 3;
+        config.is_button_ante = true;
+        table.apply_config(config);
+
+        // only the button antes and action starts left of them, with the button acting last
+        assert!(table.start_hand().is_ok());
+        let btn_idx = table.btn_idx;
+        assert_eq!(vec![LoggedAction::Posted { position: btn_idx, ante: 3, live: 0, dead: 0 }], table.action_log[1..].to_vec());
+        assert_eq!(3, table.pot.get_total_pot());
+        assert_eq!((btn_idx + 1) % 3, table.action_idx);
+        assert!(table.players.values().flat_map(|p| p.get_hand().iter()).all(|card| card.value as usize >= playing_cards::core::Value::Six as usize));
+
+        for _ in 0..2 {
+            table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+            assert_eq!(0, table.street_idx);
+        }
+        assert_eq!(btn_idx, table.action_idx);
+        table.handle_game_action(GameAction::Pot(ids[table.action_idx], PotAction::CheckCall));
+        assert_eq!(1, table.street_idx);
+        assert_eq!(3, table.game.get_board().len());
+    }
+
+    fn sit_down_action(position: usize, buy_in: u64) -> SitDownAction {
+        SitDownAction {
+            position: position,
+            name: format!("Player {}", position),
+            buy_in: buy_in,
+            wait_for_bb: false,
+        }
+    }
+
+    #[test]
+    fn sit_down_validation() {
+        let (mut table, ids) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        let id = Uuid::new_v4();
+
+        table.sit_down(id, sit_down_action(1, 200));
+        table.sit_down(id, sit_down_action(4, 200));
+        table.sit_down(id, sit_down_action(2, 0));
+        assert_eq!(None, table.get_position(&id));
+
+        table.sit_down(ids[0], sit_down_action(2, 200));
+        assert_eq!(Some(0), table.get_position(&ids[0]));
+
+        table.sit_down(id, sit_down_action(2, 150));
+        assert_eq!(Some(2), table.get_position(&id));
+        assert_eq!(150, table.players[&2].stack);
+    }
+
+    #[test]
+    fn reserved_seat_is_held() {
+        let (mut table, _) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        let reserver = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        table.reserve_seat(reserver, ReserveSeatAction { position: 3 });
+
+        table.sit_down(other, sit_down_action(3, 200));
+        assert_eq!(None, table.get_position(&other));
+
+        table.sit_down(reserver, sit_down_action(3, 200));
+        assert_eq!(Some(3), table.get_position(&reserver));
+        assert!(table.reserved_seats.is_empty());
+    }
+
+    #[test]
+    fn expired_reservation_frees_seat() {
+        let (mut table, _) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        let reserver = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        table.reserved_seats.insert(3, (reserver, Instant::now() - RESERVE_SEAT_TIMEOUT));
+
+        table.sit_down(other, sit_down_action(3, 200));
+        assert_eq!(Some(3), table.get_position(&other));
+    }
+
+    #[test]
+    fn waiting_list_only_when_full() {
+        let (mut table, _) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let waiting = Uuid::new_v4();
+
+        table.join_waiting_list(waiting, WaitingListAction { name: "Waiting".to_string(), buy_in: 100 });
+        assert!(table.waiting_list.is_empty());
+
+        table.reserve_seat(Uuid::new_v4(), ReserveSeatAction { position: 3 });
+        table.join_waiting_list(waiting, WaitingListAction { name: "Waiting".to_string(), buy_in: 100 });
+        assert_eq!(1, table.waiting_list.len());
+    }
+
+    #[test]
+    fn stand_up_seats_waiting_list_in_order() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        table.join_waiting_list(first, WaitingListAction { name: "First".to_string(), buy_in: 100 });
+        table.join_waiting_list(second, WaitingListAction { name: "Second".to_string(), buy_in: 300 });
+
+        table.stand_up(ids[2]);
+
+        assert_eq!(None, table.get_position(&ids[2]));
+        assert_eq!(Some(2), table.get_position(&first));
+        assert_eq!(100, table.players[&2].stack);
+        assert_eq!(1, table.waiting_list.len());
+    }
+
+    #[test]
+    fn waiting_list_buy_in_checked_when_seated() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        table.join_waiting_list(first, WaitingListAction { name: "First".to_string(), buy_in: 100 });
+        table.join_waiting_list(second, WaitingListAction { name: "Second".to_string(), buy_in: 300 });
+        table.config.min_buy_in = 200;
+
+        let responses = table.stand_up(ids[2]);
+        let rejected = responses.iter().any(|res| matches!(res, GameResponse::SingleResponse(id, SingleResponse::WaitingListResponse(_)) if *id == first));
+        assert!(rejected);
+        assert_eq!(None, table.get_position(&first));
+        assert_eq!(Some(2), table.get_position(&second));
+        assert!(table.waiting_list.is_empty());
+    }
+
+    #[test]
+    fn stand_up_during_hand() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.action_idx);
+
+        // player 1 leaves out of turn and forfeits the small blind
+        table.stand_up(ids[1]);
+        assert_eq!(Some(1), table.get_position(&ids[1]));
+        assert!(table.is_hand_in_progress);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(None, table.get_position(&ids[1]));
+        assert!(!table.players.contains_key(&1));
+        assert_eq!(201, table.players[&2].stack);
+    }
+
+    #[test]
+    fn stand_up_out_of_turn_does_not_close_action() {
+        let strengths = HashMap::from([(0, 0), (1, 0), (2, 0), (3, 0)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting, Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        for pos in [3, 0, 1, 2] {
+            table.handle_game_action(GameAction::Pot(ids[pos], PotAction::CheckCall));
+        }
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+
+        // the big blind leaves before anyone has acted on the flop
+        table.stand_up(ids[2]);
+        assert_eq!(1, table.action_idx);
+
+        // everyone else still gets to act before the street is over
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        assert_eq!(1, table.street_idx);
+        assert_eq!(3, table.action_idx);
+        table.handle_game_action(GameAction::Pot(ids[3], PotAction::CheckCall));
+        assert_eq!(1, table.street_idx);
+        assert_eq!(0, table.action_idx);
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        assert_eq!(2, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn blinds_rotate_between_hands() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        let mut expected_btn = [0, 1, 2, 0].iter();
+        for _ in 0..4 {
+            assert!(table.start_hand().is_ok());
+            assert_eq!(*expected_btn.next().unwrap(), table.btn_idx);
+
+            // everyone folds to the big blind
+            while table.is_hand_in_progress {
+                let id = ids[table.action_idx];
+                table.handle_game_action(GameAction::Pot(id, PotAction::Fold));
+            }
+        }
+        assert_eq!(vec![200, 199, 201], stacks(&table));
+    }
+
+    #[test]
+    fn heads_up_button_acts_first_preflop() {
+        let (mut table, ids) = setup_table(&[200, 200], vec![Street::Betting, Street::Betting], HashMap::from([(0, 0), (1, 0)]));
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.btn_idx);
+        assert_eq!(1, table.big_blind_idx);
+        assert_eq!(0, table.action_idx);
+        assert_eq!(&(200, 1), table.pot.get_player_stack_bet(&0).unwrap());
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        // big blind acts first after the flop
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn dead_small_blind_after_big_blind_leaves() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        while table.is_hand_in_progress {
+            let id = ids[table.action_idx];
+            table.handle_game_action(GameAction::Pot(id, PotAction::Fold));
+        }
+
+        table.stand_up(ids[2]);
+        assert!(table.start_hand().is_ok());
+
+        assert_eq!(1, table.btn_idx);
+        assert_eq!(3, table.big_blind_idx);
+        assert!(table.blind_positions.as_ref().unwrap().is_sb_dead);
+        assert_eq!(&(199, 0), table.pot.get_player_stack_bet(&1).unwrap());
+    }
+
+    #[test]
+    fn time_out_folds_facing_bet() {
+        let (mut table, _) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.action_idx);
+
+        assert!(table.check_action_clock(Instant::now() + Duration::from_secs(10)).is_empty());
+        assert!(table.players[&0].is_in_hand);
+
+        table.check_action_clock(Instant::now() + Duration::from_secs(31));
+        assert!(!table.players[&0].is_in_hand);
+        assert!(table.players[&0].is_away);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn time_out_checks_when_legal() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        // big blind runs out of time with their option
+        table.check_action_clock(Instant::now() + Duration::from_secs(31));
+        assert!(table.players[&2].is_in_hand);
+        assert!(table.players[&2].is_away);
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn time_bank_used_before_time_out() {
+        let (mut table, _) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        table.players.get_mut(&0).unwrap().time_bank = Duration::from_secs(20);
+
+        assert!(table.start_hand().is_ok());
+
+        let responses = table.check_action_clock(Instant::now() + Duration::from_secs(31));
+        assert_eq!(1, responses.len());
+        assert!(matches!(responses[0], GameResponse::Broadcast(Broadcast::ActionClock(_))));
+        assert!(table.clock.is_using_time_bank());
+        assert!(table.players[&0].is_in_hand);
+
+        table.check_action_clock(Instant::now() + Duration::from_secs(51));
+        assert!(!table.players[&0].is_in_hand);
+        assert_eq!(Duration::ZERO, table.players[&0].time_bank);
+    }
+
+    #[test]
+    fn paused_clock_does_not_expire() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.pause(Instant::now());
+
+        assert_eq!(None, table.get_clock_timeout(Instant::now()));
+        assert!(table.check_action_clock(Instant::now() + Duration::from_secs(1000)).is_empty());
+
+        // nobody can act while the hand is paused
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        assert!(table.players[&0].is_in_hand);
+
+        table.resume(Instant::now());
+        assert!(table.get_clock_timeout(Instant::now()).unwrap() > Duration::from_secs(29));
+    }
+
+    #[test]
+    fn away_player_sits_out_next_hand() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.check_action_clock(Instant::now() + Duration::from_secs(31));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        assert!(!table.is_hand_in_progress);
+
+        assert!(table.start_hand().is_ok());
+        assert!(!table.players[&0].is_in_hand);
+
+        table.sit_in(ids[0], SitInAction { wait_for_bb: false });
+        assert!(!table.players[&0].is_away);
+    }
+
+    fn fold_to_big_blind_by_id(table: &mut Table, ids: &HashMap<usize, Uuid>) {
+        while table.is_hand_in_progress {
+            let id = ids[&table.action_idx];
+            table.handle_game_action(GameAction::Pot(id, PotAction::Fold));
+        }
+    }
+
+    #[test]
+    fn new_player_posts_big_blind() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let mut ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        fold_to_big_blind_by_id(&mut table, &ids);
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        // big blind already passed seat 3, so they have to post to play now
+        let id = Uuid::new_v4();
+        table.sit_down(id, sit_down_action(3, 200));
+        ids.insert(3, id);
+        assert!(table.players[&3].missed_bb);
+        assert!(!table.players[&3].missed_sb);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(1, table.big_blind_idx);
+        assert!(table.players[&3].is_in_hand);
+        assert!(!table.players[&3].owes_blinds());
+        assert_eq!(&(200, 2), table.pot.get_player_stack_bet(&3).unwrap());
+    }
+
+    #[test]
+    fn new_player_waits_for_big_blind() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let mut ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        let id = Uuid::new_v4();
+        table.sit_down(id, SitDownAction { position: 3, name: "Player 3".to_string(), buy_in: 200, wait_for_bb: true });
+        ids.insert(3, id);
+
+        // big blind moves onto seat 3, so they get dealt in
+        assert!(table.start_hand().is_ok());
+        assert_eq!(3, table.big_blind_idx);
+        assert!(table.players[&3].is_in_hand);
+        assert!(!table.players[&3].owes_blinds());
+    }
+
+    #[test]
+    fn waiting_player_is_not_dealt_in_before_big_blind() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let mut ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        // seat 3 sits down right after the big blind passed it
+        let id = Uuid::new_v4();
+        table.sit_down(id, SitDownAction { position: 3, name: "Player 3".to_string(), buy_in: 200, wait_for_bb: true });
+        ids.insert(3, id);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(1, table.big_blind_idx);
+        assert!(!table.players[&3].is_in_hand);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        assert!(!table.players[&3].is_in_hand);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(3, table.big_blind_idx);
+        assert!(table.players[&3].is_in_hand);
+        assert_eq!(&(200, 2), table.pot.get_player_stack_bet(&3).unwrap());
+    }
+
+    #[test]
+    fn returning_player_posts_missed_blinds() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        // seat 3 sits out and the big blind skips over them
+        table.set_away(ids[&3], true);
+        assert!(table.start_hand().is_ok());
+        assert_eq!(0, table.big_blind_idx);
+        assert!(table.players[&3].missed_sb);
+        assert!(table.players[&3].missed_bb);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        table.sit_in(ids[&3], SitInAction { wait_for_bb: false });
+        assert!(table.start_hand().is_ok());
+        assert_eq!(1, table.big_blind_idx);
+        assert!(table.players[&3].is_in_hand);
+        assert!(!table.players[&3].owes_blinds());
+
+        // dead small blind comes out of the stack, live big blind is the bet
+        assert_eq!(&(199, 2), table.pot.get_player_stack_bet(&3).unwrap());
+    }
+
+    #[test]
+    fn failed_hand_start_leaves_blinds_in_place() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        // the pot refuses to reset, so the hand never starts
+        table.sb_amt = 0;
+        table.set_away(ids[&3], true);
+        for _ in 0..2 {
+            assert!(table.start_hand().is_err());
+            assert!(!table.is_hand_in_progress);
+            assert_eq!(0, table.btn_idx);
+            assert_eq!(2, table.big_blind_idx);
+            assert!(!table.players[&3].missed_sb);
+            assert!(!table.players[&3].missed_bb);
+            assert_eq!(vec![200, 199, 201, 200], stacks(&table));
+        }
+
+        // once it can start the blinds move on by one seat only
+        table.sb_amt = 1;
+        assert!(table.start_hand().is_ok());
+        assert_eq!(1, table.btn_idx);
+        assert_eq!(0, table.big_blind_idx);
+        assert!(table.players[&3].missed_sb);
+        assert!(table.players[&3].missed_bb);
+    }
+
+    #[test]
+    fn dead_small_blind_is_owed() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let ids: HashMap<usize, Uuid> = ids.into_iter().enumerate().collect();
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(2, table.big_blind_idx);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        // big blind sits out for a single hand, missing only the small blind
+        table.set_away(ids[&2], true);
+        assert!(table.start_hand().is_ok());
+        assert!(table.blind_positions.as_ref().unwrap().is_sb_dead);
+        assert!(table.players[&2].missed_sb);
+        assert!(!table.players[&2].missed_bb);
+        fold_to_big_blind_by_id(&mut table, &ids);
+
+        table.sit_in(ids[&2], SitInAction { wait_for_bb: false });
+        assert!(table.start_hand().is_ok());
+        assert!(table.players[&2].is_in_hand);
+        assert!(!table.players[&2].owes_blinds());
+        // 201 after winning the first hand, minus the dead small blind
+        assert_eq!(&(200, 0), table.pot.get_player_stack_bet(&2).unwrap());
+    }
+
+    #[test]
+    fn under_the_gun_straddle_gets_option() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        table.set_straddle_settings(StraddleSettings { straddle_type: StraddleType::UnderTheGun, max_straddles: 1 });
+        table.update_settings(ids[3], PlayerSettingsAction { will_straddle: Some(true), play_in_bomb_pots: None, auto_muck: None, auto_rebuy: None });
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(vec![3], table.straddle_positions);
+        assert_eq!(&(200, 4), table.pot.get_player_stack_bet(&3).unwrap());
+        assert_eq!(0, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        assert_eq!(0, table.street_idx);
+        assert_eq!(3, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[3], PotAction::CheckCall));
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn button_straddle_action_starts_at_small_blind() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        table.set_straddle_settings(StraddleSettings { straddle_type: StraddleType::Button, max_straddles: 2 });
+        for id in &ids {
+            table.update_settings(*id, PlayerSettingsAction { will_straddle: Some(true), play_in_bomb_pots: None, auto_muck: None, auto_rebuy: None });
+        }
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(vec![0], table.straddle_positions);
+        assert_eq!(1, table.action_idx);
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[3], PotAction::BetRaise(BetAction { amount: 8 })));
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::Fold));
+
+        assert_eq!(1, table.street_idx);
+        assert_eq!(vec![0, 3], table.get_players_in_hand());
+    }
+
+    #[test]
+    fn straddle_skipped_while_owing_blinds() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        table.set_straddle_settings(StraddleSettings { straddle_type: StraddleType::UnderTheGun, max_straddles: 1 });
+        table.update_settings(ids[3], PlayerSettingsAction { will_straddle: Some(true), play_in_bomb_pots: None, auto_muck: None, auto_rebuy: None });
+        table.players.get_mut(&3).unwrap().missed_bb = true;
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.straddle_positions.is_empty());
+        assert_eq!(&(200, 2), table.pot.get_player_stack_bet(&3).unwrap());
+    }
+
+    #[test]
+    fn vote_for_bomb_pot() {
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting, Street::Betting, Street::Betting], HashMap::new());
+
+        table.vote_bomb_pot(ids[0], BombPotVoteAction { vote: true });
+        table.vote_bomb_pot(ids[1], BombPotVoteAction { vote: true });
+        table.vote_bomb_pot(ids[1], BombPotVoteAction { vote: false });
+        table.vote_bomb_pot(ids[2], BombPotVoteAction { vote: true });
+        assert!(!table.is_next_hand_bomb);
+
+        table.vote_bomb_pot(ids[3], BombPotVoteAction { vote: true });
+        assert!(table.is_next_hand_bomb);
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.pot.is_bomb_pot());
+        assert!(!table.is_next_hand_bomb);
+        assert!(table.bomb_pot_votes.is_empty());
+
+        // everyone is in for the ante and nobody posts a blind
+        assert_eq!(&(190, 0), table.pot.get_player_stack_bet(&2).unwrap());
+
+        // preflop is skipped, so action starts left of the button
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn votes_disabled() {
+        let (mut table, ids) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        table.set_bomb_pot_settings(BombPotSettings { schedule: BombPotSchedule::Never, ante_amt: 10, allow_votes: false });
+
+        table.vote_bomb_pot(ids[0], BombPotVoteAction { vote: true });
+        table.vote_bomb_pot(ids[1], BombPotVoteAction { vote: true });
+        assert!(!table.is_next_hand_bomb);
+    }
+
+    #[test]
+    fn scheduled_bomb_pot_every_n_hands() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        table.set_bomb_pot_settings(BombPotSettings { schedule: BombPotSchedule::EveryHands(3), ante_amt: 5, allow_votes: true });
+
+        let mut bomb_pots = Vec::new();
+        for _ in 0..6 {
+            assert!(table.start_hand().is_ok());
+            bomb_pots.push(table.pot.is_bomb_pot());
+            while table.is_hand_in_progress {
+                let id = ids[table.action_idx];
+                table.handle_game_action(GameAction::Pot(id, PotAction::Fold));
+            }
+        }
+        assert_eq!(vec![false, false, true, false, false, true], bomb_pots);
+    }
+
+    #[test]
+    fn opted_out_player_skips_bomb_pot() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        table.update_settings(ids[1], PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: Some(false), auto_muck: None, auto_rebuy: None });
+        table.is_next_hand_bomb = true;
+
+        assert!(table.start_hand().is_ok());
+        assert!(table.pot.is_bomb_pot());
+        assert!(!table.players[&1].is_in_hand);
+        assert_eq!(vec![0, 2], table.get_players_in_hand());
+        assert!(table.pot.get_player_stack_bet(&1).is_err());
+        assert!(!table.players[&1].owes_blinds());
+    }
+
+    #[test]
+    fn bomb_pot_needs_two_players() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        table.update_settings(ids[1], PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: Some(false), auto_muck: None, auto_rebuy: None });
+        table.update_settings(ids[2], PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: Some(false), auto_muck: None, auto_rebuy: None });
+        table.is_next_hand_bomb = true;
+
+        assert!(table.start_hand().is_ok());
+        assert!(!table.pot.is_bomb_pot());
+        assert_eq!(vec![0, 1, 2], table.get_players_in_hand());
+    }
+
+    fn setup_run_it_table(stacks: &[u64], strengths: HashMap<usize, u32>, runout_strengths: Vec<HashMap<usize, u32>>) -> (Table, Vec<Uuid>) {
+        let (mut table, ids) = setup_table(stacks, vec![Street::Betting, Street::Betting, Street::Betting], HashMap::new());
+        table.game = Box::new(MockGame { streets: vec![Street::Betting, Street::Betting, Street::Betting], strengths: strengths, runout_strengths: runout_strengths });
+        (table, ids)
+    }
+
+    #[test]
+    fn run_it_twice_splits_pot() {
+        let runout_strengths = vec![
+            HashMap::from([(0, 2), (1, 1)]),
+            HashMap::from([(0, 1), (1, 2)]),
+            HashMap::from([(0, 2), (1, 1)]),
+        ];
+        let (mut table, ids) = setup_run_it_table(&[100, 100], HashMap::new(), runout_strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        assert!(table.is_hand_in_progress);
+        assert!(table.run_it_votes.is_some());
+
+        // nobody can act while deciding
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        assert!(table.players[&0].is_in_hand);
+
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 3 }));
+        assert!(table.is_hand_in_progress);
+
+        let responses = table.handle_game_action(GameAction::RunIt(ids[1], RunItAction { times: 2 }));
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(Some(2), table.run_it_times);
+        assert_eq!(2, responses.iter().filter(|res| matches!(res, GameResponse::Broadcast(Broadcast::RunoutResult(_)))).count());
+        assert_eq!(vec![100, 100], stacks(&table));
+    }
+
+    #[test]
+    fn run_it_twice_odd_chip() {
+        let runout_strengths = vec![
+            HashMap::from([(0, 2), (2, 1)]),
+            HashMap::from([(0, 1), (2, 2)]),
+        ];
+        let (mut table, ids) = setup_run_it_table(&[100, 100, 100], HashMap::new(), runout_strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 2 }));
+        table.handle_game_action(GameAction::RunIt(ids[2], RunItAction { times: 2 }));
+
+        // pot of 201 is split 101/100, the first runout gets the odd chip
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(vec![101, 99, 100], stacks(&table));
+    }
+
+    #[test]
+    fn run_it_once_when_undecided() {
+        let strengths = HashMap::from([(0, 1), (1, 2)]);
+        let runout_strengths = vec![HashMap::new(), HashMap::new()];
+        let (mut table, ids) = setup_run_it_table(&[100, 100], strengths, runout_strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 2 }));
+
+        table.check_action_clock(Instant::now() + Duration::from_secs(31));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(Some(1), table.run_it_times);
+        assert!(!table.players[&1].is_away);
+        assert_eq!(vec![0, 200], stacks(&table));
+    }
+
+    #[test]
+    fn run_it_invalid_times() {
+        let runout_strengths = vec![HashMap::new(), HashMap::new()];
+        let (mut table, ids) = setup_run_it_table(&[100, 100], HashMap::new(), runout_strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 3 }));
+        table.handle_game_action(GameAction::RunIt(ids[0], RunItAction { times: 0 }));
+        assert!(table.run_it_votes.as_ref().unwrap().is_empty());
+    }
+
+    // Positions that showed or mucked at showdown, in the order they did so
+    fn get_showdown_responses(responses: &Vec<GameResponse>, ids: &Vec<Uuid>) -> Vec<(usize, bool)> {
+        responses.iter().filter_map(|res| match res {
+            GameResponse::Multicast(id, Multicast::ShowHandResponse(_)) => Some((ids.iter().position(|i| i == id).unwrap(), true)),
+            GameResponse::Multicast(id, Multicast::MuckHandResponse(_)) => Some((ids.iter().position(|i| i == id).unwrap(), false)),
+            _ => None,
+        }).collect()
+    }
+
+    // Lets the clock run out on everyone still deciding to show or muck
+    fn time_out_showdown(table: &mut Table) -> Vec<GameResponse> {
+        let mut responses = Vec::new();
+        let mut now = Instant::now();
+        for _ in 0..table.players.len() {
+            // the clock restarts for every player as the showdown moves on
+            now += Duration::from_secs(31);
+            responses.extend(table.check_action_clock(now));
+        }
+        responses
+    }
+
+    #[test]
+    fn last_aggressor_shows_first() {
+        let strengths = HashMap::from([(0, 100), (1, 300), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::BetRaise(BetAction { amount: 10 })));
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        let mut responses = table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(0, table.action_idx);
+        responses.extend(time_out_showdown(&mut table));
+        assert_eq!(vec![(2, true), (1, true), (0, false)], get_showdown_responses(&responses, &ids));
+        assert!(!table.shown_hands.contains_key(&0));
+    }
+
+    #[test]
+    fn showdown_order_without_aggressor() {
+        let strengths = HashMap::from([(0, 300), (1, 100), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        let mut responses = table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        responses.extend(time_out_showdown(&mut table));
+
+        assert_eq!(vec![(1, true), (2, false), (0, true)], get_showdown_responses(&responses, &ids));
+    }
+
+    #[test]
+    fn losing_hand_shown_without_auto_muck() {
+        let strengths = HashMap::from([(0, 300), (1, 100), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], strengths);
+        table.update_settings(ids[2], PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: None, auto_muck: Some(false), auto_rebuy: None });
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        let mut responses = table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        responses.extend(time_out_showdown(&mut table));
+
+        assert_eq!(vec![(1, true), (2, true), (0, true)], get_showdown_responses(&responses, &ids));
+    }
+
+    #[test]
+    fn losing_hand_decides_at_showdown() {
+        let strengths = HashMap::from([(0, 300), (1, 100), (2, 200), (3, 50)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        for pos in [3, 0, 1] {
+            table.handle_game_action(GameAction::Pot(ids[pos], PotAction::CheckCall));
+        }
+        let mut responses = table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        assert_eq!(vec![(1, true)], get_showdown_responses(&responses, &ids));
+
+        // the next hand waits on the showdown, and only the player deciding can show
+        assert!(table.showdown.is_some());
+        assert!(table.start_hand().is_err());
+        let rejected = table.handle_game_action(GameAction::Showdown(ids[3], ShowdownAction { show: true }));
+        assert_eq!(json!(StatusCode::NotYourTurn as u8), get_status(&rejected));
+
+        // player 2 shows even though they would auto muck
+        responses.extend(table.handle_game_action(GameAction::Showdown(ids[2], ShowdownAction { show: true })));
+        assert_eq!(3, table.action_idx);
+
+        // player 3 is away, so they are not waited on
+        table.set_away(ids[3], true);
+        responses.extend(table.check_action_clock(Instant::now()));
+
+        assert_eq!(vec![(1, true), (2, true), (3, false), (0, true)], get_showdown_responses(&responses, &ids));
+        assert!(table.showdown.is_none());
+        assert!(table.shown_hands.contains_key(&2));
+        assert!(!table.shown_hands.contains_key(&3));
+        assert!(table.start_hand().is_ok());
+    }
+
+    #[test]
+    fn show_cards_after_fold_win() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.players.get_mut(&2).unwrap().set_new_hand(Card::vec_from_str("AsKd").unwrap());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+
+        // cannot show while the hand is still going
+        let responses = table.show_cards(ids[2], ShowCardsAction { cards: Card::vec_from_str("As").unwrap() });
+        assert!(matches!(responses[0], GameResponse::SingleResponse(_, SingleResponse::ShowHandResponse(_))));
+        assert_eq!(1, responses.len());
+
+        let responses = table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        assert!(get_showdown_responses(&responses, &ids).is_empty());
+
+        assert_eq!(1, table.show_cards(ids[1], ShowCardsAction { cards: Card::vec_from_str("As").unwrap() }).len());
+        assert_eq!(1, table.show_cards(ids[2], ShowCardsAction { cards: Card::vec_from_str("Qh").unwrap() }).len());
+
+        let responses = table.show_cards(ids[2], ShowCardsAction { cards: Card::vec_from_str("As").unwrap() });
+        assert_eq!(vec![(2, true)], get_showdown_responses(&responses, &ids));
+        assert_eq!(Card::vec_from_str("As").unwrap(), table.shown_hands[&2]);
+
+        table.show_cards(ids[2], ShowCardsAction { cards: Card::vec_from_str("AsKd").unwrap() });
+        assert_eq!(Card::vec_from_str("AsKd").unwrap(), table.shown_hands[&2]);
+    }
+
+    #[test]
+    fn config_applies_after_hand() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+
+        assert!(table.start_hand().is_ok());
+
+        let mut config = TableConfig::new();
+        config.sb_amt = 5;
+        config.bb_amt = 10;
+        table.update_config(host, config.clone());
+        assert_eq!(2, table.bb_amt);
+        assert_eq!(Some(config.clone()), table.pending_config);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+
+        assert!(!table.is_hand_in_progress);
+        assert_eq!(10, table.bb_amt);
+        assert_eq!(config, table.config);
+        assert_eq!(None, table.pending_config);
+    }
+
+    #[test]
+    fn invalid_config_rejected() {
+        let (mut table, _) = setup_table(&[200, 200, 200, 200], vec![Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+
+        let mut config = TableConfig::new();
+        config.sb_amt = 10;
+        table.update_config(host, config);
+        assert_eq!(1, table.sb_amt);
+
+        // seat 3 is taken
+        let mut config = TableConfig::new();
+        config.seat_count = 3;
+        table.update_config(host, config);
+        assert_eq!(4, table.config.seat_count);
+    }
+
+    // Status of the first response sent back to the player
+    fn get_status(responses: &Vec<GameResponse>) -> serde_json::Value {
+        responses.iter()
+            .find_map(|res| match res {
+                GameResponse::SingleResponse(_, res) => serde_json::to_value(res).ok(),
+                _ => None,
+            })
+            .and_then(|res| res.as_object().and_then(|res| res.values().next()).map(|res| res["status"].clone()))
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    #[test]
+    fn only_host_updates_config() {
+        let (mut table, ids) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        assert!(table.claim_host(ids[0]));
+        assert!(!table.claim_host(ids[1]));
+
+        let mut config = TableConfig::new();
+        config.sb_amt = 5;
+        config.bb_amt = 10;
+        let responses = table.update_config(ids[1], config.clone());
+        assert_eq!(json!(StatusCode::NotHost as u8), get_status(&responses));
+        assert_eq!(2, table.bb_amt);
+
+        table.update_config(ids[0], config);
+        assert_eq!(10, table.bb_amt);
+    }
+
+    #[test]
+    fn buy_in_must_be_within_limits() {
+        let mut config = TableConfig::new();
+        config.min_buy_in = 100;
+        config.max_buy_in = 200;
+        let mut table = Table::with_config(config).unwrap();
+
+        table.sit_down(Uuid::new_v4(), sit_down_action(0, 50));
+        table.sit_down(Uuid::new_v4(), sit_down_action(1, 250));
+        assert!(table.players.is_empty());
+
+        table.sit_down(Uuid::new_v4(), sit_down_action(1, 100));
+        assert_eq!(100, table.players[&1].stack);
+    }
+
+    #[test]
+    fn top_up_waits_for_hand_to_end() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.add_chips(ids[0], AddChipsAction { amount: 100 });
+        assert_eq!(200, table.players[&0].stack);
+        assert_eq!(100, table.players[&0].pending_chips);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        assert!(table.start_hand().is_ok());
+
+        assert_eq!(300, table.players[&0].stack);
+        assert_eq!(0, table.players[&0].pending_chips);
+        assert_eq!(LedgerEntryType::TopUp, table.ledger.get_entries().last().unwrap().entry_type);
+    }
+
+    #[test]
+    fn add_chips_within_buy_in_limits() {
+        let (mut table, ids) = setup_table(&[0, 350, 200], vec![Street::Betting], HashMap::new());
+
+        // over the max buy-in
+        table.add_chips(ids[1], AddChipsAction { amount: 100 });
+        assert_eq!(350, table.players[&1].stack);
+
+        // rebuy under the min buy-in
+        table.add_chips(ids[0], AddChipsAction { amount: 20 });
+        assert_eq!(0, table.players[&0].stack);
+
+        table.add_chips(ids[0], AddChipsAction { amount: 100 });
+        table.add_chips(ids[1], AddChipsAction { amount: 50 });
+        assert_eq!(vec![100, 400, 200], stacks(&table));
+
+        let entry_types: Vec<LedgerEntryType> = table.ledger.get_entries().iter().map(|entry| entry.entry_type).collect();
+        assert_eq!(vec![LedgerEntryType::Rebuy, LedgerEntryType::TopUp], entry_types);
+    }
+
+    #[test]
+    fn auto_rebuy_after_busting() {
+        let strengths = HashMap::from([(0, 100), (1, 300)]);
+        let (mut table, ids) = setup_table(&[100, 100], vec![Street::Betting], strengths);
+        table.update_settings(ids[0], PlayerSettingsAction { will_straddle: None, play_in_bomb_pots: None, auto_muck: None, auto_rebuy: Some(true) });
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        assert_eq!(vec![0, 200], stacks(&table));
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(400, table.players[&0].stack);
+        assert!(table.players[&0].is_in_hand);
+        assert_eq!(LedgerEntryType::AutoRebuy, table.ledger.get_entries().last().unwrap().entry_type);
+        assert_eq!(400, table.ledger.get_entries().last().unwrap().amount);
+    }
+
+    #[test]
+    fn only_host_sees_ledger() {
+        let mut table = Table::new();
+        let host = Uuid::new_v4();
+        let player = Uuid::new_v4();
+        table.host_id = Some(host);
+        table.sit_down(player, sit_down_action(2, 150));
+
+        let ledger = |responses: Vec<GameResponse>| match &responses[0] {
+            GameResponse::SingleResponse(_, SingleResponse::LedgerResponse(res)) => serde_json::to_value(res).unwrap(),
+            _ => serde_json::Value::Null,
+        };
+        let res = ledger(table.get_ledger(player));
+        assert_eq!(json!(StatusCode::NotHost as u8), res["status"]);
+        assert_eq!(json!([]), res["entries"]);
+
+        let res = ledger(table.get_ledger(host));
+        assert_eq!(json!(StatusCode::OK as u8), res["status"]);
+        assert_eq!(1, res["entries"].as_array().unwrap().len());
+    }
+
+    #[test]
+    fn ledger_records_buy_in_and_cash_out() {
+        let mut table = Table::new();
+        let id = Uuid::new_v4();
+
+        table.sit_down(id, sit_down_action(2, 150));
+        table.stand_up(id);
+
+        let ledger: Vec<(LedgerEntryType, u64)> = table.ledger.get_entries().iter().map(|entry| (entry.entry_type, entry.amount)).collect();
+        assert_eq!(vec![(LedgerEntryType::BuyIn, 150), (LedgerEntryType::CashOut, 150)], ledger);
+        assert_eq!(Some(&0), table.ledger.get_balances().get("Player 2"));
+    }
+
+    fn get_audit_failure(responses: &Vec<GameResponse>) -> Option<&AuditReport> {
+        responses.iter().find_map(|res| match res {
+            GameResponse::Broadcast(Broadcast::AuditFailure(report)) => Some(report),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn chips_conserved_through_hand() {
+        let strengths = HashMap::from([(0, 100), (1, 300), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 50, 200], vec![Street::Betting, Street::Betting], strengths);
+
+        assert!(table.start_hand().is_ok());
+        assert_eq!(450, table.auditor.get_starting_total());
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 20 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::BetRaise(BetAction { amount: 50 })));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        let mut responses = table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        responses.extend(table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall)));
+        responses.extend(table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall)));
+
+        assert!(!table.is_hand_in_progress);
+        assert!(!table.is_halted);
+        assert!(get_audit_failure(&responses).is_none());
+        assert!(table.action_log.contains(&LoggedAction::StreetEnded { street_idx: 0 }));
+    }
+
+    #[test]
+    fn missing_chips_halt_table_at_settlement() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        table.start_next_hand = true;
+
+        assert!(table.start_hand().is_ok());
+        table.auditor.start_hand(1, &BTreeMap::from([(0, 200), (1, 200), (2, 201)]));
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+        let responses = table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+
+        let report = get_audit_failure(&responses).unwrap();
+        assert_eq!("Settlement", report.checkpoint);
+        assert_eq!(601, report.expected_total);
+        assert_eq!(600, report.actual_total);
+        assert_eq!(LoggedAction::Fold { position: 1 }, report.action_log[4]);
+
+        assert!(table.is_halted);
+        assert!(!table.start_next_hand);
+        table.resume(Instant::now());
+        assert!(table.is_paused);
+    }
+
+    #[test]
+    fn missing_chips_halt_table_after_street() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+
+        assert!(table.start_hand().is_ok());
+        table.auditor.start_hand(1, &BTreeMap::from([(0, 200), (1, 200), (2, 199)]));
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        let responses = table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        assert_eq!("End of street 0", get_audit_failure(&responses).unwrap().checkpoint);
+        assert!(table.is_hand_in_progress);
+
+        // nobody can act on a halted table
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        assert!(table.players[&1].is_in_hand);
+    }
+
+    // Runs out of cards after the first street and can not rank anyone's hand
+    struct BrokenGame {
+        streets: Vec<Street>,
+    }
+
+    impl GameVariation for BrokenGame {
+        fn get_min_player_count(& self) -> usize {
+            2
+        }
+
+        fn get_max_player_count(& self) -> usize {
+            4
+        }
+
+        fn start_normal(&mut self, _players: &mut HashMap<usize, Player>, _btn_idx: usize) -> Result<(), &str> {
+            Ok(())
+        }
+
+        fn get_streets(& self) -> Vec<Street> {
+            self.streets.clone()
+        }
+
+        fn deal_street(&mut self, _players: &mut HashMap<usize, Player>, street_idx: usize) -> Result<Vec<Card>, &str> {
+            if street_idx > 0 {
+                return Err("Not enough cards in the deck");
+            }
+            Ok(Vec::new())
+        }
+
+        fn draw_cards(&mut self, _player: &mut Player, _discards: &Vec<Card>) -> Result<Vec<Card>, &str> {
+            Ok(Vec::new())
+        }
+
+        fn evaluate_all_hands(& self, _players: &HashMap<usize, Player>) -> Vec<HashMap<usize, Rank>> {
+            vec![HashMap::new()]
+        }
+    }
+
+    fn get_hand_failure(responses: &Vec<GameResponse>) -> Option<&str> {
+        responses.iter().find_map(|res| match res {
+            GameResponse::Broadcast(Broadcast::HandFailure(err)) => Some(err.as_str()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn failed_deal_halts_table() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        table.game = Box::new(BrokenGame { streets: vec![Street::Betting, Street::Betting] });
+        table.start_next_hand = true;
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        let responses = table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        assert_eq!(Some("Could not deal street 1: Not enough cards in the deck"), get_hand_failure(&responses));
+        assert!(table.is_halted);
+        assert!(!table.start_next_hand);
+        assert!(table.is_hand_in_progress);
+    }
+
+    #[test]
+    fn failed_settlement_halts_table() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], Vec::new(), HashMap::new());
+        table.game = Box::new(BrokenGame { streets: vec![Street::Betting] });
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        let responses = table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        // the pot is left as it was instead of going missing
+        assert_eq!(Some("Could not settle the pot: No eligible player of a side pot has a hand ranking"), get_hand_failure(&responses));
+        assert!(get_audit_failure(&responses).is_none());
+        assert_eq!(6, table.pot.get_total_pot());
+        assert_eq!(vec![198, 198, 198], stacks(&table));
+        assert!(table.is_halted);
+    }
+
+    #[test]
+    fn undo_last_action() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+
+        assert!(table.start_hand().is_ok());
+        let start_pot = format!("{:?}", table.pot);
+
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 6 })));
+        let raised_pot = format!("{:?}", table.pot);
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        assert!(!table.players[&1].is_in_hand);
+
+        table.undo(host, false);
+        assert_eq!(1, table.action_idx);
+        assert!(table.players[&1].is_in_hand);
+        assert_eq!(raised_pot, format!("{:?}", table.pot));
+
+        table.undo(host, false);
+        assert_eq!(0, table.action_idx);
+        assert_eq!(start_pot, format!("{:?}", table.pot));
+        assert_eq!(None, table.last_aggressor);
+        assert_eq!(3, table.action_log.len());
+
+        // action picks back up from where it was undone to
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn undo_clock_time_out() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+
+        assert!(table.start_hand().is_ok());
+        table.check_action_clock(Instant::now() + Duration::from_secs(31));
+        assert!(!table.players[&0].is_in_hand);
+        assert!(table.players[&0].is_away);
+
+        let responses = table.undo(host, false);
+        assert_eq!(0, table.action_idx);
+        assert!(table.players[&0].is_in_hand);
+        assert!(!table.players[&0].is_away);
+        assert!(responses.iter().any(|res| matches!(res, GameResponse::Multicast(id, Multicast::AwayResponse(_)) if *id == ids[0])));
+
+        // they are back in the hand and get to act
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        assert_eq!(1, table.action_idx);
+    }
+
+    #[test]
+    fn rewind_to_street_start() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting, Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        let street_pot = format!("{:?}", table.pot);
+
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::BetRaise(BetAction { amount: 10 })));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::Fold));
+
+        table.undo(host, true);
+        assert_eq!(1, table.street_idx);
+        assert_eq!(1, table.action_idx);
+        assert!(table.players[&2].is_in_hand);
+        assert_eq!(street_pot, format!("{:?}", table.pot));
+
+        // the previous street can not be undone
+        let responses = table.undo(host, false);
+        assert!(matches!(responses[0], GameResponse::SingleResponse(_, SingleResponse::UndoResponse(_))));
+        assert_eq!(1, responses.len());
+        assert_eq!(1, table.street_idx);
+    }
+
+    #[test]
+    fn only_host_can_undo() {
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], HashMap::new());
+        table.host_id = Some(Uuid::new_v4());
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 6 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::Fold));
+        let pot = format!("{:?}", table.pot);
+        let action_log = table.action_log.clone();
+
+        for to_street_start in [false, true] {
+            let responses = table.undo(ids[0], to_street_start);
+            assert_eq!(json!(StatusCode::NotHost as u8), get_status(&responses));
+            assert_eq!(1, responses.len());
+        }
+        assert_eq!(pot, format!("{:?}", table.pot));
+        assert_eq!(action_log, table.action_log);
+        assert_eq!(2, table.action_idx);
+        assert!(!table.players[&1].is_in_hand);
+    }
+
+    #[test]
+    fn hand_history_written_after_hand() {
+        let strengths = HashMap::from([(0, 100), (1, 300), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 50, 200], vec![Street::Betting], strengths);
+        let dir = std::env::temp_dir().join(format!("hand_history_{}", Uuid::new_v4()));
+        table.set_hand_history_dir(dir.clone());
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 100 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+
+        let history = table.hand_history.clone().unwrap();
+        assert_eq!(1, history.hand_number);
+        assert_eq!(vec![200, 50, 200], history.seats.iter().map(|seat| seat.stack).collect::<Vec<u64>>());
+        assert_eq!(LoggedAction::Posted { position: 1, ante: 0, live: 1, dead: 0 }, history.actions[1]);
+        assert_eq!(LoggedAction::Posted { position: 2, ante: 0, live: 2, dead: 0 }, history.actions[2]);
+        assert_eq!(LoggedAction::BetRaise { position: 0, amount: 100 }, history.actions[3]);
+        assert_eq!(vec![
+            SidePotHistory { amount: 150, eligible_players: BTreeSet::from([0, 1, 2]) },
+            SidePotHistory { amount: 100, eligible_players: BTreeSet::from([0, 2]) },
+        ], history.side_pots);
+        assert_eq!(vec![HashMap::from([(1, 150), (2, 100)])], history.winnings);
+        assert_eq!(BTreeMap::from([(0, 100), (1, 150), (2, 200)]), history.final_stacks);
+
+        let path = dir.join(history.get_file_name());
+        assert!(path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rake_taken_from_contested_pots() {
+        let strengths = HashMap::from([(0, 300), (1, 100), (2, 200)]);
+        let (mut table, ids) = setup_table(&[200, 200, 200], vec![Street::Betting], strengths);
+        table.pot.set_rake_settings(RakeSettings { percent: 10.0, cap: Some(5) });
+
+        assert!(table.start_hand().is_ok());
+        table.handle_game_action(GameAction::Pot(ids[0], PotAction::BetRaise(BetAction { amount: 40 })));
+        table.handle_game_action(GameAction::Pot(ids[1], PotAction::CheckCall));
+        table.handle_game_action(GameAction::Pot(ids[2], PotAction::CheckCall));
+        time_out_showdown(&mut table);
+
+        assert!(table.showdown.is_none());
+        assert!(!table.is_halted);
+        assert_eq!(vec![275, 160, 160], stacks(&table));
+        let history = table.hand_history.clone().unwrap();
+        assert_eq!(5, history.rake);
+        assert_eq!(vec![HashMap::from([(0, 115)])], history.winnings);
+
+        // nothing is raked when nobody calls
+        assert!(table.start_hand().is_ok());
+        while table.is_hand_in_progress {
+            let id = ids[table.action_idx];
+            table.handle_game_action(GameAction::Pot(id, PotAction::Fold));
+        }
+        assert!(!table.is_halted);
+        assert_eq!(0, table.hand_history.clone().unwrap().rake);
+        assert_eq!(600, stacks(&table).iter().sum::<u64>() + 5);
+    }
+
+    #[test]
+    fn hand_history_write_failure_sent_to_host() {
+        let (mut table, ids) = setup_table(&[200, 200], vec![Street::Betting], HashMap::new());
+        let host = Uuid::new_v4();
+        table.host_id = Some(host);
+        // a file where the directory should be
+        let path = std::env::temp_dir().join(format!("hand_history_{}", Uuid::new_v4()));
+        std::fs::write(&path, "").unwrap();
+        table.set_hand_history_dir(path.clone());
+
+        assert!(table.start_hand().is_ok());
+        let responses = table.handle_game_action(GameAction::Pot(ids[0], PotAction::Fold));
+
+        let error = responses.iter().find_map(|res| match res {
+            GameResponse::SingleResponse(id, SingleResponse::HandHistoryError(err)) if *id == host => Some(err),
+            _ => None,
+        });
+        assert!(error.unwrap().starts_with("Could not write hand history for hand 1"));
+        assert!(!table.is_hand_in_progress);
+        let _ = std::fs::remove_file(&path);
     }
 }
@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+
+use crate::poker::{ClockSettings, StraddleSettings, StraddleType, BombPotSettings};
+use crate::poker::games::{GameVariation, FiveCardDraw, DeuceToSevenDraw, Badugi, TexasHoldem, Omaha, SevenCardStud, ShortDeckHoldem, CustomGame, GameSpec};
+use crate::poker::pots::{Pot, NoLimitPot, PotLimitPot, FixedLimitPot, RakeSettings};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum GameType {
+    FiveCardDraw,
+    SingleDrawDeuceToSeven,
+    TripleDrawDeuceToSeven,
+    Badugi,
+    Badeucy,        // Badugi and 2-7 split
+    Badacey,        // Badugi and A-5 split
+    TexasHoldem,
+    TexasHoldemHiLo,
+    Omaha,          // 4 hole cards
+    FiveCardOmaha,
+    SixCardOmaha,
+    OmahaHiLo,      // eight or better
+    FiveCardOmahaHiLo,
+    SevenCardStud,
+    SevenCardStudHiLo,  // eight or better
+    Razz,
+    ShortDeckHoldem,    // 6+, without the twos through fives
+    DoubleBoardHoldem,
+    DoubleBoardOmaha,
+    Custom,         // played by the table's custom_game spec
+}
+
+impl GameType {
+    // Betting the game is played with unless the table says otherwise
+    pub fn get_default_betting_structure(& self) -> BettingStructure {
+        match self {
+            GameType::FiveCardDraw | GameType::TexasHoldem | GameType::TexasHoldemHiLo
+                | GameType::ShortDeckHoldem | GameType::DoubleBoardHoldem | GameType::Custom => BettingStructure::NoLimit,
+            GameType::SingleDrawDeuceToSeven | GameType::TripleDrawDeuceToSeven
+                | GameType::Badugi | GameType::Badeucy | GameType::Badacey
+                | GameType::SevenCardStud | GameType::SevenCardStudHiLo | GameType::Razz => BettingStructure::FixedLimit,
+            GameType::Omaha | GameType::FiveCardOmaha | GameType::SixCardOmaha
+                | GameType::OmahaHiLo | GameType::FiveCardOmahaHiLo | GameType::DoubleBoardOmaha => BettingStructure::PotLimit,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BettingStructure {
+    NoLimit,
+    PotLimit,
+    FixedLimit,
+}
+
+// Everything the host can set up about a table. Changes made during a hand take effect once the hand is over
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TableConfig {
+    pub game_type: GameType,
+    pub betting_structure: Option<BettingStructure>,  // None to use the game's default
+    pub sb_amt: u64,            // the bring-in in stud games
+    pub bb_amt: u64,            // the small bet in stud games
+    pub ante_amt: u64,
+    pub is_button_ante: bool,   // only the button antes, instead of anyone posting blinds
+    pub min_buy_in: u64,
+    pub max_buy_in: u64,
+    pub seat_count: usize,      // can not be more than the game allows
+    pub clock_settings: ClockSettings,
+    pub straddle_settings: StraddleSettings,
+    pub bomb_pot_settings: BombPotSettings,
+    pub rake_settings: RakeSettings,
+    pub custom_game: Option<GameSpec>,      // only used when game_type is Custom
+}
+
+impl TableConfig {
+    pub fn new() -> TableConfig {
+        TableConfig {
+            game_type: GameType::FiveCardDraw,
+            betting_structure: None,
+            sb_amt: 1,
+            bb_amt: 2,
+            ante_amt: 0,
+            is_button_ante: false,
+            min_buy_in: 40,
+            max_buy_in: 400,
+            seat_count: 6,
+            clock_settings: ClockSettings::new(),
+            straddle_settings: StraddleSettings::new(),
+            bomb_pot_settings: BombPotSettings::new(),
+            rake_settings: RakeSettings::new(),
+            custom_game: None,
+        }
+    }
+
+    pub fn create_game(& self) -> Box<dyn GameVariation + Send> {
+        match self.game_type {
+            GameType::FiveCardDraw => Box::new(FiveCardDraw::new()),
+            GameType::SingleDrawDeuceToSeven => Box::new(DeuceToSevenDraw::new_single_draw()),
+            GameType::TripleDrawDeuceToSeven => Box::new(DeuceToSevenDraw::new_triple_draw()),
+            GameType::Badugi => Box::new(Badugi::new()),
+            GameType::Badeucy => Box::new(Badugi::new_badeucy()),
+            GameType::Badacey => Box::new(Badugi::new_badacey()),
+            GameType::TexasHoldem => Box::new(TexasHoldem::new()),
+            GameType::TexasHoldemHiLo => Box::new(TexasHoldem::new_hi_lo()),
+            GameType::Omaha => Box::new(Omaha::new(4)),
+            GameType::FiveCardOmaha => Box::new(Omaha::new(5)),
+            GameType::SixCardOmaha => Box::new(Omaha::new(6)),
+            GameType::OmahaHiLo => Box::new(Omaha::new_hi_lo(4)),
+            GameType::FiveCardOmahaHiLo => Box::new(Omaha::new_hi_lo(5)),
+            GameType::SevenCardStud => Box::new(SevenCardStud::new()),
+            GameType::SevenCardStudHiLo => Box::new(SevenCardStud::new_hi_lo()),
+            GameType::Razz => Box::new(SevenCardStud::new_razz()),
+            GameType::ShortDeckHoldem => Box::new(ShortDeckHoldem::new()),
+            GameType::DoubleBoardHoldem => Box::new(TexasHoldem::new_double_board()),
+            GameType::DoubleBoardOmaha => Box::new(Omaha::new_double_board(4)),
+            GameType::Custom => Box::new(CustomGame::new(self.custom_game.clone().unwrap_or_default())),
+        }
+    }
+
+    // Custom games bring their own default along with the rest of their rules
+    pub fn get_betting_structure(& self) -> BettingStructure {
+        let default = match &self.custom_game {
+            Some(spec) if self.game_type == GameType::Custom => spec.betting_structure,
+            _ => self.game_type.get_default_betting_structure(),
+        };
+        self.betting_structure.unwrap_or(default)
+    }
+
+    pub fn create_pot(& self) -> Box<dyn Pot + Send> {
+        let mut pot: Box<dyn Pot + Send> = match self.get_betting_structure() {
+            BettingStructure::NoLimit => Box::new(NoLimitPot::new()),
+            BettingStructure::PotLimit => Box::new(PotLimitPot::new()),
+            BettingStructure::FixedLimit => Box::new(FixedLimitPot::new()),
+        };
+        pot.set_rake_settings(self.rake_settings);
+        pot
+    }
+
+    pub fn validate(& self) -> Result<(), std::string::String> {
+        if self.game_type == GameType::Custom {
+            match &self.custom_game {
+                Some(spec) => spec.validate()?,
+                None => return Err(format!("Custom games need a game spec")),
+            }
+        }
+        let game = self.create_game();
+
+        if self.sb_amt == 0 {
+            return Err(format!("Small blind must be greater than 0"));
+        }
+        if self.bb_amt == 0 {
+            return Err(format!("Big blind must be greater than 0"));
+        }
+        if self.sb_amt > self.bb_amt {
+            return Err(format!("Small blind ({}) can not be larger than the big blind ({})", self.sb_amt, self.bb_amt));
+        }
+        if self.min_buy_in < self.bb_amt {
+            return Err(format!("Minimum buy-in ({}) must cover the big blind ({})", self.min_buy_in, self.bb_amt));
+        }
+        if self.min_buy_in > self.max_buy_in {
+            return Err(format!("Minimum buy-in ({}) can not be larger than the maximum buy-in ({})", self.min_buy_in, self.max_buy_in));
+        }
+        if self.seat_count < game.get_min_player_count() || self.seat_count > game.get_max_player_count() {
+            return Err(format!("Seat count must be between {} and {}", game.get_min_player_count(), game.get_max_player_count()));
+        }
+        if self.clock_settings.action_secs == 0 {
+            return Err(format!("Action time must be greater than 0"));
+        }
+        if self.clock_settings.starting_time_bank_secs > self.clock_settings.max_time_bank_secs {
+            return Err(format!("Starting time bank can not be larger than the max time bank"));
+        }
+        if self.is_button_ante && !game.has_blinds() {
+            return Err(format!("Button antes are only allowed in games with blinds"));
+        }
+        if self.is_button_ante && self.ante_amt == 0 {
+            return Err(format!("Ante must be greater than 0 when the button antes"));
+        }
+        if self.straddle_settings.straddle_type != StraddleType::None && (!game.has_blinds() || self.is_button_ante) {
+            return Err(format!("Straddling is only allowed in games with blinds"));
+        }
+        if self.straddle_settings.straddle_type != StraddleType::None && self.straddle_settings.max_straddles == 0 {
+            return Err(format!("Max straddles must be at least 1 when straddling is allowed"));
+        }
+        if self.bomb_pot_settings.ante_amt == 0 {
+            return Err(format!("Bomb pot ante must be greater than 0"));
+        }
+        if !(0.0..=100.0).contains(&self.rake_settings.percent) {
+            return Err(format!("Rake must be between 0 and 100 percent"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for TableConfig {
+    fn default() -> TableConfig {
+        TableConfig::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json};
+
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid() {
+        assert_eq!(Ok(()), TableConfig::new().validate());
+    }
+
+    #[test]
+    fn invalid_blinds() {
+        let mut config = TableConfig::new();
+        config.bb_amt = 0;
+        assert!(config.validate().is_err());
+
+        config.bb_amt = 2;
+        config.sb_amt = 5;
+        assert!(config.validate().is_err());
+
+        config.sb_amt = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_buy_ins() {
+        let mut config = TableConfig::new();
+        config.min_buy_in = 500;
+        assert!(config.validate().is_err());
+
+        config.min_buy_in = 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_rake() {
+        let mut config = TableConfig::new();
+        config.rake_settings.percent = 101.0;
+        assert!(config.validate().is_err());
+
+        config.rake_settings.percent = -1.0;
+        assert!(config.validate().is_err());
+
+        config.rake_settings.percent = 5.0;
+        assert_eq!(Ok(()), config.validate());
+    }
+
+    #[test]
+    fn seat_count_limited_by_game() {
+        let mut config = TableConfig::new();
+        config.seat_count = 7;
+        assert!(config.validate().is_err());
+
+        config.seat_count = 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn deserialize_partial_config() {
+        let value = json!({
+            "gameType": "FiveCardDraw",
+            "sbAmt": 5,
+            "bbAmt": 10,
+            "maxBuyIn": 1000,
+            "straddleSettings": {"straddleType": "UnderTheGun", "maxStraddles": 2},
+        });
+        let config: TableConfig = serde_json::from_value(value).expect("An error occurred!");
+
+        assert_eq!(5, config.sb_amt);
+        assert_eq!(10, config.bb_amt);
+        assert_eq!(1000, config.max_buy_in);
+        assert_eq!(40, config.min_buy_in);
+        assert_eq!(StraddleType::UnderTheGun, config.straddle_settings.straddle_type);
+        assert_eq!(ClockSettings::new(), config.clock_settings);
+        assert_eq!(Ok(()), config.validate());
+    }
+
+    #[test]
+    fn omaha_defaults_to_pot_limit() {
+        let mut config: TableConfig = serde_json::from_value(json!({"gameType": "FiveCardOmaha"})).expect("An error occurred!");
+        assert_eq!(BettingStructure::PotLimit, config.get_betting_structure());
+        assert_eq!(8, config.create_game().get_max_player_count());
+
+        config.betting_structure = Some(BettingStructure::NoLimit);
+        assert_eq!(BettingStructure::NoLimit, config.get_betting_structure());
+
+        config.game_type = GameType::TexasHoldem;
+        config.betting_structure = None;
+        assert_eq!(BettingStructure::NoLimit, config.get_betting_structure());
+    }
+
+    #[test]
+    fn double_board_games() {
+        let mut config: TableConfig = serde_json::from_value(json!({"gameType": "DoubleBoardOmaha"})).expect("An error occurred!");
+        assert_eq!(BettingStructure::PotLimit, config.get_betting_structure());
+        assert_eq!(9, config.create_game().get_max_player_count());
+
+        config.game_type = GameType::DoubleBoardHoldem;
+        assert_eq!(BettingStructure::NoLimit, config.get_betting_structure());
+        assert_eq!(Ok(()), config.validate());
+    }
+
+    #[test]
+    fn custom_game_from_spec() {
+        let mut config: TableConfig = serde_json::from_value(json!({"gameType": "Custom"})).expect("An error occurred!");
+        assert!(config.validate().is_err());
+
+        config = serde_json::from_value(json!({
+            "gameType": "Custom",
+            "customGame": {
+                "name": "Pineapple Hi-Lo",
+                "streets": [{"downCards": 3}, {"boardCards": 3}, {"boardCards": 1}, {"boardCards": 1}],
+                "bettingStructure": "PotLimit",
+                "objectives": ["high", "low8"],
+            },
+        })).expect("An error occurred!");
+        assert_eq!(Ok(()), config.validate());
+        assert_eq!(BettingStructure::PotLimit, config.get_betting_structure());
+        assert_eq!(4, config.create_game().get_streets().len());
+
+        config.custom_game.as_mut().unwrap().objectives.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn stud_can_not_be_straddled() {
+        let mut config: TableConfig = serde_json::from_value(json!({"gameType": "Razz", "seatCount": 8})).expect("An error occurred!");
+        assert_eq!(BettingStructure::FixedLimit, config.get_betting_structure());
+        assert_eq!(Ok(()), config.validate());
+
+        config.straddle_settings.straddle_type = StraddleType::UnderTheGun;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn button_ante_needs_an_ante() {
+        let mut config: TableConfig = serde_json::from_value(json!({"gameType": "ShortDeckHoldem", "isButtonAnte": true})).expect("An error occurred!");
+        assert!(config.validate().is_err());
+
+        config.ante_amt = 2;
+        assert_eq!(Ok(()), config.validate());
+
+        config.straddle_settings.straddle_type = StraddleType::UnderTheGun;
+        assert!(config.validate().is_err());
+
+        config.straddle_settings.straddle_type = StraddleType::None;
+        config.game_type = GameType::SevenCardStud;
+        assert!(config.validate().is_err());
+    }
+}